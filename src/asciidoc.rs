@@ -0,0 +1,65 @@
+use crate::{Justification, Table};
+
+/// Escapes a cell for an AsciiDoc table: pipes are backslash-escaped and
+/// newlines become hard line breaks (` +` at the end of a line)
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace("\r\n", "\n").replace('\n', " +\n")
+}
+
+/// Returns the cols attribute alignment for a justification
+fn align_spec(justification: Justification) -> &'static str {
+    match justification {
+        Justification::Left => "<",
+        Justification::Center => "^",
+        Justification::Right | Justification::Decimal => ">",
+    }
+}
+
+impl Table {
+    /// Renders the table as an AsciiDoc `|===` table with a header row, for
+    /// Asciidoctor and Antora. The cols attribute carries each column's alignment
+    /// (`>` for right-justified and Decimal columns, `^` for centered). Pipes in
+    /// cells are escaped and newlines become hard line breaks. Headers use the
+    /// same text as the text renderer.
+    pub fn to_asciidoc(&self) -> String {
+        let cols: Vec<&str> = (0..self.columns.len()).map(|idx| align_spec(self.cell_align(idx).justification)).collect();
+        let mut out = format!("[cols=\"{}\",options=\"header\"]\n|===\n", cols.join(","));
+        let mut push_line = |cells: &mut dyn Iterator<Item = String>| {
+            for (idx, cell) in cells.enumerate() {
+                if idx > 0 {
+                    out.push(' ');
+                }
+                out.push('|');
+                out.push_str(&cell);
+            }
+            out.push('\n');
+        };
+        push_line(&mut self.columns.iter().map(|col| escape_cell(&self.header_text(col))));
+        for row in &self.rows {
+            push_line(&mut row.cells.iter().enumerate().map(|(idx, cell)| escape_cell(&self.redacted(idx, cell))));
+        }
+        out.push_str("|===\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+
+    #[test]
+    fn test_to_asciidoc() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Size", 0, Justification::Decimal).unwrap(),
+            Column::new("Notes", 0, Justification::Center).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["a|b".to_string(), "1.5".to_string(), "one\ntwo".to_string()]).unwrap();
+        table.add_row(vec!["c".to_string(), "10".to_string(), String::new()]).unwrap();
+        assert_eq!(
+            table.to_asciidoc(),
+            "[cols=\"<,>,^\",options=\"header\"]\n|===\n|Name |Size |Notes\n|a\\|b |1.5 |one +\ntwo\n|c |10 |\n|===\n"
+        );
+    }
+}
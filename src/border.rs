@@ -0,0 +1,632 @@
+use crate::{text_width, Table};
+
+/// Lines drawn around and between the columns of the text render
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BorderStyle {
+    /// Columns separated by a space, with a rule of '=' under the header
+    #[default]
+    None,
+    /// A frame of Unicode box-drawing characters around the table and between
+    /// the columns, with a rule under the header (BorderChars::UNICODE)
+    Unicode,
+    /// The same frame in ASCII, as the MySQL and psql clients draw it, for
+    /// terminals and mail that can't show box-drawing characters (BorderChars::ASCII)
+    Ascii,
+    /// Box-drawing characters with rounded corners (BorderChars::ROUNDED)
+    Rounded,
+    /// Double box-drawing lines (BorderChars::DOUBLE)
+    Double,
+    /// Heavy box-drawing lines (BorderChars::HEAVY)
+    Heavy,
+    /// A frame drawn with your own characters
+    Custom(BorderChars),
+}
+
+/// Characters drawing a border: the corners, the junctions where rules meet the
+/// vertical lines, and the lines themselves. The rule under the header has its
+/// own junctions and line, so it can stand out from the rest (e.g. `╞══╪══╡`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderChars {
+    pub top_left: char,
+    pub top_junction: char,
+    pub top_right: char,
+    /// Left end, crossings, and right end of rules between rows
+    pub left_junction: char,
+    pub cross: char,
+    pub right_junction: char,
+    pub bottom_left: char,
+    pub bottom_junction: char,
+    pub bottom_right: char,
+    /// Left end, crossings, and right end of the rule under the header
+    pub header_left: char,
+    pub header_cross: char,
+    pub header_right: char,
+    /// Line of the top and bottom rules and rules between rows
+    pub horizontal: char,
+    /// Line of the rule under the header
+    pub header_horizontal: char,
+    /// Line of the left and right edges and between columns
+    pub vertical: char,
+}
+
+impl BorderChars {
+    /// Box-drawing characters: `┌──┬──┐`, `│  │  │`, `├──┼──┤`, `└──┴──┘`
+    pub const UNICODE: BorderChars = BorderChars {
+        top_left: '┌',
+        top_junction: '┬',
+        top_right: '┐',
+        left_junction: '├',
+        cross: '┼',
+        right_junction: '┤',
+        bottom_left: '└',
+        bottom_junction: '┴',
+        bottom_right: '┘',
+        header_left: '├',
+        header_cross: '┼',
+        header_right: '┤',
+        horizontal: '─',
+        header_horizontal: '─',
+        vertical: '│',
+    };
+
+    /// Box-drawing characters with rounded corners: `╭──┬──╮`, `╰──┴──╯`
+    pub const ROUNDED: BorderChars = BorderChars {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..BorderChars::UNICODE
+    };
+
+    /// Double lines: `╔══╦══╗`, `║  ║  ║`, `╠══╬══╣`, `╚══╩══╝`
+    pub const DOUBLE: BorderChars = BorderChars {
+        top_left: '╔',
+        top_junction: '╦',
+        top_right: '╗',
+        left_junction: '╠',
+        cross: '╬',
+        right_junction: '╣',
+        bottom_left: '╚',
+        bottom_junction: '╩',
+        bottom_right: '╝',
+        header_left: '╠',
+        header_cross: '╬',
+        header_right: '╣',
+        horizontal: '═',
+        header_horizontal: '═',
+        vertical: '║',
+    };
+
+    /// Heavy lines: `┏━━┳━━┓`, `┃  ┃  ┃`, `┣━━╋━━┫`, `┗━━┻━━┛`
+    pub const HEAVY: BorderChars = BorderChars {
+        top_left: '┏',
+        top_junction: '┳',
+        top_right: '┓',
+        left_junction: '┣',
+        cross: '╋',
+        right_junction: '┫',
+        bottom_left: '┗',
+        bottom_junction: '┻',
+        bottom_right: '┛',
+        header_left: '┣',
+        header_cross: '╋',
+        header_right: '┫',
+        horizontal: '━',
+        header_horizontal: '━',
+        vertical: '┃',
+    };
+
+    /// ASCII characters: `+--+--+`, `|  |  |`
+    pub const ASCII: BorderChars = BorderChars {
+        top_left: '+',
+        top_junction: '+',
+        top_right: '+',
+        left_junction: '+',
+        cross: '+',
+        right_junction: '+',
+        bottom_left: '+',
+        bottom_junction: '+',
+        bottom_right: '+',
+        header_left: '+',
+        header_cross: '+',
+        header_right: '+',
+        horizontal: '-',
+        header_horizontal: '-',
+        vertical: '|',
+    };
+}
+
+impl BorderStyle {
+    /// Returns the characters the style draws with, or None for no border
+    pub fn chars(self) -> Option<BorderChars> {
+        match self {
+            BorderStyle::None => None,
+            BorderStyle::Unicode => Some(BorderChars::UNICODE),
+            BorderStyle::Ascii => Some(BorderChars::ASCII),
+            BorderStyle::Rounded => Some(BorderChars::ROUNDED),
+            BorderStyle::Double => Some(BorderChars::DOUBLE),
+            BorderStyle::Heavy => Some(BorderChars::HEAVY),
+            BorderStyle::Custom(chars) => Some(chars),
+        }
+    }
+}
+
+/// Which lines of a border are drawn. Without a border, only `header` applies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderLines {
+    /// Rule above the header
+    pub top: bool,
+    /// Rule under the header
+    pub header: bool,
+    /// Rule beneath the last row
+    pub bottom: bool,
+    /// Lines down the left and right edges
+    pub sides: bool,
+    /// Lines between columns. Without them, the columns inside the frame are
+    /// set apart by their padding alone, as in a borderless table.
+    pub columns: bool,
+}
+
+impl Default for BorderLines {
+    fn default() -> Self {
+        BorderLines { top: true, header: true, bottom: true, sides: true, columns: true }
+    }
+}
+
+/// Familiar table layouts, each a combination of border settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableStyle {
+    /// The default: columns separated by a space, with a rule of '=' under the header
+    Plain,
+    /// Like psql: `|` between columns and a `-+-` rule under the header, with no
+    /// outer frame
+    Psql,
+    /// Like the mysql client: a full ASCII frame (BorderStyle::Ascii)
+    Mysql,
+    /// A GitHub-flavored markdown table: `|` at the edges and between columns,
+    /// and a `|---|` rule under the header
+    Github,
+    /// Columns separated by a space, with no rules at all
+    Minimal,
+    /// A Unicode box around the table and a rule under the header, with no lines
+    /// between the columns
+    Boxed,
+}
+
+/// Border characters of TableStyle::Github
+const GITHUB: BorderChars = BorderChars {
+    header_left: '|',
+    header_cross: '|',
+    header_right: '|',
+    ..BorderChars::ASCII
+};
+
+/// Spaces set between a cell's text and the lines (or, without a border, the
+/// neighbouring columns) on either side of it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Padding {
+    pub left: usize,
+    pub right: usize,
+}
+
+/// What a render puts around and between the cells of each line: the text before
+/// the first column, between columns, and after the last
+#[derive(Debug, Clone)]
+pub(crate) struct Frame {
+    pub(crate) left: String,
+    pub(crate) separator: String,
+    pub(crate) right: String,
+    /// The line at the left and right edges ("" without one) and the text between
+    /// columns
+    edge: String,
+    gap: String,
+    chars: Option<BorderChars>,
+    lines: BorderLines,
+    padding: Padding,
+    /// Line of the rule under the header, if not the border's own
+    header_fill: Option<char>,
+}
+
+impl Frame {
+    /// Returns the width the frame adds to a line of the given number of columns
+    pub(crate) fn overhead(&self, columns: usize) -> usize {
+        text_width(&self.left) + text_width(&self.right) + columns.saturating_sub(1) * text_width(&self.separator)
+    }
+
+    /// Joins padded cells into a line
+    pub(crate) fn join(&self, cells: &[String]) -> String {
+        let mut line = self.left.clone();
+        line.push_str(&cells.join(&self.separator));
+        line.push_str(&self.right);
+        line
+    }
+
+    /// Draws a rule across columns of the given widths: fill under the cells and
+    /// their padding, and the given characters where the rule crosses the left
+    /// edge, the lines between columns, and the right edge. Spaces around a line
+    /// are filled too, but a blank separator between borderless columns is kept.
+    fn rule(&self, widths: &[usize], fill: char, (left, junction, right): (char, char, char)) -> String {
+        let under = |text: &str, cross: char| -> String {
+            if self.chars.is_none() && text.trim().is_empty() {
+                return text.to_string();
+            }
+            text.chars().map(|c| if c.is_whitespace() { fill } else { cross }).collect()
+        };
+        let pad = self.padding.left + self.padding.right;
+        let cells: Vec<String> = widths.iter().map(|&width| String::from_iter(std::iter::repeat_n(fill, width + pad))).collect();
+        let mut line = under(&self.edge, left);
+        line.push_str(&cells.join(&under(&self.gap, junction)));
+        line.push_str(&under(&self.edge, right));
+        line
+    }
+
+    /// Returns the rule under the header, if it is drawn
+    pub(crate) fn header_rule(&self, widths: &[usize]) -> Option<String> {
+        if !self.lines.header {
+            return None;
+        }
+        Some(match self.chars {
+            Some(c) => {
+                let fill = self.header_fill.unwrap_or(c.header_horizontal);
+                self.rule(widths, fill, (c.header_left, c.header_cross, c.header_right))
+            }
+            None => self.rule(widths, self.header_fill.unwrap_or('='), (' ', '+', ' ')),
+        })
+    }
+
+    /// Returns the rule drawn between rows: the border's row rule, or a '-' under
+    /// each column without a border
+    pub(crate) fn row_rule(&self, widths: &[usize]) -> String {
+        match self.chars {
+            Some(c) => self.rule(widths, c.horizontal, (c.left_junction, c.cross, c.right_junction)),
+            None => self.rule(widths, '-', (' ', '+', ' ')),
+        }
+    }
+
+    /// Returns a line with no text in any column, inside the frame's lines
+    pub(crate) fn blank_line(&self, widths: &[usize]) -> String {
+        let cells: Vec<String> = widths.iter().map(|&width| " ".repeat(width)).collect();
+        self.join(&cells)
+    }
+
+    /// Returns the rule above the header, if the frame has one
+    pub(crate) fn top_rule(&self, widths: &[usize]) -> Option<String> {
+        let c = self.chars.filter(|_| self.lines.top)?;
+        Some(self.rule(widths, c.horizontal, (c.top_left, c.top_junction, c.top_right)))
+    }
+
+    /// Returns the rule beneath the last row, if the frame has one
+    pub(crate) fn bottom_rule(&self, widths: &[usize]) -> Option<String> {
+        let c = self.chars.filter(|_| self.lines.bottom)?;
+        Some(self.rule(widths, c.horizontal, (c.bottom_left, c.bottom_junction, c.bottom_right)))
+    }
+}
+
+impl Table {
+    /// Sets the lines drawn around and between the columns of the text render
+    /// (default BorderStyle::None). Bordered cells are set off from the lines by
+    /// a space on each side unless the cell padding is set.
+    pub fn set_border(&mut self, style: BorderStyle) {
+        self.options.border = style;
+    }
+
+    /// Sets which lines of the border are drawn (default all of them). Without
+    /// the sides, the edge cells keep their space of padding.
+    pub fn set_border_lines(&mut self, lines: BorderLines) {
+        self.options.border_lines = lines;
+    }
+
+    /// Sets a rule to be drawn between rows after every n rows, e.g. 1 for a rule
+    /// between every two rows, which sets multiline rows apart (0, the default,
+    /// for none). Bordered tables draw it with the border's junctions.
+    pub fn set_row_separators(&mut self, n: usize) {
+        self.options.row_separators = n;
+    }
+
+    /// Returns true if a rule between rows is drawn before the row at idx
+    pub(crate) fn rule_before(&self, idx: usize) -> bool {
+        let every = self.options.row_separators;
+        every > 0 && idx > 0 && idx.is_multiple_of(every)
+    }
+
+    /// Sets whether an empty line is put between rows, so the boundaries of
+    /// multiline rows show without a border (off by default). Where a row
+    /// separator rule is drawn, it takes the place of the empty line.
+    pub fn set_blank_line_between_rows(&mut self, blank: bool) {
+        self.options.blank_line_between_rows = blank;
+    }
+
+    /// Returns true if a rule or an empty line is drawn before the row at idx
+    pub(crate) fn line_before(&self, idx: usize) -> bool {
+        self.rule_before(idx) || (self.options.blank_line_between_rows && idx > 0)
+    }
+
+    /// Returns the number of rules and empty lines between rows in a full render
+    pub(crate) fn between_row_lines(&self) -> usize {
+        (1..self.rows.len()).filter(|&idx| self.line_before(idx)).count()
+    }
+
+    /// Sets the border and border lines to one of the familiar table layouts
+    pub fn set_style(&mut self, style: TableStyle) {
+        let all = BorderLines::default();
+        let (border, lines) = match style {
+            TableStyle::Plain => (BorderStyle::None, all),
+            TableStyle::Psql => (BorderStyle::Ascii, BorderLines { top: false, bottom: false, sides: false, ..all }),
+            TableStyle::Mysql => (BorderStyle::Ascii, all),
+            TableStyle::Github => (BorderStyle::Custom(GITHUB), BorderLines { top: false, bottom: false, ..all }),
+            TableStyle::Minimal => (BorderStyle::None, BorderLines { header: false, ..all }),
+            TableStyle::Boxed => (BorderStyle::Unicode, BorderLines { columns: false, ..all }),
+        };
+        self.options.border = border;
+        self.options.border_lines = lines;
+    }
+
+    /// Sets the spaces between each cell's text and the lines on either side of
+    /// it, for all columns, in the header, rules, and rows alike. By default
+    /// bordered tables pad each side with one space and borderless ones with none.
+    pub fn set_cell_padding(&mut self, left: usize, right: usize) {
+        self.options.padding = Some(Padding { left, right });
+    }
+
+    /// Goes back to the border style's own cell padding
+    pub fn clear_cell_padding(&mut self) {
+        self.options.padding = None;
+    }
+
+    /// Sets the text joining the columns, e.g. " | ", "  ", or "│", in place of
+    /// the border style's own line (a space without a border). Rules across the
+    /// table put a junction under each visible character of it; cell padding is
+    /// still added on either side.
+    pub fn set_column_separator(&mut self, separator: &str) {
+        self.options.column_separator = Some(separator.to_string());
+    }
+
+    /// Goes back to the border style's own line between columns
+    pub fn clear_column_separator(&mut self) {
+        self.options.column_separator = None;
+    }
+
+    /// Sets the character the rule under the header is drawn with, e.g. '-',
+    /// '─', or '·', in place of '=' (or the border's header line). The
+    /// junctions where it crosses a border are unchanged.
+    pub fn set_header_rule_char(&mut self, c: char) {
+        self.options.header_rule_char = Some(c);
+    }
+
+    /// Goes back to the border style's own rule under the header
+    pub fn clear_header_rule_char(&mut self) {
+        self.options.header_rule_char = None;
+    }
+
+    /// Shows or hides the rule under the header (shown by default), the same
+    /// as setting the header field of the border lines
+    pub fn set_show_header_rule(&mut self, show: bool) {
+        self.options.border_lines.header = show;
+    }
+
+    /// Returns the frame of the current border style
+    pub(crate) fn frame(&self) -> Frame {
+        let lines = self.options.border_lines;
+        let chars = self.options.border.chars();
+        let (edge, gap) = match chars {
+            None => (String::new(), " ".to_string()),
+            Some(chars) => {
+                let line = |drawn: bool| if drawn { chars.vertical.to_string() } else { String::new() };
+                (line(lines.sides), line(lines.columns))
+            }
+        };
+        let gap = self.options.column_separator.clone().unwrap_or(gap);
+        let default = if chars.is_some() { Padding { left: 1, right: 1 } } else { Padding::default() };
+        let padding = self.options.padding.unwrap_or(default);
+        let (left_pad, right_pad) = (" ".repeat(padding.left), " ".repeat(padding.right));
+        Frame {
+            left: format!("{}{}", edge, left_pad),
+            separator: format!("{}{}{}", right_pad, gap, left_pad),
+            right: format!("{}{}", right_pad, edge),
+            edge,
+            gap,
+            chars,
+            lines,
+            padding,
+            header_fill: self.options.header_rule_char,
+        }
+    }
+
+    /// Returns the number of lines above the first row in a full render: the
+    /// title, the top rule, the header, the units row, and the rule under the header
+    pub(crate) fn header_line_count(&self) -> usize {
+        let frame = self.frame();
+        let top = frame.chars.is_some() && frame.lines.top;
+        self.title_lines() + self.header_height() + usize::from(self.has_units()) + usize::from(top) + usize::from(frame.lines.header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_unicode_border() {
+        let mut table = Table::new(vec![
+            Column::new("Host", 0, Justification::Left).unwrap(),
+            Column::new("Load", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "0.5".to_string()]).unwrap();
+        table.add_row(vec!["db\nprimary".to_string(), "12".to_string()]).unwrap();
+        table.add_section("Standby");
+        table.add_row(vec!["db-2".to_string(), "1".to_string()]).unwrap();
+        table.set_border(BorderStyle::Unicode);
+        let expected = "\
+            ┌─────────┬──────┐\n\
+            │ Host    │ Load │\n\
+            ├─────────┼──────┤\n\
+            │ web-1   │  0.5 │\n\
+            │ db      │   12 │\n\
+            │ primary │      │\n\
+            │ Standby        │\n\
+            │ db-2    │    1 │\n\
+            └─────────┴──────┘\n";
+        assert_eq!(table.to_string(), expected);
+        assert_eq!(table.header_line_count(), 3);
+
+        table.set_max_width(16);
+        let rendered = table.to_string();
+        assert!(rendered.lines().all(|line| text_width(line) == 16), "{}", rendered);
+    }
+
+    #[test]
+    fn test_ascii_border() {
+        let mut table = Table::with_headers(["Id", "Name"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.set_border(BorderStyle::Ascii);
+        assert_eq!(table.to_string(), "+----+-------+\n| Id | Name  |\n+----+-------+\n| 1  | Alice |\n+----+-------+\n");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+    }
+
+    #[test]
+    fn test_custom_border() {
+        let mut table = Table::with_headers(["Id", "Name"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        let chars = BorderChars {
+            header_left: '╞',
+            header_cross: '╪',
+            header_right: '╡',
+            header_horizontal: '═',
+            ..BorderChars::UNICODE
+        };
+        table.set_border(BorderStyle::Custom(chars));
+        assert_eq!(table.to_string(), "┌────┬───────┐\n│ Id │ Name  │\n╞════╪═══════╡\n│ 1  │ Alice │\n└────┴───────┘\n");
+        assert_eq!(BorderStyle::Ascii.chars(), Some(BorderChars::ASCII));
+    }
+
+    #[test]
+    fn test_row_separators() {
+        let mut table = Table::with_headers(["Id", "Note"]).unwrap();
+        for (id, note) in [("1", "a"), ("2", "b\nc"), ("3", "d")] {
+            table.add_row(vec![id.to_string(), note.to_string()]).unwrap();
+        }
+        table.set_row_separators(2);
+        assert_eq!(table.to_string(), "Id Note\n== ====\n1  a   \n2  b   \n   c   \n-- ----\n3  d   \n");
+        table.set_row_separators(1);
+        table.set_border(BorderStyle::Ascii);
+        let rendered = table.to_string();
+        assert!(rendered.contains("| 1  | a    |\n+----+------+\n| 2  | b    |\n|    | c    |\n+----+------+\n"), "{}", rendered);
+        assert_eq!(table.estimated_render_len(), rendered.len());
+        assert_eq!(table.render_rows(1..3).unwrap(), "+----+------+\n| 2  | b    |\n|    | c    |\n+----+------+\n| 3  | d    |\n");
+    }
+
+    #[test]
+    fn test_border_variants() {
+        let mut table = Table::with_headers(["Id"]).unwrap();
+        table.add_row(vec!["1".to_string()]).unwrap();
+        table.set_border(BorderStyle::Rounded);
+        assert_eq!(table.to_string(), "╭────╮\n│ Id │\n├────┤\n│ 1  │\n╰────╯\n");
+        table.set_border(BorderStyle::Double);
+        assert_eq!(table.to_string(), "╔════╗\n║ Id ║\n╠════╣\n║ 1  ║\n╚════╝\n");
+        table.set_border(BorderStyle::Heavy);
+        assert_eq!(table.to_string(), "┏━━━━┓\n┃ Id ┃\n┣━━━━┫\n┃ 1  ┃\n┗━━━━┛\n");
+    }
+
+    #[test]
+    fn test_table_styles() {
+        let mut table = Table::new(vec![
+            Column::new("Id", 0, Justification::Right).unwrap(),
+            Column::new("Name", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.set_style(TableStyle::Psql);
+        assert_eq!(table.to_string(), " Id | Name  \n----+-------\n  1 | Alice \n");
+        table.set_style(TableStyle::Github);
+        assert_eq!(table.to_string(), "| Id | Name  |\n|----|-------|\n|  1 | Alice |\n");
+        table.set_style(TableStyle::Minimal);
+        assert_eq!(table.to_string(), "Id Name \n 1 Alice\n");
+        assert_eq!(table.header_line_count(), 1);
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+        table.set_style(TableStyle::Mysql);
+        assert!(table.to_string().starts_with("+----+-------+\n"));
+        table.set_border_lines(BorderLines { columns: false, ..Default::default() });
+        assert_eq!(table.to_string(), "+-----------+\n| Id  Name  |\n+-----------+\n|  1  Alice |\n+-----------+\n");
+        table.set_style(TableStyle::Plain);
+        assert_eq!(table.to_string(), "Id Name \n== =====\n 1 Alice\n");
+    }
+
+    #[test]
+    fn test_boxed_style() {
+        let mut table = Table::new(vec![
+            Column::new("Id", 0, Justification::Right).unwrap(),
+            Column::new("Name", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.add_row(vec!["22".to_string(), "Bob\nSmith".to_string()]).unwrap();
+        table.set_style(TableStyle::Boxed);
+        assert_eq!(
+            table.to_string(),
+            "┌───────────┐\n│ Id  Name  │\n├───────────┤\n│  1  Alice │\n│ 22  Bob   │\n│     Smith │\n└───────────┘\n"
+        );
+        assert_eq!(table.render_options().border_lines, BorderLines { columns: false, ..Default::default() });
+
+        // Without side lines either, only the rules remain
+        table.set_border_lines(BorderLines { sides: false, columns: false, ..Default::default() });
+        assert!(table.to_string().starts_with("───────────\n Id  Name  \n───────────\n"), "{}", table);
+    }
+
+    #[test]
+    fn test_cell_padding() {
+        let mut table = Table::with_headers(["Id", "Name"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.set_cell_padding(1, 1);
+        assert_eq!(table.to_string(), " Id   Name  \n==== =======\n 1    Alice \n");
+        table.set_border(BorderStyle::Ascii);
+        table.set_cell_padding(2, 0);
+        assert_eq!(table.to_string(), "+----+-------+\n|  Id|  Name |\n+----+-------+\n|  1 |  Alice|\n+----+-------+\n");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+        table.clear_cell_padding();
+        assert_eq!(table.to_string(), "+----+-------+\n| Id | Name  |\n+----+-------+\n| 1  | Alice |\n+----+-------+\n");
+    }
+
+    #[test]
+    fn test_column_separator() {
+        let mut table = Table::with_headers(["Id", "Name"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.set_column_separator(" | ");
+        assert_eq!(table.to_string(), "Id | Name \n===+======\n1  | Alice\n");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+        table.set_column_separator("  ");
+        assert_eq!(table.to_string(), "Id  Name \n==  =====\n1   Alice\n");
+        table.set_border(BorderStyle::Unicode);
+        table.set_column_separator("┃");
+        assert_eq!(table.to_string(), "┌────┬───────┐\n│ Id ┃ Name  │\n├────┼───────┤\n│ 1  ┃ Alice │\n└────┴───────┘\n");
+    }
+
+    #[test]
+    fn test_header_rule_char() {
+        let mut table = Table::with_headers(["Id", "Name"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Alice".to_string()]).unwrap();
+        table.set_header_rule_char('·');
+        assert_eq!(table.to_string(), "Id Name \n·· ·····\n1  Alice\n");
+        table.set_border(BorderStyle::Unicode);
+        table.set_header_rule_char('═');
+        assert!(table.to_string().contains("├════┼═══════┤\n"));
+        table.set_show_header_rule(false);
+        assert_eq!(table.to_string(), "┌────┬───────┐\n│ Id │ Name  │\n│ 1  │ Alice │\n└────┴───────┘\n");
+        assert_eq!(table.header_line_count(), 2);
+    }
+
+    #[test]
+    fn test_blank_line_between_rows() {
+        let mut table = Table::with_headers(["Id", "Note"]).unwrap();
+        for (id, note) in [("1", "a\nb"), ("2", "c"), ("3", "d")] {
+            table.add_row(vec![id.to_string(), note.to_string()]).unwrap();
+        }
+        table.set_blank_line_between_rows(true);
+        table.set_trim_trailing_padding(true);
+        assert_eq!(table.to_string(), "Id Note\n== ====\n1  a\n   b\n\n2  c\n\n3  d\n");
+        table.set_trim_trailing_padding(false);
+        table.set_row_separators(2);
+        table.set_border(BorderStyle::Ascii);
+        let rendered = table.to_string();
+        assert!(rendered.contains("|    | b    |\n|    |      |\n| 2  | c    |\n+----+------+\n| 3  |"), "{}", rendered);
+        assert_eq!(table.estimated_render_len(), rendered.len());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Table;
+
+/// Adds rows to a table in one fluent expression, holding on to the first error
+/// until finish is called. Created by Table::chain_rows.
+///
+/// Once a row is rejected, later rows are skipped so the error reported is the
+/// one that caused the table to stop growing; rows added before it are kept.
+#[derive(Debug)]
+pub struct RowChain<'a> {
+    table: &'a mut Table,
+    added: usize,
+    error: Option<String>,
+}
+
+impl RowChain<'_> {
+    /// Adds a row, or records why it couldn't be added (see Table::add_row)
+    pub fn add_row<I, S>(&mut self, row: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if self.error.is_none() {
+            match self.table.add_row(row.into_iter().map(Into::into).collect()) {
+                Ok(()) => self.added += 1,
+                Err(e) => self.error = Some(format!("RowChain::add_row: row {}: {}", self.added, e)),
+            }
+        }
+        self
+    }
+
+    /// Returns the first error from the chained rows, naming the (0-based) position
+    /// of the failed row within the chain
+    pub fn finish(&mut self) -> Result<(), String> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Table {
+    /// Starts a chain of rows added with a single error check at the end, e.g.
+    /// `table.chain_rows().add_row(["Ada", "36"]).add_row(["Grace", "85"]).finish()?`
+    pub fn chain_rows(&mut self) -> RowChain<'_> {
+        RowChain { table: self, added: 0, error: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_rows() {
+        let mut table = Table::with_headers(["a", "b"]).unwrap();
+        let result = table.chain_rows()
+            .add_row(["1", "2"])
+            .add_row(vec!["3".to_string()])
+            .add_row(["5", "6"])
+            .finish();
+        assert_eq!(result, Err("RowChain::add_row: row 1: Table::add_row: row has 1 columns, expected 2".to_string()));
+        assert_eq!(table.row_count(), 1);
+
+        assert_eq!(table.chain_rows().add_row(["7", "8"]).finish(), Ok(()));
+        assert_eq!(table.row(1), Some(&["7".to_string(), "8".to_string()][..]));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{text_width, truncate_line, Table};
+
+/// Widest rendered table (in characters) that to_slack keeps as an aligned code
+/// block; code blocks wider than this wrap in typical Slack windows.
+pub const SLACK_CODE_BLOCK_WIDTH: usize = 80;
+
+/// Default message length limit for render_code_block (Discord's 2000 characters)
+pub const DEFAULT_MESSAGE_LIMIT: usize = 2000;
+
+/// Escapes the characters Slack mrkdwn treats as control sequences
+fn escape_mrkdwn(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Puts a zero-width space between adjacent backticks so text inside a code block
+/// can't close its ``` fence
+fn break_backtick_runs(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut prev = None;
+    for c in s.chars() {
+        if c == '`' && prev == Some('`') {
+            out.push('\u{200B}');
+        }
+        out.push(c);
+        prev = Some(c);
+    }
+    out
+}
+
+impl Table {
+    /// Renders the table for posting to Slack. If the rendered table is at most
+    /// SLACK_CODE_BLOCK_WIDTH characters wide it is sent as an aligned code block
+    /// (with runs of backticks broken up so cells can't end it early); otherwise
+    /// each row becomes a mrkdwn field list ("*Header:* value" lines) with a blank
+    /// line between rows, which stays readable at any width.
+    pub fn to_slack(&self) -> String {
+        let rendered = self.to_string();
+        let width = rendered.lines().map(text_width).max().unwrap_or(0);
+        if width <= SLACK_CODE_BLOCK_WIDTH {
+            return format!("```\n{}```\n", break_backtick_runs(&escape_mrkdwn(&rendered)));
+        }
+
+        let headers: Vec<String> = self.columns.iter().map(|col| escape_mrkdwn(&self.header_text(col))).collect();
+        let mut out = String::new();
+        for (idx, row) in self.rows.iter().enumerate() {
+            if idx > 0 {
+                out.push('\n');
+            }
+            for (idx, (header, value)) in headers.iter().zip(&row.cells).enumerate() {
+                out.push_str(&format!("*{}:* {}\n", header, escape_mrkdwn(&self.redacted(idx, value))));
+            }
+        }
+        out
+    }
+
+    /// Renders the table as fenced (```) code blocks for chat platforms, using the
+    /// DEFAULT_MESSAGE_LIMIT. See render_code_block_with_limit.
+    pub fn render_code_block(&self, max_width: usize) -> Vec<String> {
+        self.render_code_block_with_limit(max_width, DEFAULT_MESSAGE_LIMIT)
+    }
+
+    /// Renders the table as fenced (```) code blocks no wider than max_width, one
+    /// per message. Wide columns are truncated (widest first) to fit, and lines that
+    /// still don't fit are cut. When the output would exceed message_limit characters,
+    /// rows are split across several blocks, each repeating the header; a row's lines
+    /// stay together unless the row alone is over the limit. Runs of backticks in
+    /// the rendered lines are broken up with zero-width spaces so a cell can't close
+    /// the fence.
+    pub fn render_code_block_with_limit(&self, max_width: usize, message_limit: usize) -> Vec<String> {
+        let mut fitted = self.clone();
+        fitted.fit_to_width(max_width);
+        let rendered = fitted.to_string();
+        let mut lines = rendered.lines().map(|line| break_backtick_runs(truncate_line(line, max_width).trim_end()));
+
+        // Top rule, header, units, and separator repeat in every block; each row's lines form
+        // one group, and any legend lines that follow are single-line groups
+        let header: Vec<String> = lines.by_ref().take(fitted.header_line_count()).collect();
+        let layout = fitted.layout();
+        let mut groups: Vec<Vec<String>> = fitted.rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                let rule = usize::from(fitted.line_before(idx));
+                lines.by_ref().take(rule + fitted.row_height(&layout, row)).collect()
+            })
+            .collect();
+        groups.extend(lines.map(|line| vec![line]));
+
+        let len = |lines: &[String]| lines.iter().map(|l| l.chars().count() + 1).sum::<usize>();
+        // Opening "```\n" and closing "```"
+        let base_len = 7 + len(&header);
+
+        let mut blocks = Vec::new();
+        let mut block = header.clone();
+        let mut block_len = base_len;
+        for group in groups {
+            let group_len = len(&group);
+            if block_len + group_len > message_limit && block.len() > header.len() {
+                blocks.push(std::mem::replace(&mut block, header.clone()));
+                block_len = base_len;
+            }
+            for line in group {
+                let line_len = line.chars().count() + 1;
+                if block_len + line_len > message_limit && block.len() > header.len() {
+                    blocks.push(std::mem::replace(&mut block, header.clone()));
+                    block_len = base_len;
+                }
+                block_len += line_len;
+                block.push(line);
+            }
+        }
+        blocks.push(block);
+
+        blocks
+            .into_iter()
+            .map(|lines| format!("```\n{}\n```", lines.join("\n")))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_slack() {
+        let mut table = Table::new(vec![
+            Column::new("Host", 0, Justification::Left).unwrap(),
+            Column::new("Status", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "<ok>".to_string()]).unwrap();
+        assert_eq!(table.to_slack(), "```\nHost  Status\n===== ======\nweb-1 &lt;ok&gt;  \n```\n");
+
+        table.add_row(vec!["db-1".to_string(), "x".repeat(90)]).unwrap();
+        let slack = table.to_slack();
+        assert!(slack.starts_with("*Host:* web-1\n*Status:* &lt;ok&gt;\n\n*Host:* db-1\n"));
+        assert!(!slack.contains("```"));
+    }
+
+    #[test]
+    fn test_render_code_block() {
+        let mut table = Table::new(vec![
+            Column::new("Id", 0, Justification::Right).unwrap(),
+            Column::new("Description", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["1".to_string(), "a fairly long description".to_string()]).unwrap();
+        table.add_row(vec!["2".to_string(), "two\nlines".to_string()]).unwrap();
+
+        let blocks = table.render_code_block(16);
+        assert_eq!(blocks, ["```\nId Description\n== =============\n 1 a fairly l...\n 2 two\n   lines\n```"]);
+
+        // Each block repeats the header and keeps multiline rows together
+        let blocks = table.render_code_block_with_limit(16, 60);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].ends_with(" 1 a fairly l...\n```"));
+        assert_eq!(blocks[1], "```\nId Description\n== =============\n 2 two\n   lines\n```");
+        assert!(blocks.iter().all(|b| b.chars().count() <= 60));
+    }
+
+    #[test]
+    fn test_code_block_fences() {
+        let mut table = Table::with_headers(["Note"]).unwrap();
+        table.add_row(vec!["```\n@everyone".to_string()]).unwrap();
+        let blocks = table.render_code_block(40);
+        assert_eq!(blocks, ["```\nNote\n=========\n`\u{200B}`\u{200B}`\n@everyone\n```"]);
+        assert_eq!(table.to_slack(), "```\nNote     \n=========\n`\u{200B}`\u{200B}`      \n@everyone\n```\n");
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::Table;
+
+/// A row sent through a collector, with the optional ordering tag it was pushed with
+#[derive(Debug)]
+struct Message {
+    tag: Option<u64>,
+    cells: Vec<String>,
+}
+
+/// A cloneable, Send handle that worker threads push rows through.
+/// Created by Table::into_collector; the rows are assembled into the table by
+/// the matching TableAssembly once every clone of the collector is dropped.
+#[derive(Debug, Clone)]
+pub struct TableCollector {
+    sender: Sender<Message>,
+    column_count: usize,
+}
+
+/// The receiving side of a TableCollector that assembles the final table
+#[derive(Debug)]
+pub struct TableAssembly {
+    table: Table,
+    receiver: Receiver<Message>,
+}
+
+impl TableCollector {
+    /// Sends a row to be added in arrival order
+    ///
+    /// # Errors
+    /// - If number of values doesn't match number of columns
+    /// - If the TableAssembly has been dropped
+    pub fn push(&self, row: Vec<String>) -> Result<(), String> {
+        self.send(None, row)
+    }
+
+    /// Sends a row with an ordering tag (e.g. the index of the input it came from);
+    /// tagged rows are assembled in ascending tag order regardless of arrival order
+    ///
+    /// # Errors
+    /// - If number of values doesn't match number of columns
+    /// - If the TableAssembly has been dropped
+    pub fn push_tagged(&self, tag: u64, row: Vec<String>) -> Result<(), String> {
+        self.send(Some(tag), row)
+    }
+
+    fn send(&self, tag: Option<u64>, cells: Vec<String>) -> Result<(), String> {
+        if cells.len() != self.column_count {
+            return Err(format!(
+                "TableCollector::push: row has {} columns, expected {}",
+                cells.len(),
+                self.column_count
+            ));
+        }
+        self.sender
+            .send(Message { tag, cells })
+            .map_err(|_| "TableCollector::push: the table assembly has been dropped".to_string())
+    }
+}
+
+impl TableAssembly {
+    /// Waits until every TableCollector clone has been dropped, then appends the
+    /// collected rows to the table: tagged rows first in ascending tag order (ties
+    /// keep arrival order), followed by untagged rows in arrival order.
+    ///
+    /// # Errors
+    /// - If the rows exceed the table limits (see Table::add_row)
+    pub fn finish(mut self) -> Result<Table, String> {
+        let mut tagged = Vec::new();
+        let mut untagged = Vec::new();
+        for message in self.receiver.iter() {
+            match message.tag {
+                Some(tag) => tagged.push((tag, message.cells)),
+                None => untagged.push(message.cells),
+            }
+        }
+        tagged.sort_by_key(|(tag, _)| *tag);
+        for row in tagged.into_iter().map(|(_, cells)| cells).chain(untagged) {
+            self.table.add_row(row)?;
+        }
+        Ok(self.table)
+    }
+}
+
+impl Table {
+    /// Converts the table into a collector for concurrent row production.
+    /// Clone the returned TableCollector into worker threads, drop the clones when
+    /// done, and call TableAssembly::finish to get the table with all rows added.
+    pub fn into_collector(self) -> (TableCollector, TableAssembly) {
+        let (sender, receiver) = mpsc::channel();
+        let collector = TableCollector { sender, column_count: self.columns.len() };
+        (collector, TableAssembly { table: self, receiver })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_tagged_order() {
+        let table = Table::with_headers(["Worker", "Item"]).unwrap();
+        let (collector, assembly) = table.into_collector();
+
+        let handles: Vec<_> = (0..4u64)
+            .map(|worker| {
+                let collector = collector.clone();
+                std::thread::spawn(move || {
+                    for item in (0..5u64).rev() {
+                        let tag = item * 4 + worker;
+                        collector.push_tagged(tag, vec![worker.to_string(), tag.to_string()]).unwrap();
+                    }
+                })
+            })
+            .collect();
+        collector.push(vec!["main".to_string(), "last".to_string()]).unwrap();
+        assert!(collector.push(vec!["too few".to_string()]).is_err());
+        drop(collector);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let table = assembly.finish().unwrap();
+        assert_eq!(table.row_count(), 21);
+        let items: Vec<String> = (0..20).map(|i| table.row(i).unwrap()[1].clone()).collect();
+        let expected: Vec<String> = (0..20).map(|i: u64| i.to_string()).collect();
+        assert_eq!(items, expected);
+        assert_eq!(table.row(20).unwrap(), ["main", "last"]);
+    }
+
+    #[test]
+    fn test_collector_after_assembly_dropped() {
+        let (collector, assembly) = Table::with_headers(["A"]).unwrap().into_collector();
+        drop(assembly);
+        assert!(collector.push(vec!["x".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+use std::borrow::Cow;
+use std::io::{self, IsTerminal, Write};
+
+use crate::pager::terminal_size;
+use crate::width::escape_len;
+use crate::{Table, TextView};
+
+/// Standard stream a console write goes to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn is_terminal(self) -> bool {
+        match self {
+            Stream::Stdout => io::stdout().is_terminal(),
+            Stream::Stderr => io::stderr().is_terminal(),
+        }
+    }
+}
+
+/// A piece of rendered text: either plain text or the parameters of an ANSI
+/// "select graphic rendition" (ESC [ ... m) sequence
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Style(Vec<u16>),
+}
+
+/// Splits text into plain runs and SGR sequences. Other escape sequences, such
+/// as OSC hyperlinks, are dropped whole, since a console that doesn't understand
+/// ANSI would print them raw. SGR parameters too large to be codes are left out.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('\x1b') {
+        if start > 0 {
+            segments.push(Segment::Text(&rest[..start]));
+        }
+        let Some(params) = rest[start + 1..].strip_prefix('[') else {
+            rest = &rest[start + escape_len(&rest[start..])..];
+            continue;
+        };
+        // Parameters are digits and ';', ended by a final byte in '@'..='~'
+        let end = params.find(|c: char| !c.is_ascii_digit() && c != ';').unwrap_or(params.len());
+        if params[end..].starts_with('m') {
+            let codes = params[..end]
+                .split(';')
+                .filter_map(|code| if code.is_empty() { Some(0) } else { code.parse().ok() })
+                .collect();
+            segments.push(Segment::Style(codes));
+        }
+        let final_len = params[end..].chars().next().map_or(0, char::len_utf8);
+        rest = &params[end + final_len..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+/// Console text attribute for an SGR foreground color (0-7, in ANSI order). ANSI
+/// numbers the colors red=1, green=2, blue=4, while the console uses blue=1,
+/// green=2, red=4.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn legacy_color(ansi: u16) -> u16 {
+    let ansi = ansi & 7;
+    ((ansi & 1) << 2) | (ansi & 2) | ((ansi & 4) >> 2)
+}
+
+/// Applies SGR codes to a console text attribute, starting from the attribute the
+/// console had before any styling (used for resets). The arguments of extended
+/// colors (38/48;5;n and 38/48;2;r;g;b) are skipped rather than read as codes.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn apply_sgr(mut attribute: u16, codes: &[u16], default: u16) -> u16 {
+    const FOREGROUND: u16 = 0x0f;
+    const INTENSITY: u16 = 0x08;
+    let mut codes = codes.iter().copied();
+    while let Some(code) = codes.next() {
+        attribute = match code {
+            38 | 48 => {
+                let args = match codes.next() {
+                    Some(5) => 1,
+                    Some(2) => 3,
+                    _ => 0,
+                };
+                codes.by_ref().take(args).for_each(drop);
+                attribute
+            }
+            0 => default,
+            1 => attribute | INTENSITY,
+            22 => (attribute & !INTENSITY) | (default & INTENSITY),
+            30..=37 => (attribute & !0x07) | legacy_color(code - 30),
+            39 => (attribute & !FOREGROUND) | (default & FOREGROUND),
+            90..=97 => (attribute & !FOREGROUND) | legacy_color(code - 90) | INTENSITY,
+            _ => attribute,
+        };
+    }
+    attribute
+}
+
+/// Writes rendered text, including any ANSI styling, to stdout or stderr. On
+/// Windows consoles that can't interpret ANSI sequences (before Windows 10) the
+/// styling is applied through the console API instead; everywhere else, and when
+/// the stream is redirected, the text is written unchanged.
+pub(crate) fn write_styled(stream: Stream, text: &str) -> io::Result<()> {
+    #[cfg(windows)]
+    if let Some(console) = windows::LegacyConsole::detect(stream) {
+        return console.write(stream, text);
+    }
+    match stream {
+        Stream::Stdout => {
+            let mut out = io::stdout().lock();
+            out.write_all(text.as_bytes())?;
+            out.flush()
+        }
+        Stream::Stderr => io::stderr().lock().write_all(text.as_bytes()),
+    }
+}
+
+/// Whether colors should be left out of output to the stream: when it isn't a
+/// terminal, or when the NO_COLOR environment variable is set to a non-empty value
+fn color_disabled(stream: Stream) -> bool {
+    !stream.is_terminal() || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+impl Table {
+    /// Prints the table to stdout. On a terminal, wide columns are truncated (widest
+    /// first) so the table fits its width; colors are left out when stdout isn't a
+    /// terminal or NO_COLOR is set.
+    pub fn print(&self) -> io::Result<()> {
+        self.print_to(Stream::Stdout)
+    }
+
+    /// Prints the table to stderr, fitting it to the terminal like print
+    pub fn eprint(&self) -> io::Result<()> {
+        self.print_to(Stream::Stderr)
+    }
+
+    /// Fits the table to the width of the terminal (see fit_to_width), returning
+    /// false if the width couldn't be found
+    pub fn fit_to_terminal(&mut self) -> bool {
+        match terminal_size() {
+            Some((columns, _)) => {
+                self.fit_to_width(columns);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn print_to(&self, stream: Stream) -> io::Result<()> {
+        let mut table = Cow::Borrowed(self);
+        if stream.is_terminal() {
+            if let Some((columns, _)) = terminal_size().filter(|&(columns, _)| self.layout().line_len() > columns) {
+                table.to_mut().fit_to_width(columns);
+            }
+        }
+        write_styled(stream, &table.render_for(stream))
+    }
+
+    /// Renders the table for output to the stream, leaving colors out when the
+    /// stream isn't a terminal or NO_COLOR is set
+    pub(crate) fn render_for(&self, stream: Stream) -> String {
+        let color = !self.options.no_color && !color_disabled(stream);
+        TextView { table: self, color }.to_string()
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::io::{self, Write};
+
+    use super::{apply_sgr, segments, Segment, Stream};
+
+    type Handle = *mut c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    // Win32 struct layouts; only ScreenBufferInfo::attributes is read
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct Coord {
+        x: i16,
+        y: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct SmallRect {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct ScreenBufferInfo {
+        size: Coord,
+        cursor_position: Coord,
+        attributes: u16,
+        window: SmallRect,
+        maximum_window_size: Coord,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+        fn GetConsoleScreenBufferInfo(console: Handle, info: *mut ScreenBufferInfo) -> i32;
+        fn SetConsoleTextAttribute(console: Handle, attributes: u16) -> i32;
+    }
+
+    /// A console that doesn't interpret ANSI sequences, with its original text attribute
+    pub(super) struct LegacyConsole {
+        handle: Handle,
+        default: u16,
+    }
+
+    impl LegacyConsole {
+        /// Returns None when the stream isn't a console (so it is redirected and
+        /// should get the text unchanged) or when the console accepted virtual
+        /// terminal processing and understands ANSI itself
+        pub(super) fn detect(stream: Stream) -> Option<Self> {
+            let std_handle = match stream {
+                Stream::Stdout => STD_OUTPUT_HANDLE,
+                Stream::Stderr => STD_ERROR_HANDLE,
+            };
+            // SAFETY: plain Win32 calls on a standard handle with valid out pointers
+            unsafe {
+                let handle = GetStdHandle(std_handle);
+                let mut mode = 0;
+                if handle.is_null() || GetConsoleMode(handle, &mut mode) == 0 {
+                    return None;
+                }
+                if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                    || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+                {
+                    return None;
+                }
+                let mut info = ScreenBufferInfo::default();
+                if GetConsoleScreenBufferInfo(handle, &mut info) == 0 {
+                    return None;
+                }
+                Some(LegacyConsole { handle, default: info.attributes })
+            }
+        }
+
+        pub(super) fn write(&self, stream: Stream, text: &str) -> io::Result<()> {
+            let mut out: Box<dyn Write> = match stream {
+                Stream::Stdout => Box::new(io::stdout().lock()),
+                Stream::Stderr => Box::new(io::stderr().lock()),
+            };
+            let mut attribute = self.default;
+            for segment in segments(text) {
+                match segment {
+                    Segment::Text(text) => out.write_all(text.as_bytes())?,
+                    Segment::Style(codes) => {
+                        // Text already written must reach the console before its color changes
+                        out.flush()?;
+                        attribute = apply_sgr(attribute, &codes, self.default);
+                        // SAFETY: the handle came from GetStdHandle and is a console
+                        unsafe { SetConsoleTextAttribute(self.handle, attribute) };
+                    }
+                }
+            }
+            out.flush()?;
+            // SAFETY: as above
+            unsafe { SetConsoleTextAttribute(self.handle, self.default) };
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnsiColor;
+
+    #[test]
+    fn test_segments() {
+        let painted = format!("a {} b\x1b[2Kc", AnsiColor::Red.paint("hot"));
+        assert_eq!(
+            segments(&painted),
+            [
+                Segment::Text("a "),
+                Segment::Style(vec![31]),
+                Segment::Text("hot"),
+                Segment::Style(vec![0]),
+                Segment::Text(" b"),
+                Segment::Text("c"),
+            ]
+        );
+        assert_eq!(segments("\x1b[1;32mok"), [Segment::Style(vec![1, 32]), Segment::Text("ok")]);
+        assert_eq!(
+            segments("\x1b]8;;http://example.com\x07link\x1b]8;;\x1b\\ done"),
+            [Segment::Text("link"), Segment::Text(" done")]
+        );
+        assert_eq!(segments("\x1b[1;99999;38;2;0;0;0mx"), [Segment::Style(vec![1, 38, 2, 0, 0, 0]), Segment::Text("x")]);
+    }
+
+    #[test]
+    fn test_legacy_attributes() {
+        // Light gray on black, the usual console default
+        let default = 0x07;
+        assert_eq!(apply_sgr(default, &[31], default), 0x04);
+        assert_eq!(apply_sgr(default, &[34], default), 0x01);
+        assert_eq!(apply_sgr(default, &[33], default), 0x06);
+        assert_eq!(apply_sgr(default, &[1, 36], default), 0x0b);
+        assert_eq!(apply_sgr(0x1e, &[0], default), default);
+        assert_eq!(apply_sgr(default, &[38, 2, 0, 0, 0, 1], default), 0x0f);
+        // The background is left alone
+        assert_eq!(apply_sgr(0x17, &[32], 0x17), 0x12);
+    }
+}
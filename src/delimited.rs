@@ -0,0 +1,225 @@
+use std::borrow::Cow;
+
+use crate::{Column, Justification, OutputFormat, Table};
+
+/// How Table::to_tsv_with handles tabs and line breaks inside cells, which
+/// would otherwise split a field or a record
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TsvEscaping {
+    /// Write tabs, newlines, carriage returns, and backslashes as \t, \n, \r,
+    /// and \\, so values can be restored exactly
+    #[default]
+    Escape,
+    /// Replace each tab and line break with a space, so every field is plain
+    /// text for cut and awk
+    Strip,
+}
+
+/// Options for Table::to_tsv_with
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TsvOptions {
+    pub escaping: TsvEscaping,
+}
+
+/// Quotes a CSV field if it contains a comma, quote, or line break, doubling
+/// any quotes inside (RFC 4180)
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Splits CSV text into records of fields (RFC 4180). Quoted fields may hold
+/// commas, doubled quotes, and line breaks; records end at LF or CRLF, and blank
+/// lines are skipped.
+fn csv_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let start = line;
+            loop {
+                match chars.next() {
+                    None => return Err(format!("line {}: unterminated quoted field", start)),
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => {
+                        line += usize::from(c == '\n');
+                        field.push(c);
+                    }
+                }
+            }
+            if let Some(&c) = chars.peek().filter(|&&c| !matches!(c, ',' | '\n' | '\r')) {
+                return Err(format!("line {}: unexpected '{}' after a closing quote", line, c));
+            }
+        } else {
+            while let Some(c) = chars.next_if(|&c| !matches!(c, ',' | '\n' | '\r')) {
+                field.push(c);
+            }
+        }
+        record.push(field);
+        match chars.next() {
+            Some(',') => continue,
+            Some('\r') => {
+                chars.next_if_eq(&'\n');
+            }
+            _ => {}
+        }
+        line += 1;
+        let record = std::mem::take(&mut record);
+        if record.len() > 1 || !record[0].is_empty() {
+            records.push(record);
+        }
+        if chars.peek().is_none() {
+            return Ok(records);
+        }
+    }
+}
+
+/// Makes a value safe for a TSV field
+fn tsv_field(value: &str, escaping: TsvEscaping) -> Cow<'_, str> {
+    if !value.contains(['\t', '\n', '\r', '\\']) {
+        return Cow::Borrowed(value);
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (escaping, c) {
+            (TsvEscaping::Escape, '\t') => out.push_str("\\t"),
+            (TsvEscaping::Escape, '\n') => out.push_str("\\n"),
+            (TsvEscaping::Escape, '\r') => out.push_str("\\r"),
+            (TsvEscaping::Escape, '\\') => out.push_str("\\\\"),
+            (TsvEscaping::Strip, '\r') if chars.peek() == Some(&'\n') => {}
+            (TsvEscaping::Strip, '\t' | '\n' | '\r') => out.push(' '),
+            (_, c) => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+impl Table {
+    /// Renders the table as CSV: a header line of column names (each column's
+    /// CSV alias or name), then one line per row. Fields with commas, quotes, or
+    /// line breaks are quoted.
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',', csv_field)
+    }
+
+    /// Reads a table from CSV (RFC 4180). The first record holds the column names
+    /// (left-justified, not truncated). Fields may be quoted, with quotes inside
+    /// doubled, and quoted fields may span lines. Records with too few fields are
+    /// padded with empty cells and extra fields are ignored. Blank lines are skipped.
+    ///
+    /// # Errors
+    /// - If a quoted field is unterminated or followed by text before the next comma
+    /// - If there is no header record or a column name is empty
+    /// - If the rows exceed the table limits
+    pub fn from_csv(text: &str) -> Result<Table, String> {
+        let mut records = csv_records(text).map_err(|e| format!("Table::from_csv: {}", e))?.into_iter();
+        let header = records.next().ok_or_else(|| "Table::from_csv: no header record".to_string())?;
+        let columns = header
+            .into_iter()
+            .map(|name| Column::new(name, 0, Justification::Left))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::from_csv: {}", e))?;
+        let mut table = Table::new(columns).map_err(|e| format!("Table::from_csv: {}", e))?;
+        let column_count = table.columns.len();
+        for mut record in records {
+            record.resize(column_count, String::new());
+            table.add_row(record).map_err(|e| format!("Table::from_csv: {}", e))?;
+        }
+        Ok(table)
+    }
+
+    /// Renders the table as TSV with the default TsvOptions (tabs and newlines
+    /// in cells escaped)
+    pub fn to_tsv(&self) -> String {
+        self.to_tsv_with(&TsvOptions::default())
+    }
+
+    /// Renders the table as tab-separated values for cut, awk, and other line
+    /// tools: a header line of column names (each column's CSV alias or name),
+    /// then one line per row. Tabs and line breaks in cells are escaped or
+    /// replaced with spaces, as the options say, so each row is one line.
+    pub fn to_tsv_with(&self, options: &TsvOptions) -> String {
+        self.to_delimited('\t', |value| tsv_field(value, options.escaping))
+    }
+
+    /// Joins the header and each row's redacted cells with the separator, one
+    /// line each, after passing every field through field
+    fn to_delimited(&self, separator: char, field: impl for<'a> Fn(&'a str) -> Cow<'a, str>) -> String {
+        let mut out = String::new();
+        let mut push_line = |fields: &mut dyn Iterator<Item = Cow<str>>| {
+            for (idx, value) in fields.enumerate() {
+                if idx > 0 {
+                    out.push(separator);
+                }
+                out.push_str(&field(&value));
+            }
+            out.push('\n');
+        };
+        push_line(&mut self.columns.iter().map(|col| Cow::Borrowed(col.name_for(OutputFormat::Csv))));
+        for row in &self.rows {
+            push_line(&mut row.cells.iter().enumerate().map(|(idx, cell)| self.redacted(idx, cell)));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    fn sample() -> Table {
+        let mut table = Table::new(vec![
+            Column::new("Host", 0, Justification::Left).unwrap(),
+            Column::new("Note", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "a\tb\r\nsaid \"hi\", C:\\tmp".to_string()]).unwrap();
+        table.add_row(vec!["db-1".to_string(), String::new()]).unwrap();
+        table
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut table = sample();
+        table.columns[0].set_alias(OutputFormat::Csv, "host");
+        assert_eq!(table.to_csv(), "host,Note\nweb-1,\"a\tb\r\nsaid \"\"hi\"\", C:\\tmp\"\ndb-1,\n");
+    }
+
+    #[test]
+    fn test_from_csv() {
+        let table = Table::from_csv(&sample().to_csv()).unwrap();
+        assert_eq!(table.columns()[1].name(), "Note");
+        assert_eq!(table.row(0).unwrap(), ["web-1", "a\tb\r\nsaid \"hi\", C:\\tmp"]);
+        assert_eq!(table.row(1).unwrap(), ["db-1", ""]);
+
+        let table = Table::from_csv("a,b,c\r\n1,\"2\"\r\n\r\n3,4,5,6").unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(0).unwrap(), ["1", "2", ""]);
+        assert_eq!(table.row(1).unwrap(), ["3", "4", "5"]);
+
+        assert_eq!(Table::from_csv("a\n\"open\n").unwrap_err(), "Table::from_csv: line 2: unterminated quoted field");
+        assert_eq!(Table::from_csv("a\n\"x\"y\n").unwrap_err(), "Table::from_csv: line 2: unexpected 'y' after a closing quote");
+        assert!(Table::from_csv("").unwrap_err().contains("no header"));
+        assert!(Table::from_csv("a,,b\n").unwrap_err().contains("empty"));
+    }
+
+    #[test]
+    fn test_to_tsv() {
+        let table = sample();
+        assert_eq!(table.to_tsv(), "Host\tNote\nweb-1\ta\\tb\\r\\nsaid \"hi\", C:\\\\tmp\ndb-1\t\n");
+        let options = TsvOptions { escaping: TsvEscaping::Strip };
+        assert_eq!(table.to_tsv_with(&options), "Host\tNote\nweb-1\ta b said \"hi\", C:\\tmp\ndb-1\t\n");
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{Column, Justification, Table};
+
+/// Returns the trimmed text between two character positions of a line
+/// (end is exclusive and may extend past the end of the line)
+fn slice_chars(line: &[char], start: usize, end: usize) -> String {
+    let end = end.min(line.len());
+    if start >= end {
+        return String::new();
+    }
+    line[start..end].iter().collect::<String>().trim().to_string()
+}
+
+/// A column found by the alignment heuristic, as a half-open character range
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: usize,
+    end: usize,
+}
+
+/// Returns true if the line looks like a header rule ("---- ------", "==== ===")
+fn is_separator_line(line: &[char]) -> bool {
+    line.iter().any(|c| matches!(c, '-' | '=' | '─'))
+        && line.iter().all(|c| matches!(c, '-' | '=' | '─' | '+' | '|' | ':' | ' '))
+}
+
+/// Splits a line into runs of characters that are not spaces
+fn runs(line: &[char]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut start = None;
+    for (i, c) in line.iter().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                segments.push(Segment { start: s, end: i });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        segments.push(Segment { start: s, end: line.len() });
+    }
+    segments
+}
+
+/// Infers column segments from positions that are blank in every line.
+/// Segments without header text are folded into their left neighbor, as are
+/// segments that are blank in every data row (e.g. the "on" of "Mounted on").
+fn infer_segments(header: &[char], data: &[Vec<char>]) -> Vec<Segment> {
+    let width = data.iter().map(|l| l.len()).chain([header.len()]).max().unwrap_or(0);
+    let mut occupied = vec![false; width];
+    for line in data.iter().map(Vec::as_slice).chain([header]) {
+        for (i, c) in line.iter().enumerate() {
+            if !c.is_whitespace() {
+                occupied[i] = true;
+            }
+        }
+    }
+    let occupied: Vec<char> = occupied.iter().map(|&o| if o { 'x' } else { ' ' }).collect();
+
+    let mut segments: Vec<Segment> = Vec::new();
+    for seg in runs(&occupied) {
+        let has_header = !slice_chars(header, seg.start, seg.end).is_empty();
+        let has_data = data.iter().any(|l| !slice_chars(l, seg.start, seg.end).is_empty());
+        match segments.last_mut() {
+            Some(prev) if !has_header || !has_data => prev.end = seg.end,
+            _ => segments.push(seg),
+        }
+    }
+    segments
+}
+
+impl Table {
+    /// Loads fixed-width text where each field is given as (name, start, end)
+    /// character positions, end exclusive. Every non-blank line is a data row;
+    /// values are trimmed and positions past the end of a line yield empty cells.
+    /// Columns are left-justified and not truncated.
+    ///
+    /// # Errors
+    /// - If no fields are given, a field name is empty, or a field has start >= end
+    /// - If the rows exceed the table limits
+    pub fn from_fixed_width(text: &str, fields: &[(&str, usize, usize)]) -> Result<Table, String> {
+        if let Some((name, start, end)) = fields.iter().find(|(_, start, end)| start >= end) {
+            return Err(format!(
+                "Table::from_fixed_width: field '{}' has start {} not before end {}",
+                name, start, end
+            ));
+        }
+        let columns = fields
+            .iter()
+            .map(|(name, _, _)| Column::new(*name, 0, Justification::Left))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::from_fixed_width: {}", e))?;
+        let mut table = Table::new(columns)?;
+
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let chars: Vec<char> = line.chars().collect();
+            let row = fields
+                .iter()
+                .map(|&(_, start, end)| slice_chars(&chars, start, end))
+                .collect();
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+
+    /// Loads column-aligned text such as the output of ps, df, or netstat, or a
+    /// table previously rendered by this crate, inferring column boundaries.
+    /// The first non-blank line is the header. If the next line is a rule made of
+    /// dashes or equals signs, its runs define the columns exactly; otherwise columns
+    /// are separated at positions that are blank in every line. Each column extends
+    /// to the start of the next, and the last column takes the rest of the line.
+    /// Columns whose values all end at the right edge are right-justified.
+    ///
+    /// # Errors
+    /// - If the text has no header line
+    /// - If the rows exceed the table limits
+    pub fn from_aligned(text: &str) -> Result<Table, String> {
+        let mut lines = text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.trim_end().chars().collect::<Vec<char>>());
+        let header = lines.next().ok_or_else(|| "Table::from_aligned: no header line found".to_string())?;
+        let mut data: Vec<Vec<char>> = lines.collect();
+
+        let segments = if data.first().is_some_and(|l| is_separator_line(l)) {
+            let separator = data.remove(0);
+            runs(&separator)
+        } else {
+            infer_segments(&header, &data)
+        };
+        if segments.is_empty() {
+            return Err("Table::from_aligned: no columns found in header".to_string());
+        }
+
+        // Each column spans up to the next column's start; the last takes the rest of the line
+        let bounds: Vec<(usize, usize)> = segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| (seg.start, segments.get(i + 1).map_or(usize::MAX, |next| next.start)))
+            .collect();
+
+        let columns = segments
+            .iter()
+            .zip(&bounds)
+            .enumerate()
+            .map(|(i, (seg, &(start, end)))| {
+                let name = match slice_chars(&header, start, end) {
+                    name if name.is_empty() => format!("Column{}", i + 1),
+                    name => name,
+                };
+                let right_aligned = data.iter().any(|l| !slice_chars(l, start, end).is_empty())
+                    && data.iter().all(|l| {
+                        slice_chars(l, start, end).is_empty()
+                            || l.get(seg.end - 1).is_some_and(|c| !c.is_whitespace())
+                    })
+                    && data.iter().any(|l| l.get(seg.start).is_some_and(|c| c.is_whitespace()));
+                let justification = if right_aligned { Justification::Right } else { Justification::Left };
+                Column::new(name, 0, justification)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut table = Table::new(columns)?;
+        for line in &data {
+            let row = bounds.iter().map(|&(start, end)| slice_chars(line, start, end)).collect();
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_fixed_width() {
+        let text = "abc  12 x\n\nde    3\n";
+        let table = Table::from_fixed_width(text, &[("Name", 0, 5), ("Count", 5, 7), ("Flag", 7, 20)]).unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(0).unwrap(), ["abc", "12", "x"]);
+        assert_eq!(table.row(1).unwrap(), ["de", "3", ""]);
+        assert!(Table::from_fixed_width(text, &[("Name", 5, 5)]).is_err());
+    }
+
+    #[test]
+    fn test_from_aligned_unix_output() {
+        let text = "\
+Filesystem     1K-blocks    Used Available Use% Mounted on
+/dev/sda1       41152736 9876543  29163980  26% /
+tmpfs             816028       0    816028   0% /dev/shm
+";
+        let table = Table::from_aligned(text).unwrap();
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["Filesystem", "1K-blocks", "Used", "Available", "Use%", "Mounted on"]);
+        assert_eq!(table.row(1).unwrap(), ["tmpfs", "816028", "0", "816028", "0%", "/dev/shm"]);
+        assert_eq!(table.columns()[1].justification(), Justification::Right);
+        assert_eq!(table.columns()[0].justification(), Justification::Left);
+    }
+
+    #[test]
+    fn test_from_aligned_round_trip() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Job Title", 0, Justification::Left).unwrap(),
+            Column::new("Salary", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["John Doe".to_string(), "Engineer".to_string(), "100".to_string()]).unwrap();
+        table.add_row(vec!["Jane".to_string(), "Project Manager".to_string(), "2500".to_string()]).unwrap();
+
+        let parsed = Table::from_aligned(&table.to_string()).unwrap();
+        assert_eq!(parsed.row_count(), 2);
+        assert_eq!(parsed.columns()[1].name(), "Job Title");
+        assert_eq!(parsed.row(0).unwrap(), ["John Doe", "Engineer", "100"]);
+        assert_eq!(parsed.row(1).unwrap(), ["Jane", "Project Manager", "2500"]);
+        assert_eq!(parsed.columns()[2].justification(), Justification::Right);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{push_justified, text_width, truncate_line, Layout, Table};
+
+impl Table {
+    /// Sets a footer row drawn by the text renderer beneath the data rows, set
+    /// apart by a rule: the place for totals and summaries. Footer values count
+    /// toward the column widths (up to a column's truncation width) and follow
+    /// the column justification, but are not formatted, redacted, or wrapped
+    /// like data values.
+    ///
+    /// # Errors
+    /// - If number of values doesn't match number of columns
+    pub fn set_footer(&mut self, footer: Vec<String>) -> Result<(), String> {
+        if footer.len() != self.columns.len() {
+            return Err(format!(
+                "Table::set_footer: footer has {} columns, expected {}",
+                footer.len(),
+                self.columns.len()
+            ));
+        }
+        self.footer = Some(footer);
+        Ok(())
+    }
+
+    /// Returns the footer values, if any
+    pub fn footer(&self) -> Option<&[String]> {
+        self.footer.as_deref()
+    }
+
+    /// Removes the footer row
+    pub fn clear_footer(&mut self) {
+        self.footer = None;
+    }
+
+    pub(crate) fn write_footer(&self, f: &mut std::fmt::Formatter, layout: &Layout) -> std::fmt::Result {
+        let Some(footer) = &self.footer else {
+            return Ok(());
+        };
+        self.write_line(f, &layout.frame.row_rule(&layout.widths))?;
+        let lines: Vec<Vec<&str>> = footer.iter().map(|value| value.split('\n').collect()).collect();
+        for line_idx in 0..self.footer_lines() - 1 {
+            let cells: Vec<String> = lines
+                .iter()
+                .zip(&layout.widths)
+                .zip(&layout.aligns)
+                .map(|((cell, &width), align)| {
+                    let text = truncate_line(cell.get(line_idx).copied().unwrap_or(""), width);
+                    let mut out = String::with_capacity(width);
+                    push_justified(&mut out, &text, width - text_width(&text), align.justification, align.fraction);
+                    out
+                })
+                .collect();
+            self.write_line(f, &layout.frame.join(&cells))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of lines the footer adds to a full render: its rule
+    /// and one for each line of its tallest value
+    pub(crate) fn footer_lines(&self) -> usize {
+        self.footer.as_ref().map_or(0, |footer| 1 + footer.iter().map(|value| value.split('\n').count()).max().unwrap_or(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BorderStyle, Column, Justification, Table};
+
+    #[test]
+    fn test_footer() {
+        let mut table = Table::new(vec![
+            Column::new("Item", 0, Justification::Left).unwrap(),
+            Column::new("Cost", 0, Justification::Decimal).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["tea".to_string(), "3.5".to_string()]).unwrap();
+        table.add_row(vec!["cake".to_string(), "12.25".to_string()]).unwrap();
+        table.set_footer(vec!["Total".to_string(), "15.75".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Item   Cost\n===== =====\ntea    3.5 \ncake  12.25\n----- -----\nTotal 15.75\n");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+
+        table.set_border(BorderStyle::Ascii);
+        assert!(table.to_string().ends_with("| cake  | 12.25 |\n+-------+-------+\n| Total | 15.75 |\n+-------+-------+\n"));
+        assert!(table.set_footer(vec!["Total".to_string()]).is_err());
+        assert_eq!(table.footer().map(<[String]>::len), Some(2));
+
+        table.set_footer(vec!["Total for all items this month".to_string(), "15.75".to_string()]).unwrap();
+        table.fit_to_width(20);
+        assert!(table.to_string().ends_with("| Total... | 15.75 |\n+----------+-------+\n"), "{}", table);
+    }
+}
@@ -0,0 +1,429 @@
+/// How a column's numeric values are displayed by the renderers. Formatting works on
+/// the decimal digits of the cell text, so no precision is lost to floating point,
+/// and rounds according to the column's RoundingMode. Values that are not plain decimal numbers
+/// (optionally signed, with an optional exponent) are shown unchanged.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum NumberFormat {
+    /// Fixed notation with the given number of decimal places: 2.675 with 2 renders as "2.68"
+    Fixed(usize),
+    /// Scientific notation with the given number of digits after the decimal point:
+    /// 1234567 with 2 renders as "1.23e+06"
+    Scientific(usize),
+    /// Fixed notation rounded to the given number of significant figures (at least 1):
+    /// with 3, 0.0012345 renders as "0.00123" and 123456 as "123000"
+    SignificantFigures(usize),
+    /// A ratio shown as a percentage with the given number of decimal places:
+    /// 0.8734 with 1 renders as "87.3%"
+    Percent(usize),
+    /// Compact human-readable numbers with a magnitude suffix and the given number of
+    /// decimal places: 12412 with 1 renders as "12.4k". Values under 1000 are rounded
+    /// to the same decimal places with trailing zeros dropped.
+    Compact { precision: usize, scale: CompactScale },
+}
+
+/// How number formats round values that don't fit the requested precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Ties round away from zero: 2.5 -> 3, -2.5 -> -3
+    #[default]
+    HalfUp,
+    /// Ties round to the even neighbor (banker's rounding): 2.5 -> 2, 3.5 -> 4
+    HalfEven,
+    /// Extra digits are dropped: 2.9 -> 2, -2.9 -> -2
+    TowardZero,
+}
+
+/// Suffixes used by NumberFormat::Compact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompactScale {
+    /// Short scale: k, M, B (billion), T (trillion)
+    #[default]
+    Short,
+    /// SI prefixes: k, M, G, T, P, E
+    Si,
+}
+
+impl CompactScale {
+    fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            CompactScale::Short => &["", "k", "M", "B", "T"],
+            CompactScale::Si => &["", "k", "M", "G", "T", "P", "E"],
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Formats a cell value, or returns None if it is not a number
+    pub(crate) fn apply(&self, value: &str, mode: RoundingMode) -> Option<String> {
+        let decimal = Decimal::parse(value)?;
+        Some(match self {
+            NumberFormat::Fixed(decimals) => decimal.to_fixed(*decimals, mode),
+            NumberFormat::Scientific(precision) => decimal.to_scientific(*precision, mode),
+            NumberFormat::SignificantFigures(figures) => decimal.to_significant(*figures, mode),
+            NumberFormat::Percent(decimals) => {
+                let percent = Decimal { exponent: decimal.exponent + 2, ..decimal };
+                format!("{}%", percent.to_fixed(*decimals, mode))
+            }
+            NumberFormat::Compact { precision, scale } => decimal.to_compact(*precision, *scale, mode),
+        })
+    }
+}
+
+/// Terminal colors for highlighting values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    /// Wraps text in the escape sequences that set and reset this foreground color
+    pub(crate) fn paint(self, text: &str) -> String {
+        format!("\x1b[{}m{}\x1b[0m", 30 + self as u8, text)
+    }
+}
+
+/// How values beyond a column's thresholds are highlighted
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThresholdStyle {
+    /// Colors the value in the text renderer (other renderers show it plainly)
+    Color { below: AnsiColor, above: AnsiColor },
+    /// Appends a marker after the value, separated by a space, in every renderer
+    Flag { below: String, above: String },
+}
+
+/// Highlights numeric values of a column that fall below or above given limits.
+/// Limits compare against the cell value itself (the ratio, for Percent columns).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thresholds {
+    /// Values less than this are highlighted as low
+    pub below: Option<f64>,
+    /// Values greater than this are highlighted as high
+    pub above: Option<f64>,
+    pub style: ThresholdStyle,
+}
+
+/// Which threshold a value crossed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Crossed {
+    Below,
+    Above,
+}
+
+impl Thresholds {
+    /// Returns which threshold the cell value crosses, if it is a number that crosses one
+    pub(crate) fn check(&self, value: &str) -> Option<Crossed> {
+        let number: f64 = value.trim().parse().ok()?;
+        if self.below.is_some_and(|limit| number < limit) {
+            Some(Crossed::Below)
+        } else if self.above.is_some_and(|limit| number > limit) {
+            Some(Crossed::Above)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the marker to append for a crossed threshold, for the Flag style
+    pub(crate) fn flag(&self, crossed: Crossed) -> Option<&str> {
+        match (&self.style, crossed) {
+            (ThresholdStyle::Flag { below, .. }, Crossed::Below) => Some(below),
+            (ThresholdStyle::Flag { above, .. }, Crossed::Above) => Some(above),
+            (ThresholdStyle::Color { .. }, _) => None,
+        }
+    }
+
+    /// Returns the color for a crossed threshold, for the Color style
+    pub(crate) fn color(&self, crossed: Crossed) -> Option<AnsiColor> {
+        match (&self.style, crossed) {
+            (ThresholdStyle::Color { below, .. }, Crossed::Below) => Some(*below),
+            (ThresholdStyle::Color { above, .. }, Crossed::Above) => Some(*above),
+            (ThresholdStyle::Flag { .. }, _) => None,
+        }
+    }
+}
+
+/// Largest exponent magnitude Decimal::parse accepts. Fixed notation writes out
+/// every digit up to the decimal point, so values beyond this are left unformatted.
+const MAX_EXPONENT: i64 = 400;
+
+/// A decimal number as sign, significant digits, and exponent:
+/// value = d0.d1d2... × 10^exponent. Zero has no digits.
+#[derive(Debug, Clone, PartialEq)]
+struct Decimal {
+    negative: bool,
+    digits: Vec<u8>,
+    exponent: i64,
+}
+
+impl Decimal {
+    /// Parses text such as "-12.50", ".5", or "6.02e23" (surrounding whitespace allowed).
+    /// Nonzero values with an exponent beyond ±MAX_EXPONENT are rejected.
+    fn parse(text: &str) -> Option<Decimal> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (mantissa, exp) = match text.find(['e', 'E']) {
+            Some(pos) => (&text[..pos], text[pos + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int_part.is_empty() && frac_part.is_empty()
+            || !int_part.bytes().chain(frac_part.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let all: Vec<u8> = int_part.bytes().chain(frac_part.bytes()).map(|b| b - b'0').collect();
+        let Some(first) = all.iter().position(|&d| d != 0) else {
+            return Some(Decimal { negative, digits: Vec::new(), exponent: 0 });
+        };
+        let last = all.iter().rposition(|&d| d != 0).unwrap_or(first);
+        let exponent = (int_part.len() as i64 - 1 - first as i64).checked_add(exp)?;
+        if exponent.abs() > MAX_EXPONENT {
+            return None;
+        }
+        Some(Decimal { negative, digits: all[first..=last].to_vec(), exponent })
+    }
+
+    /// Rounds so at most `keep` leading digits remain. keep may be zero or
+    /// negative, in which case the result may become zero.
+    fn round_to(&self, keep: i64, mode: RoundingMode) -> Decimal {
+        if keep >= self.digits.len() as i64 {
+            return self.clone();
+        }
+        let mut exponent = self.exponent;
+        let mut digits: Vec<u8> = if keep > 0 { self.digits[..keep as usize].to_vec() } else { Vec::new() };
+        let next = if keep >= 0 { self.digits[keep as usize] } else { 0 };
+        // Digits are trimmed of trailing zeros, so anything after `next` is nonzero
+        let more = keep.checked_add(1).is_some_and(|after| (self.digits.len() as i64) > after);
+        let round_up = match mode {
+            RoundingMode::HalfUp => next >= 5,
+            RoundingMode::HalfEven => next > 5 || next == 5 && (more || digits.last().is_some_and(|d| d % 2 == 1)),
+            RoundingMode::TowardZero => false,
+        };
+        if round_up {
+            // Propagate the carry; a carry out of the first digit adds a new leading 1
+            let mut i = digits.len();
+            loop {
+                if i == 0 {
+                    digits.insert(0, 1);
+                    exponent += 1;
+                    break;
+                }
+                i -= 1;
+                if digits[i] == 9 {
+                    digits[i] = 0;
+                } else {
+                    digits[i] += 1;
+                    break;
+                }
+            }
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            exponent = 0;
+        }
+        Decimal { negative: self.negative, digits, exponent }
+    }
+
+    /// Returns how many leading digits remain when rounding to the given decimal places
+    fn keep_for_decimals(&self, decimals: usize) -> i64 {
+        (self.exponent + 1).saturating_add(i64::try_from(decimals).unwrap_or(i64::MAX))
+    }
+
+    fn sign(&self) -> &'static str {
+        if self.negative && !self.digits.is_empty() { "-" } else { "" }
+    }
+
+    fn to_scientific(&self, precision: usize, mode: RoundingMode) -> String {
+        let rounded = self.round_to(i64::try_from(precision).unwrap_or(i64::MAX).saturating_add(1), mode);
+        let mut mantissa: String = rounded.digits.iter().map(|d| (b'0' + d) as char).collect();
+        if mantissa.is_empty() {
+            mantissa.push('0');
+        }
+        while mantissa.len() < precision + 1 {
+            mantissa.push('0');
+        }
+        if precision > 0 {
+            mantissa.insert(1, '.');
+        }
+        let exp_sign = if rounded.exponent < 0 { '-' } else { '+' };
+        format!("{}{}e{}{:02}", rounded.sign(), mantissa, exp_sign, rounded.exponent.abs())
+    }
+
+    /// Formats in fixed notation with exactly the given number of decimal places
+    fn to_fixed(&self, decimals: usize, mode: RoundingMode) -> String {
+        let rounded = self.round_to(self.keep_for_decimals(decimals), mode);
+        let digits: String = rounded.digits.iter().map(|d| (b'0' + d) as char).collect();
+        // Digits before the decimal point, then pad or place the fraction
+        let int_len = rounded.exponent + 1;
+        let (int_part, frac_part) = if rounded.digits.is_empty() || int_len <= 0 {
+            ("0".to_string(), format!("{}{}", "0".repeat((-int_len).max(0) as usize), digits))
+        } else if int_len as usize >= digits.len() {
+            (format!("{}{}", digits, "0".repeat(int_len as usize - digits.len())), String::new())
+        } else {
+            let (i, f) = digits.split_at(int_len as usize);
+            (i.to_string(), f.to_string())
+        };
+        let mut frac_part = frac_part;
+        while frac_part.len() < decimals {
+            frac_part.push('0');
+        }
+        if decimals == 0 {
+            format!("{}{}", rounded.sign(), int_part)
+        } else {
+            format!("{}{}.{}", rounded.sign(), int_part, frac_part)
+        }
+    }
+
+    fn to_compact(&self, precision: usize, scale: CompactScale, mode: RoundingMode) -> String {
+        let suffixes = scale.suffixes();
+        let max_group = suffixes.len() as i64 - 1;
+        let mut group = (self.exponent.max(0) / 3).min(max_group);
+        loop {
+            let scaled = Decimal { exponent: self.exponent - 3 * group, ..self.clone() };
+            // Rounding can carry into the next group (999.96k -> 1000.0k -> 1.0M)
+            if group < max_group && scaled.round_to(scaled.keep_for_decimals(precision), mode).exponent >= 3 {
+                group += 1;
+                continue;
+            }
+            let mut text = scaled.to_fixed(precision, mode);
+            if group == 0 && text.contains('.') {
+                text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+            }
+            return format!("{}{}", text, suffixes[group as usize]);
+        }
+    }
+
+    fn to_significant(&self, figures: usize, mode: RoundingMode) -> String {
+        let figures = figures.max(1);
+        let rounded = self.round_to(i64::try_from(figures).unwrap_or(i64::MAX), mode);
+        if rounded.digits.is_empty() {
+            return "0".to_string();
+        }
+        let mut digits: String = rounded.digits.iter().map(|d| (b'0' + d) as char).collect();
+        while digits.len() < figures {
+            digits.push('0');
+        }
+        let exponent = rounded.exponent;
+        let body = if exponent < 0 {
+            format!("0.{}{}", "0".repeat((-exponent - 1) as usize), digits)
+        } else if exponent as usize + 1 >= digits.len() {
+            format!("{}{}", digits, "0".repeat(exponent as usize + 1 - digits.len()))
+        } else {
+            let (int_part, frac_part) = digits.split_at(exponent as usize + 1);
+            format!("{}.{}", int_part, frac_part)
+        };
+        format!("{}{}", rounded.sign(), body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_UP: RoundingMode = RoundingMode::HalfUp;
+
+    #[test]
+    fn test_scientific() {
+        let f = NumberFormat::Scientific(2);
+        assert_eq!(f.apply("1234567", HALF_UP).as_deref(), Some("1.23e+06"));
+        assert_eq!(f.apply("-0.000456789", HALF_UP).as_deref(), Some("-4.57e-04"));
+        assert_eq!(f.apply("9.999", HALF_UP).as_deref(), Some("1.00e+01"));
+        assert_eq!(f.apply("0", HALF_UP).as_deref(), Some("0.00e+00"));
+        assert_eq!(f.apply("6.02E23", HALF_UP).as_deref(), Some("6.02e+23"));
+        assert_eq!(NumberFormat::Scientific(0).apply("15", HALF_UP).as_deref(), Some("2e+01"));
+        assert_eq!(f.apply("n/a", HALF_UP), None);
+        assert_eq!(f.apply("1.2.3", HALF_UP), None);
+    }
+
+    #[test]
+    fn test_significant_figures() {
+        let f = NumberFormat::SignificantFigures(3);
+        assert_eq!(f.apply("0.0012345", HALF_UP).as_deref(), Some("0.00123"));
+        assert_eq!(f.apply("123456", HALF_UP).as_deref(), Some("123000"));
+        assert_eq!(f.apply("9.996", HALF_UP).as_deref(), Some("10.0"));
+        assert_eq!(f.apply("-2.5", HALF_UP).as_deref(), Some("-2.50"));
+        assert_eq!(f.apply("0.0004", HALF_UP).as_deref(), Some("0.000400"));
+        assert_eq!(f.apply("-0.0", HALF_UP).as_deref(), Some("0"));
+        assert_eq!(NumberFormat::SignificantFigures(1).apply("0.96", HALF_UP).as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_compact() {
+        let short = |p| NumberFormat::Compact { precision: p, scale: CompactScale::Short };
+        assert_eq!(short(1).apply("12412", HALF_UP).as_deref(), Some("12.4k"));
+        assert_eq!(short(1).apply("3100000", HALF_UP).as_deref(), Some("3.1M"));
+        assert_eq!(short(1).apply("7.9e9", HALF_UP).as_deref(), Some("7.9B"));
+        assert_eq!(short(1).apply("999960", HALF_UP).as_deref(), Some("1.0M"));
+        assert_eq!(short(1).apply("-2500", HALF_UP).as_deref(), Some("-2.5k"));
+        assert_eq!(short(1).apply("950", HALF_UP).as_deref(), Some("950"));
+        assert_eq!(short(2).apply("12.5", HALF_UP).as_deref(), Some("12.5"));
+        assert_eq!(short(0).apply("5e15", HALF_UP).as_deref(), Some("5000T"));
+
+        let si = NumberFormat::Compact { precision: 2, scale: CompactScale::Si };
+        assert_eq!(si.apply("7890000000", HALF_UP).as_deref(), Some("7.89G"));
+    }
+
+    #[test]
+    fn test_percent() {
+        let f = NumberFormat::Percent(1);
+        assert_eq!(f.apply("0.8734", HALF_UP).as_deref(), Some("87.3%"));
+        assert_eq!(f.apply("1", HALF_UP).as_deref(), Some("100.0%"));
+        assert_eq!(f.apply("0.00004", HALF_UP).as_deref(), Some("0.0%"));
+        assert_eq!(f.apply("-0.05", HALF_UP).as_deref(), Some("-5.0%"));
+        assert_eq!(NumberFormat::Percent(0).apply("0.125", HALF_UP).as_deref(), Some("13%"));
+        assert_eq!(NumberFormat::Percent(2).apply("0.0005", HALF_UP).as_deref(), Some("0.05%"));
+
+        let thresholds = Thresholds { below: Some(0.9), above: None, style: ThresholdStyle::Flag { below: "!".into(), above: String::new() } };
+        assert_eq!(thresholds.check("0.85"), Some(Crossed::Below));
+        assert_eq!(thresholds.check("0.95"), None);
+        assert_eq!(thresholds.check("n/a"), None);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let fixed = NumberFormat::Fixed(2);
+        assert_eq!(fixed.apply("2.675", HALF_UP).as_deref(), Some("2.68"));
+        assert_eq!(fixed.apply("2.665", RoundingMode::HalfEven).as_deref(), Some("2.66"));
+        assert_eq!(fixed.apply("2.675", RoundingMode::HalfEven).as_deref(), Some("2.68"));
+        assert_eq!(fixed.apply("2.6651", RoundingMode::HalfEven).as_deref(), Some("2.67"));
+        assert_eq!(fixed.apply("-2.679", RoundingMode::TowardZero).as_deref(), Some("-2.67"));
+        assert_eq!(fixed.apply("-2.665", HALF_UP).as_deref(), Some("-2.67"));
+        assert_eq!(fixed.apply("12", HALF_UP).as_deref(), Some("12.00"));
+
+        let whole = NumberFormat::Fixed(0);
+        assert_eq!(whole.apply("0.5", RoundingMode::HalfEven).as_deref(), Some("0"));
+        assert_eq!(whole.apply("1.5", RoundingMode::HalfEven).as_deref(), Some("2"));
+        assert_eq!(whole.apply("0.5", HALF_UP).as_deref(), Some("1"));
+        assert_eq!(NumberFormat::Percent(0).apply("0.125", RoundingMode::HalfEven).as_deref(), Some("12%"));
+    }
+
+    #[test]
+    fn test_exponent_limits() {
+        let fixed = NumberFormat::Fixed(2);
+        assert_eq!(fixed.apply("1e9223372036854775807", HALF_UP), None);
+        assert_eq!(fixed.apply("1e100000000000", HALF_UP), None);
+        assert_eq!(fixed.apply("1e-100000000000", HALF_UP), None);
+        assert_eq!(NumberFormat::Scientific(2).apply("1e401", HALF_UP), None);
+        assert_eq!(NumberFormat::Scientific(2).apply("1e400", HALF_UP).as_deref(), Some("1.00e+400"));
+        assert_eq!(fixed.apply("0e100000000000", HALF_UP).as_deref(), Some("0.00"));
+
+        let mut column = crate::Column::new("N", 0, crate::Justification::Left).unwrap();
+        column.set_number_format(fixed);
+        let mut table = crate::Table::new(vec![column]).unwrap();
+        table.add_row(vec!["1e100000000000".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "N             \n==============\n1e100000000000\n");
+    }
+}
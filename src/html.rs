@@ -0,0 +1,202 @@
+use std::fmt::Write;
+
+use crate::{escape_xml, Justification, Metadata, OutputFormat, Table};
+
+#[cfg(feature = "html-import")]
+mod import;
+
+/// Settings for Table::to_html_with
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlOptions {
+    /// Emit a complete, self-contained page with a filter box and headers that
+    /// sort the rows when clicked, instead of a bare <table> element
+    pub interactive: bool,
+    /// Page title for interactive output
+    pub title: Option<String>,
+    /// CSS class (or space-separated classes) for the <table> element
+    pub class: Option<String>,
+}
+
+/// Minimal client-side sorting and filtering for interactive pages. Columns sort
+/// numerically when both values parse as numbers, otherwise as text.
+const INTERACTIVE_SCRIPT: &str = r#"(function () {
+  var table = document.querySelector("table");
+  var body = table.tBodies[0];
+  var headers = table.tHead.rows[0].cells;
+  var key = function (row, i) { return row.cells[i].textContent.trim(); };
+  Array.prototype.forEach.call(headers, function (th, i) {
+    th.addEventListener("click", function () {
+      var asc = th.getAttribute("aria-sort") !== "ascending";
+      Array.prototype.forEach.call(headers, function (h) { h.removeAttribute("aria-sort"); });
+      th.setAttribute("aria-sort", asc ? "ascending" : "descending");
+      var rows = Array.prototype.slice.call(body.rows);
+      rows.sort(function (a, b) {
+        var x = key(a, i), y = key(b, i);
+        var nx = parseFloat(x.replace(/,/g, "")), ny = parseFloat(y.replace(/,/g, ""));
+        var cmp = !isNaN(nx) && !isNaN(ny) ? nx - ny : x.localeCompare(y);
+        return asc ? cmp : -cmp;
+      });
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+  document.getElementById("filter").addEventListener("input", function (e) {
+    var needle = e.target.value.toLowerCase();
+    Array.prototype.forEach.call(body.rows, function (row) {
+      row.hidden = row.textContent.toLowerCase().indexOf(needle) < 0;
+    });
+  });
+})();"#;
+
+const INTERACTIVE_STYLE: &str = "\
+body { font-family: sans-serif; margin: 1em; }
+#filter { margin-bottom: 0.5em; padding: 0.25em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.5em; vertical-align: top; }
+th { background: #f0f0f0; cursor: pointer; user-select: none; }
+th[aria-sort=ascending]::after { content: \" \\25B2\"; }
+th[aria-sort=descending]::after { content: \" \\25BC\"; }
+tbody tr:nth-child(even) { background: #f7f7f7; }
+";
+
+/// Returns the style attribute aligning a cell, or nothing for left-justified ones
+fn align_attribute(justification: Justification) -> &'static str {
+    match justification {
+        Justification::Left => "",
+        Justification::Center => " style=\"text-align: center\"",
+        Justification::Right | Justification::Decimal => " style=\"text-align: right\"",
+    }
+}
+
+/// Returns row or cell metadata as attributes: `id` and `class` as they are and
+/// other keys as `data-<key>`, with characters not allowed in a name replaced by '-'
+fn metadata_attributes(metadata: Option<&Metadata>) -> String {
+    let mut out = String::new();
+    for (key, value) in metadata.into_iter().flatten() {
+        let value = escape_xml(value);
+        if key == "id" || key == "class" {
+            let _ = write!(out, " {}=\"{}\"", key, value);
+        } else {
+            let name: String = key
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c.to_ascii_lowercase() } else { '-' })
+                .collect();
+            let _ = write!(out, " data-{}=\"{}\"", name, value);
+        }
+    }
+    out
+}
+
+/// Escapes cell text for HTML, turning newlines into <br>
+fn escape_cell(value: &str) -> String {
+    value.split('\n').map(escape_xml).collect::<Vec<_>>().join("<br>")
+}
+
+impl Table {
+    /// Renders the table as an HTML <table> element. See to_html_with.
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&HtmlOptions::default())
+    }
+
+    /// Renders the table as HTML with a <thead> of column names (each column's HTML
+    /// alias or name) and a <tbody> of rows. Text is escaped and newlines inside
+    /// cells become <br>. Cells of right-justified, Decimal, and centered columns
+    /// get a text-align style (headers follow the header justification). Row
+    /// metadata becomes attributes of the <tr> and cell metadata of the <td>:
+    /// `id` and `class` are written as they are and other keys as `data-<key>`
+    /// attributes, so scripts can make rows clickable. With
+    /// interactive set, the table is wrapped in a complete page with inline CSS and
+    /// JavaScript for a filter box and click-to-sort columns.
+    pub fn to_html_with(&self, options: &HtmlOptions) -> String {
+        let justifications: Vec<Justification> = (0..self.columns.len()).map(|idx| self.cell_align(idx).justification).collect();
+        let mut table = match &options.class {
+            Some(class) => format!("<table class=\"{}\">\n<thead>\n<tr>", escape_xml(class)),
+            None => String::from("<table>\n<thead>\n<tr>"),
+        };
+        for (col, &justification) in self.columns.iter().zip(&justifications) {
+            let align = align_attribute(col.header_justification.unwrap_or(justification));
+            let _ = write!(table, "<th{}>{}</th>", align, escape_cell(col.name_for(OutputFormat::Html)));
+        }
+        table.push_str("</tr>\n</thead>\n<tbody>\n");
+        for row in &self.rows {
+            let _ = write!(table, "<tr{}>", metadata_attributes(Some(&row.metadata)));
+            for (idx, cell) in row.cells.iter().enumerate() {
+                let align = align_attribute(justifications[idx]);
+                let attributes = metadata_attributes(row.cell_metadata.get(&idx));
+                let _ = write!(table, "<td{}{}>{}</td>", align, attributes, escape_cell(&self.redacted(idx, cell)));
+            }
+            table.push_str("</tr>\n");
+        }
+        table.push_str("</tbody>\n</table>\n");
+
+        if !options.interactive {
+            return table;
+        }
+        let title = escape_xml(options.title.as_deref().unwrap_or("Table"));
+        format!(
+            concat!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n",
+                "<style>\n{style}</style>\n</head>\n<body>\n",
+                "<input id=\"filter\" type=\"search\" placeholder=\"Filter rows\" aria-label=\"Filter rows\">\n",
+                "{table}<script>\n{script}\n</script>\n</body>\n</html>\n"
+            ),
+            title = title,
+            style = INTERACTIVE_STYLE,
+            table = table,
+            script = INTERACTIVE_SCRIPT,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    fn sample() -> Table {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Size", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["<b>&co".to_string(), "10".to_string()]).unwrap();
+        table.add_row(vec!["two\nlines".to_string(), "9".to_string()]).unwrap();
+        table
+    }
+
+    #[test]
+    fn test_to_html() {
+        let html = sample().to_html();
+        let right = " style=\"text-align: right\"";
+        assert_eq!(html, format!("<table>\n<thead>\n<tr><th>Name</th><th{right}>Size</th></tr>\n</thead>\n<tbody>\n\
+            <tr><td>&lt;b&gt;&amp;co</td><td{right}>10</td></tr>\n\
+            <tr><td>two<br>lines</td><td{right}>9</td></tr>\n</tbody>\n</table>\n"));
+
+        let mut table = sample();
+        table.columns[1].set_header_justification(Justification::Center);
+        let options = HtmlOptions { class: Some("report wide".to_string()), ..Default::default() };
+        let html = table.to_html_with(&options);
+        assert!(html.starts_with("<table class=\"report wide\">\n<thead>\n<tr><th>Name</th><th style=\"text-align: center\">Size</th>"));
+    }
+
+    #[test]
+    fn test_to_html_metadata() {
+        let mut table = sample();
+        table.set_row_metadata(0, "id", "row-1").unwrap();
+        table.set_row_metadata(0, "href", "/items?a=1&b=\"2\"").unwrap();
+        table.set_cell_metadata(1, 1, "class", "low").unwrap();
+        table.set_cell_metadata(1, 1, "Unit Name", "MB").unwrap();
+        let html = table.to_html();
+        assert!(html.contains("<tr data-href=\"/items?a=1&amp;b=&quot;2&quot;\" id=\"row-1\"><td>&lt;b&gt;&amp;co</td>"), "{}", html);
+        assert!(html.contains("<tr><td>two<br>lines</td><td style=\"text-align: right\" data-unit-name=\"MB\" class=\"low\">9</td></tr>"), "{}", html);
+    }
+
+    #[test]
+    fn test_to_html_interactive() {
+        let options = HtmlOptions { interactive: true, title: Some("Disk <usage>".to_string()), ..Default::default() };
+        let html = sample().to_html_with(&options);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Disk &lt;usage&gt;</title>"));
+        assert!(html.contains("<input id=\"filter\""));
+        assert!(html.contains("<td>two<br>lines</td>"));
+        assert!(html.contains("<script>") && html.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{Column, Justification, Table};
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(String),
+    Close(String),
+    Text(&'a str),
+}
+
+/// Splits HTML into open tags, close tags, and text, skipping comments, doctypes,
+/// and the contents of script and style elements. Tag names are lowercased and
+/// attributes are discarded.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if lt > 0 {
+            tokens.push(Token::Text(&rest[..lt]));
+        }
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(gt) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let tag = &rest[1..gt];
+        rest = &rest[gt + 1..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(t) => (true, t),
+            None => (false, tag),
+        };
+        let name: String = tag
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name.is_empty() {
+            // Doctype, processing instruction, or a stray '<'
+            continue;
+        }
+        if closing {
+            tokens.push(Token::Close(name));
+        } else if name == "script" || name == "style" {
+            let end_tag = format!("</{}", name);
+            rest = find_ignore_case(rest, &end_tag).map_or("", |end| {
+                let after = &rest[end..];
+                after.find('>').map_or("", |gt| &after[gt + 1..])
+            });
+        } else {
+            tokens.push(Token::Open(name));
+        }
+    }
+    tokens
+}
+
+/// Finds an ASCII needle in the haystack ignoring case
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Decodes the common named entities and numeric character references
+fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok().and_then(char::from_u32),
+                    Some(dec) => dec.parse().ok().and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                result.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Collapses whitespace runs within each line and trims the lines of a cell
+fn normalize_cell(raw: &str) -> String {
+    let lines: Vec<String> = raw
+        .split('\n')
+        .map(|line| decode_entities(&line.split_whitespace().collect::<Vec<_>>().join(" ")))
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// Extracts the rows of the nth top-level <table> in the document
+fn parse_table(html: &str, index: usize) -> Option<Vec<Vec<String>>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut found = false;
+    let mut tables_seen = 0;
+    let mut depth = 0;
+    // Raw text of the open cell, with '\0' marking <br>
+    let mut cell: Option<String> = None;
+
+    let close_cell = |rows: &mut Vec<Vec<String>>, cell: &mut Option<String>| {
+        if let (Some(text), Some(row)) = (cell.take(), rows.last_mut()) {
+            row.push(normalize_cell(&text.replace('\n', " ").replace('\0', "\n")));
+        }
+    };
+
+    for token in tokenize(html) {
+        match token {
+            Token::Open(name) if name == "table" => {
+                if depth == 0 {
+                    found = tables_seen == index;
+                    tables_seen += 1;
+                }
+                depth += 1;
+            }
+            Token::Close(name) if name == "table" && depth > 0 => {
+                depth -= 1;
+                if depth == 0 && found {
+                    close_cell(&mut rows, &mut cell);
+                    return Some(rows);
+                }
+            }
+            _ if !found || depth == 0 => {}
+            // Structure of the selected table itself; nested tables only contribute text
+            Token::Open(name) if depth == 1 && name == "tr" => {
+                close_cell(&mut rows, &mut cell);
+                rows.push(Vec::new());
+            }
+            Token::Open(name) if depth == 1 && (name == "td" || name == "th") => {
+                close_cell(&mut rows, &mut cell);
+                if rows.is_empty() {
+                    rows.push(Vec::new());
+                }
+                cell = Some(String::new());
+            }
+            Token::Close(name) if depth == 1 && matches!(name.as_str(), "td" | "th" | "tr") => {
+                close_cell(&mut rows, &mut cell);
+            }
+            Token::Open(name) if name == "br" => {
+                if let Some(text) = cell.as_mut() {
+                    text.push('\0');
+                }
+            }
+            Token::Open(_) | Token::Close(_) => {
+                if let Some(text) = cell.as_mut() {
+                    text.push(' ');
+                }
+            }
+            Token::Text(t) => {
+                if let Some(text) = cell.as_mut() {
+                    text.push_str(t);
+                }
+            }
+        }
+    }
+    // Unterminated table: keep what was parsed
+    close_cell(&mut rows, &mut cell);
+    found.then_some(rows)
+}
+
+impl Table {
+    /// Extracts the first <table> in an HTML document into a Table.
+    /// See from_html_nth for details.
+    ///
+    /// # Errors
+    /// - If the document has no table or the table has no rows
+    /// - If the rows exceed the table limits
+    pub fn from_html(html: &str) -> Result<Table, String> {
+        Table::from_html_nth(html, 0)
+    }
+
+    /// Extracts the top-level <table> at the given index (0-based) into a Table.
+    /// The first row becomes the columns (left-justified, not truncated), normally a
+    /// row of <th> cells; remaining rows become data. Cell text has tags stripped,
+    /// entities decoded, and whitespace collapsed, with <br> kept as a newline.
+    /// Rows are padded or cut to the header's column count. Nested tables are
+    /// flattened into the text of their enclosing cell.
+    ///
+    /// # Errors
+    /// - If there is no table at the index or the table has no rows
+    /// - If the rows exceed the table limits
+    pub fn from_html_nth(html: &str, index: usize) -> Result<Table, String> {
+        let mut rows = parse_table(html, index)
+            .ok_or_else(|| format!("Table::from_html: no table found at index {}", index))?
+            .into_iter()
+            .filter(|row| !row.is_empty());
+
+        let header = rows.next().ok_or_else(|| "Table::from_html: table has no rows".to_string())?;
+        let columns = header
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let name = if name.is_empty() { format!("Column{}", i + 1) } else { name };
+                Column::new(name, 0, Justification::Left)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut table = Table::new(columns)?;
+        let column_count = table.columns.len();
+        for mut row in rows {
+            row.resize(column_count, String::new());
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><head><style>td { color: red; }</style></head>
+<body>
+<!-- <table><tr><td>commented out</td></tr></table> -->
+<TABLE class="status">
+  <thead><tr><th>Service</th><th>State</th><th></th></tr></thead>
+  <tbody>
+    <tr><td><a href="/api">API</a></td><td>Up &amp; running</td><td>x</td></tr>
+    <tr><td>DB</td><td>Degraded<br/>since   09:00</td></tr>
+    <tr><td>Cache<table><tr><td>inner</td></tr></table></td><td>Up</td><td>a</td><td>extra</td></tr>
+  </tbody>
+</TABLE>
+<table><tr><td>second</td></tr></table>
+</body></html>"#;
+
+    #[test]
+    fn test_from_html() {
+        let table = Table::from_html(PAGE).unwrap();
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["Service", "State", "Column3"]);
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.row(0).unwrap(), ["API", "Up & running", "x"]);
+        assert_eq!(table.row(1).unwrap(), ["DB", "Degraded\nsince 09:00", ""]);
+        assert_eq!(table.row(2).unwrap(), ["Cache inner", "Up", "a"]);
+    }
+
+    #[test]
+    fn test_from_html_nth() {
+        let table = Table::from_html_nth(PAGE, 1).unwrap();
+        assert_eq!(table.columns()[0].name(), "second");
+        assert_eq!(table.row_count(), 0);
+        assert!(Table::from_html_nth(PAGE, 2).is_err());
+        assert!(Table::from_html("<p>no tables</p>").is_err());
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &#65;&#x42; &bogus; & done"), "a <b> AB &bogus; & done");
+    }
+}
@@ -0,0 +1,50 @@
+use crate::Table;
+
+/// Escapes a cell for JIRA wiki markup: pipes are backslash-escaped, newlines
+/// become forced line breaks (\\), and empty cells get a space so they keep their column
+fn escape_cell(value: &str) -> String {
+    if value.is_empty() {
+        return " ".to_string();
+    }
+    value.replace('|', "\\|").replace("\r\n", "\n").replace('\n', " \\\\ ")
+}
+
+impl Table {
+    /// Renders the table as JIRA wiki markup: a `||Header||Header||` row followed by
+    /// one `|cell|cell|` line per row, ready to paste into a ticket or comment.
+    /// Headers use the same text as the text renderer.
+    pub fn to_jira(&self) -> String {
+        let mut out = String::from("||");
+        for col in &self.columns {
+            out.push_str(&escape_cell(&self.header_text(col)));
+            out.push_str("||");
+        }
+        out.push('\n');
+        for row in &self.rows {
+            out.push('|');
+            for (idx, cell) in row.cells.iter().enumerate() {
+                out.push_str(&escape_cell(&self.redacted(idx, cell)));
+                out.push('|');
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_jira() {
+        let mut table = Table::new(vec![
+            Column::new("Key", 0, Justification::Left).unwrap(),
+            Column::new("Notes", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["OPS-1".to_string(), "a|b\nsecond line".to_string()]).unwrap();
+        table.add_row(vec!["OPS-2".to_string(), String::new()]).unwrap();
+        assert_eq!(table.to_jira(), "||Key||Notes||\n|OPS-1|a\\|b \\\\ second line|\n|OPS-2| |\n");
+    }
+}
@@ -0,0 +1,464 @@
+use std::io::{BufRead, BufWriter, Write};
+
+use crate::{Column, Justification, OutputFormat, Row, Table};
+
+/// A parsed JSON value. Numbers keep their original text so that cell values
+/// round-trip exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Converts the value to cell text: strings are used as-is, null becomes
+    /// empty, and arrays/objects are written back as compact JSON.
+    pub(crate) fn to_cell(&self) -> String {
+        match self {
+            JsonValue::Null => String::new(),
+            JsonValue::Bool(b) => b.to_string(),
+            JsonValue::Number(n) => n.clone(),
+            JsonValue::String(s) => s.clone(),
+            JsonValue::Array(_) | JsonValue::Object(_) => {
+                let mut out = String::new();
+                self.write_compact(&mut out);
+                out
+            }
+        }
+    }
+
+    /// Writes the value as compact JSON
+    pub(crate) fn write_compact(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Number(n) => out.push_str(n),
+            JsonValue::String(s) => push_string(out, s),
+            JsonValue::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write_compact(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    push_string(out, key);
+                    out.push(':');
+                    value.write_compact(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Appends a JSON string literal (with quotes) for the given text
+pub(crate) fn push_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Deepest nesting of arrays and objects the parser accepts
+const MAX_DEPTH: usize = 128;
+
+/// Parses a complete JSON document
+pub(crate) fn parse(text: &str) -> Result<JsonValue, String> {
+    let mut parser = Parser { chars: text.char_indices().peekable(), text, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some((pos, c)) => Err(format!("unexpected '{}' at offset {}", c, pos)),
+    }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+    /// Number of arrays and objects enclosing the current position
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some((_, ' ' | '\t' | '\n' | '\r'))) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((pos, c)) => Err(format!("expected '{}' but found '{}' at offset {}", expected, c, pos)),
+            None => Err(format!("expected '{}' but reached end of input", expected)),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, String> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err("unexpected end of input".to_string()),
+            Some((pos, '{' | '[')) if self.depth == MAX_DEPTH => {
+                Err(format!("values nested more than {} deep at offset {}", MAX_DEPTH, pos))
+            }
+            Some((_, '{')) => self.nested(Self::object),
+            Some((_, '[')) => self.nested(Self::array),
+            Some((_, '"')) => self.string().map(JsonValue::String),
+            Some((_, 't')) => self.literal("true", JsonValue::Bool(true)),
+            Some((_, 'f')) => self.literal("false", JsonValue::Bool(false)),
+            Some((_, 'n')) => self.literal("null", JsonValue::Null),
+            Some((start, c)) if c == '-' || c.is_ascii_digit() => {
+                let mut end = start;
+                while let Some(&(pos, c)) = self.chars.peek() {
+                    if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                        end = pos + 1;
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let number = &self.text[start..end];
+                if number.parse::<f64>().is_err() {
+                    return Err(format!("invalid number '{}' at offset {}", number, start));
+                }
+                Ok(JsonValue::Number(number.to_string()))
+            }
+            Some((pos, c)) => Err(format!("unexpected '{}' at offset {}", c, pos)),
+        }
+    }
+
+    /// Parses an array or object one level deeper
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<JsonValue, String>) -> Result<JsonValue, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<JsonValue, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if let Some((_, '}')) = self.chars.peek() {
+            self.chars.next();
+            return Ok(JsonValue::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+            fields.push((key, value));
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(JsonValue::Object(fields)),
+                Some((pos, c)) => return Err(format!("expected ',' or '}}' but found '{}' at offset {}", c, pos)),
+                None => return Err("unterminated object".to_string()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if let Some((_, ']')) = self.chars.peek() {
+            self.chars.next();
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(JsonValue::Array(items)),
+                Some((pos, c)) => return Err(format!("expected ',' or ']' but found '{}' at offset {}", c, pos)),
+                None => return Err("unterminated array".to_string()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("unterminated string".to_string()),
+                Some((_, '"')) => return Ok(result),
+                Some((pos, '\\')) => match self.chars.next() {
+                    Some((_, '"')) => result.push('"'),
+                    Some((_, '\\')) => result.push('\\'),
+                    Some((_, '/')) => result.push('/'),
+                    Some((_, 'b')) => result.push('\u{8}'),
+                    Some((_, 'f')) => result.push('\u{c}'),
+                    Some((_, 'n')) => result.push('\n'),
+                    Some((_, 'r')) => result.push('\r'),
+                    Some((_, 't')) => result.push('\t'),
+                    Some((_, 'u')) => {
+                        let high = self.hex4()?;
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            self.expect('\\')?;
+                            self.expect('u')?;
+                            let low = self.hex4()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        } else {
+                            high
+                        };
+                        result.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                    }
+                    _ => return Err(format!("invalid escape at offset {}", pos)),
+                },
+                Some((_, c)) => result.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.chars.next()
+                .and_then(|(_, c)| c.to_digit(16))
+                .ok_or_else(|| "invalid \\u escape".to_string())?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+}
+
+impl Table {
+    /// Returns the keys of row objects: each column's JSON alias or name
+    fn json_keys(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.name_for(OutputFormat::Json)).collect()
+    }
+
+    /// Appends a row as a compact JSON object of its redacted cells
+    fn push_object(&self, out: &mut String, keys: &[&str], row: &Row) {
+        out.push('{');
+        for (i, (key, value)) in keys.iter().zip(&row.cells).enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            push_string(out, key);
+            out.push(':');
+            push_string(out, &self.redacted(i, value));
+        }
+        out.push('}');
+    }
+
+    /// Renders the table as a JSON array with one object per row, keyed by each
+    /// column's JSON alias or name, for machine-readable output of the same table
+    /// that is printed for people. Values are strings; each object is on its own line.
+    pub fn to_json(&self) -> String {
+        let keys = self.json_keys();
+        let mut out = String::from("[");
+        for (idx, row) in self.rows.iter().enumerate() {
+            out.push_str(if idx > 0 { ",\n" } else { "\n" });
+            self.push_object(&mut out, &keys, row);
+        }
+        out.push_str(if self.rows.is_empty() { "]\n" } else { "\n]\n" });
+        out
+    }
+
+    /// Writes the table as JSON Lines (NDJSON): one object per row, keyed by
+    /// each column's JSON alias or name. Rows are streamed through a buffer to the
+    /// writer, so memory use stays flat however many rows the table has.
+    pub fn write_ndjson(&self, writer: impl Write) -> std::io::Result<()> {
+        let keys = self.json_keys();
+        let mut writer = BufWriter::new(writer);
+        let mut line = String::new();
+        for row in &self.rows {
+            line.clear();
+            self.push_object(&mut line, &keys, row);
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the table as JSON Lines; the same as write_ndjson
+    pub fn to_jsonl<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_ndjson(writer)
+    }
+
+    /// Reads a table from JSON Lines (NDJSON), one object per line.
+    /// Columns are the union of keys in first-seen order (left-justified, not truncated);
+    /// missing keys become empty cells. Strings are used as-is, numbers and booleans keep
+    /// their JSON text, null becomes empty, and nested values are stored as compact JSON.
+    /// Blank lines are skipped.
+    ///
+    /// # Errors
+    /// - If reading fails or a line is not a JSON object
+    /// - If no objects are found
+    /// - If the rows exceed the table limits
+    pub fn from_jsonl<R: BufRead>(reader: R) -> Result<Table, String> {
+        let mut keys: Vec<String> = Vec::new();
+        let mut records: Vec<Vec<(usize, String)>> = Vec::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| format!("Table::from_jsonl: line {}: {}", idx + 1, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = match parse(&line) {
+                Ok(JsonValue::Object(fields)) => fields,
+                Ok(_) => return Err(format!("Table::from_jsonl: line {}: expected a JSON object", idx + 1)),
+                Err(e) => return Err(format!("Table::from_jsonl: line {}: {}", idx + 1, e)),
+            };
+            let record = fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let pos = match keys.iter().position(|k| *k == key) {
+                        Some(pos) => pos,
+                        None => {
+                            keys.push(key);
+                            keys.len() - 1
+                        }
+                    };
+                    (pos, value.to_cell())
+                })
+                .collect();
+            records.push(record);
+        }
+
+        if keys.is_empty() {
+            return Err("Table::from_jsonl: no JSON objects with keys found".to_string());
+        }
+
+        let columns = keys
+            .into_iter()
+            .map(|key| Column::new(key, 0, Justification::Left))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::from_jsonl: {}", e))?;
+        let mut table = Table::new(columns).map_err(|e| format!("Table::from_jsonl: {}", e))?;
+        let column_count = table.columns.len();
+        for record in records {
+            let mut row = vec![String::new(); column_count];
+            for (pos, value) in record {
+                row[pos] = value;
+            }
+            table.add_row(row).map_err(|e| format!("Table::from_jsonl: {}", e))?;
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let value = parse(r#"{"a": [1, -2.5e3, true, null], "b": "x\"yé😀"}"#).unwrap();
+        let mut out = String::new();
+        value.write_compact(&mut out);
+        assert_eq!(out, "{\"a\":[1,-2.5e3,true,null],\"b\":\"x\\\"yé😀\"}");
+        assert!(parse("{\"a\": }").is_err());
+        assert!(parse("[1, 2] 3").is_err());
+        assert!(parse(&format!("{}{}", "[".repeat(128), "]".repeat(128))).is_ok());
+        assert!(parse(&"[".repeat(100_000)).unwrap_err().starts_with("values nested more than 128 deep"));
+    }
+
+    #[test]
+    fn test_jsonl_round_trip() {
+        let mut qty = Column::new("Quantity", 0, Justification::Right).unwrap();
+        qty.set_alias(OutputFormat::Json, "qty");
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            qty,
+        ]).unwrap();
+        table.add_row(vec!["Widget \"A\"".to_string(), "3".to_string()]).unwrap();
+        table.add_row(vec!["Line1\nLine2".to_string(), "10".to_string()]).unwrap();
+
+        let mut out = Vec::new();
+        table.to_jsonl(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "{\"Name\":\"Widget \\\"A\\\"\",\"qty\":\"3\"}\n{\"Name\":\"Line1\\nLine2\",\"qty\":\"10\"}\n");
+
+        let loaded = Table::from_jsonl(text.as_bytes()).unwrap();
+        assert_eq!(loaded.row_count(), 2);
+        assert_eq!(loaded.columns()[1].name(), "qty");
+        assert_eq!(loaded.row(1).unwrap(), ["Line1\nLine2", "10"]);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut table = Table::with_headers(["Name", "Size"]).unwrap();
+        assert_eq!(table.to_json(), "[]\n");
+        table.add_row(vec!["a\"b".to_string(), "10".to_string()]).unwrap();
+        table.add_row(vec!["c".to_string(), String::new()]).unwrap();
+        assert_eq!(table.to_json(), "[\n{\"Name\":\"a\\\"b\",\"Size\":\"10\"},\n{\"Name\":\"c\",\"Size\":\"\"}\n]\n");
+        assert!(matches!(parse(&table.to_json()), Ok(JsonValue::Array(rows)) if rows.len() == 2));
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        /// Counts the writes that reach it
+        struct Counter(Vec<u8>, usize);
+        impl Write for Counter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.1 += 1;
+                self.0.write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut table = Table::with_headers(["N"]).unwrap();
+        for n in 0..100 {
+            table.add_row(vec![n.to_string()]).unwrap();
+        }
+        let mut out = Counter(Vec::new(), 0);
+        table.write_ndjson(&mut out).unwrap();
+        let text = String::from_utf8(out.0).unwrap();
+        assert_eq!(text.lines().count(), 100);
+        assert_eq!(text.lines().nth(42), Some("{\"N\":\"42\"}"));
+        assert_eq!(out.1, 1);
+    }
+
+    #[test]
+    fn test_from_jsonl_union_of_keys() {
+        let input = "{\"a\": 1, \"b\": null}\n\n{\"c\": {\"x\": [1]}, \"a\": true}\n";
+        let table = Table::from_jsonl(input.as_bytes()).unwrap();
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(table.row(0).unwrap(), ["1", "", ""]);
+        assert_eq!(table.row(1).unwrap(), ["true", "", "{\"x\":[1]}"]);
+
+        assert!(Table::from_jsonl("[1]\n".as_bytes()).is_err());
+        assert!(Table::from_jsonl("".as_bytes()).is_err());
+        assert!(Table::from_jsonl("{\"\": 1}\n".as_bytes()).unwrap_err().starts_with("Table::from_jsonl: "));
+    }
+}
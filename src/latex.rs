@@ -0,0 +1,103 @@
+use crate::{Justification, Table};
+
+/// Escapes text for LaTeX: the special characters get their escaped forms
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes a cell for a tabular environment. Cells with several lines are
+/// stacked with \shortstack, aligned like the column.
+fn escape_cell(value: &str, spec: char) -> String {
+    let value = value.replace("\r\n", "\n");
+    if !value.contains('\n') {
+        return escape_text(&value);
+    }
+    let lines: Vec<String> = value.split('\n').map(escape_text).collect();
+    format!("\\shortstack[{}]{{{}}}", spec, lines.join("\\\\"))
+}
+
+/// Returns the tabular column specifier for a justification
+fn column_spec(justification: Justification) -> char {
+    match justification {
+        Justification::Left => 'l',
+        Justification::Center => 'c',
+        Justification::Right | Justification::Decimal => 'r',
+    }
+}
+
+impl Table {
+    /// Renders the table as a LaTeX tabular environment, for papers and reports.
+    /// Each column gets an l, c, or r specifier from its justification (Decimal
+    /// columns are right-justified), and headers with their own justification are
+    /// wrapped in \multicolumn. Special characters are escaped and cells with
+    /// several lines are stacked. Headers use the same text as the text renderer.
+    pub fn to_latex(&self) -> String {
+        let specs: Vec<char> = (0..self.columns.len()).map(|idx| column_spec(self.cell_align(idx).justification)).collect();
+        let mut out = format!("\\begin{{tabular}}{{{}}}\n\\hline\n", specs.iter().collect::<String>());
+        let headers: Vec<String> = self.columns
+            .iter()
+            .zip(&specs)
+            .map(|(col, &spec)| {
+                let header_spec = col.header_justification.map_or(spec, column_spec);
+                let text = escape_cell(&self.header_text(col), header_spec);
+                if header_spec == spec {
+                    text
+                } else {
+                    format!("\\multicolumn{{1}}{{{}}}{{{}}}", header_spec, text)
+                }
+            })
+            .collect();
+        out.push_str(&headers.join(" & "));
+        out.push_str(" \\\\\n\\hline\n");
+        for row in &self.rows {
+            let cells: Vec<String> = row.cells
+                .iter()
+                .enumerate()
+                .map(|(idx, cell)| escape_cell(&self.redacted(idx, cell), specs[idx]))
+                .collect();
+            out.push_str(&cells.join(" & "));
+            out.push_str(" \\\\\n");
+        }
+        out.push_str("\\hline\n\\end{tabular}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+
+    #[test]
+    fn test_to_latex() {
+        let mut table = Table::new(vec![
+            Column::new("Item_name", 0, Justification::Left).unwrap(),
+            Column::new("Share", 0, Justification::Right).unwrap(),
+            Column::new("Note", 0, Justification::Center).unwrap(),
+        ]).unwrap();
+        table.columns[1].set_header_justification(Justification::Left);
+        table.add_row(vec!["R&D #1".to_string(), "50%".to_string(), "a\\b ~{x}^".to_string()]).unwrap();
+        table.add_row(vec!["$5".to_string(), "3.5".to_string(), "two\nlines".to_string()]).unwrap();
+        assert_eq!(
+            table.to_latex(),
+            "\\begin{tabular}{lrc}\n\\hline\n\
+             Item\\_name & \\multicolumn{1}{l}{Share} & Note \\\\\n\\hline\n\
+             R\\&D \\#1 & 50\\% & a\\textbackslash{}b \\textasciitilde{}\\{x\\}\\textasciicircum{} \\\\\n\
+             \\$5 & 3.5 & \\shortstack[c]{two\\\\lines} \\\\\n\
+             \\hline\n\\end{tabular}\n"
+        );
+    }
+}
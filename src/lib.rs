@@ -1,4 +1,108 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[cfg(feature = "asciidoc")]
+mod asciidoc;
+mod border;
+mod chain;
+#[cfg(feature = "chat")]
+mod chat;
+#[cfg(feature = "streaming")]
+mod collector;
+#[cfg(feature = "terminal")]
+mod console;
+#[cfg(feature = "delimited")]
+mod delimited;
+#[cfg(feature = "fixed-width")]
+mod fixed_width;
+mod footer;
+mod format;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(feature = "jira")]
+mod jira;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "latex")]
+mod latex;
+#[cfg(feature = "streaming")]
+mod lines;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "mediawiki")]
+mod mediawiki;
+#[cfg(feature = "org")]
+mod org;
+#[cfg(feature = "terminal")]
+mod pager;
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "png")]
+mod png;
+#[cfg(feature = "profile")]
+mod profile;
+mod redact;
+mod regex;
+mod row_ref;
+#[cfg(feature = "rst")]
+mod rst;
+mod sample;
+mod section;
+mod spec;
+#[cfg(feature = "sql")]
+mod sql;
+#[cfg(feature = "svg")]
+mod svg;
+mod title;
+#[cfg(feature = "toml")]
+mod toml;
+mod transform;
+mod typed;
+mod union;
+mod validate;
+#[cfg(feature = "interactive")]
+mod viewer;
+mod width;
+mod wrap;
+
+pub use border::{BorderChars, BorderLines, BorderStyle, Padding, TableStyle};
+use border::Frame;
+pub use chain::RowChain;
+#[cfg(feature = "chat")]
+pub use chat::{DEFAULT_MESSAGE_LIMIT, SLACK_CODE_BLOCK_WIDTH};
+#[cfg(feature = "streaming")]
+pub use collector::{TableAssembly, TableCollector};
+#[cfg(feature = "delimited")]
+pub use delimited::{TsvEscaping, TsvOptions};
+pub use format::{AnsiColor, CompactScale, NumberFormat, RoundingMode, ThresholdStyle, Thresholds};
+use format::Crossed;
+#[cfg(feature = "html")]
+pub use html::HtmlOptions;
+#[cfg(feature = "streaming")]
+pub use lines::{Splitter, TableSink};
+#[cfg(feature = "png")]
+pub use png::PngOptions;
+pub use redact::{EMAIL_PATTERN, IPV4_PATTERN, TOKEN_PATTERN};
+use redact::Redaction;
+pub use row_ref::{RowMut, RowRef};
+pub use section::SectionStyle;
+use section::Section;
+pub use spec::ColumnSpec;
+#[cfg(feature = "svg")]
+pub use svg::SvgOptions;
+pub use title::TitleStyle;
+use title::Title;
+pub use typed::{RowTuple, TypedTable};
+pub use union::{ColumnOrder, UnionOptions};
+pub use validate::Lint;
+use width::{char_width, escapes, expand_tabs, strip_escapes, visible_chars};
+pub use wrap::Overflow;
+
 const MAX_TRUNCATE_WIDTH: usize = 5000;
+/// Columns between tab stops when tabs in values are expanded
+const DEFAULT_TAB_WIDTH: usize = 8;
+const MAX_TAB_WIDTH: usize = 32;
 const MAX_TABLE_ROWS: usize = 5_000_000;
 const MAX_CELL_LINES: usize = 5000;
 
@@ -7,13 +111,97 @@ pub struct Column {
     name: String,
     truncate_at: usize,
     justification: Justification,
+    header_justification: Option<Justification>,
+    align_mode: AlignMode,
+    vertical_alignment: VerticalAlignment,
     max_length: usize,
+    // Set when a removed row may have held the longest value or the tab width
+    // changed, so max_length is recomputed from the rows at the next render
+    width_stale: bool,
+    // Set when a value with more than one line was added; cleared when widths are
+    // rescanned. While no column has one, rows take the single-line render path.
+    multiline: bool,
+    header_transform: Option<HeaderTransform>,
+    truncate_header: bool,
+    aliases: BTreeMap<OutputFormat, String>,
+    description: Option<String>,
+    unit: Option<String>,
+    number_format: Option<NumberFormat>,
+    rounding_mode: RoundingMode,
+    thresholds: Option<Thresholds>,
+    value_transform: CaseTransform,
+    truncate_percentile: Option<f64>,
+    truncator: Option<Truncator>,
+    truncate_position: TruncatePosition,
+    ellipsis: String,
+    tab_width: usize,
+    overflow: Overflow,
+    redactions: Vec<Redaction>,
+    fixed_width: Option<usize>,
+    width_percent: Option<f64>,
+    min_width: usize,
+}
+
+/// Arbitrary key/value pairs attached to rows or cells (ids, css classes, data attributes).
+/// The text renderer ignores metadata; structured and custom renderers can consume it.
+pub type Metadata = BTreeMap<String, String>;
+
+/// Output formats a column can carry an alternate name for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum OutputFormat {
+    /// The console/text renderer (Display)
+    Text,
+    Csv,
+    Json,
+    Html,
+    Parquet,
+    Sql,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Justification {
     Left,
     Right,
+    /// Centered, with any odd space on the right
+    Center,
+    /// Right-justified numbers lined up on their decimal points, e.g. "1000.5"
+    /// above "3.25", with the fractions padded on the right. Values without a
+    /// decimal point end where the points of the others are.
+    Decimal,
+}
+
+/// Whether a column keeps its justification or has one chosen from its values
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AlignMode {
+    /// Use the column justification as set
+    #[default]
+    Manual,
+    /// Right-justify the column at render time when every non-empty value is a
+    /// number, and use the column justification otherwise
+    Auto,
+}
+
+/// Which part of a value too wide for its column is replaced by the ellipsis
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TruncatePosition {
+    /// "...tail"
+    Start,
+    /// "head...tail", with any odd character in the head
+    Middle,
+    /// "head..."
+    #[default]
+    End,
+}
+
+/// Where a cell's lines sit in a row made taller by a multiline neighbor
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    /// Centered, with any odd blank line below
+    Middle,
+    Bottom,
 }
 
 impl Column {
@@ -21,7 +209,8 @@ impl Column {
     /// Creates a new Column with the given name, truncation width, and justification.
     /// If truncate_at is 0, no truncation will occur.
     /// The effective truncation width will be the maximum of the provided width
-    /// and the length of the column name to ensure headers are never truncated.
+    /// and the length of the rendered header to ensure headers are never truncated,
+    /// unless header truncation is enabled with set_truncate_header.
     /// 
     /// # Errors
     /// - If name is empty
@@ -36,9 +225,8 @@ impl Column {
                 truncate_at, MAX_TRUNCATE_WIDTH));
         }
 
-        let name_len = name.chars().count();
         let effective_truncate = if truncate_at > 0 {
-            truncate_at.max(3).max(name_len)
+            truncate_at.max(3)
         } else {
             truncate_at
         };
@@ -47,34 +235,437 @@ impl Column {
             name,
             truncate_at: effective_truncate,
             justification,
-            max_length: name_len,
+            header_justification: None,
+            align_mode: AlignMode::Manual,
+            vertical_alignment: VerticalAlignment::Top,
+            max_length: 0,
+            width_stale: false,
+            multiline: false,
+            header_transform: None,
+            truncate_header: false,
+            aliases: BTreeMap::new(),
+            description: None,
+            unit: None,
+            number_format: None,
+            rounding_mode: RoundingMode::default(),
+            thresholds: None,
+            value_transform: CaseTransform::None,
+            truncate_percentile: None,
+            truncator: None,
+            truncate_position: TruncatePosition::End,
+            ellipsis: "...".to_string(),
+            tab_width: DEFAULT_TAB_WIDTH,
+            overflow: Overflow::Truncate,
+            redactions: Vec::new(),
+            fixed_width: None,
+            width_percent: None,
+            min_width: 0,
         })
     }
 
-    /// Sets the justification (Left or Right) for this column
+    /// Sets the justification (Left, Right, Center, or Decimal) for this column
     pub fn set_justification(&mut self, j: Justification) {
         self.justification = j;
     }
 
+    /// Returns the column name as given to Column::new
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets an alternate name used instead of the column name when rendering
+    /// or exporting to the given format (e.g. "Qty" for text, "quantity" for JSON keys)
+    pub fn set_alias(&mut self, format: OutputFormat, alias: impl Into<String>) {
+        self.aliases.insert(format, alias.into());
+    }
+
+    /// Returns the name to use for the given output format: its alias if one
+    /// was set, otherwise the column name
+    pub fn name_for(&self, format: OutputFormat) -> &str {
+        self.aliases.get(&format).map_or(&self.name, String::as_str)
+    }
+
+    /// Sets a description (meaning, units) for this column, shown in the legend
+    /// when the table's legend is enabled
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.description = Some(description.into());
+    }
+
+    /// Returns the column description, if one was set
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Sets the unit or qualifier ("ms", "GiB") shown centered in a row beneath the
+    /// header. The row is rendered when any column has a unit.
+    pub fn set_unit(&mut self, unit: impl Into<String>) {
+        self.unit = Some(unit.into());
+    }
+
+    /// Returns the column unit, if one was set
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Returns the justification of this column
+    pub fn justification(&self) -> Justification {
+        self.justification
+    }
+
+    /// Sets whether the justification is chosen from the column's values at each
+    /// render (see AlignMode::Auto)
+    pub fn set_align_mode(&mut self, mode: AlignMode) {
+        self.align_mode = mode;
+    }
+
+    /// Returns the align mode of this column
+    pub fn align_mode(&self) -> AlignMode {
+        self.align_mode
+    }
+
+    /// Sets the justification of the header, e.g. Center over right-justified
+    /// numbers. By default the header follows the column justification (as
+    /// chosen by AlignMode::Auto); a Decimal column's header is right-justified.
+    pub fn set_header_justification(&mut self, j: Justification) {
+        self.header_justification = Some(j);
+    }
+
+    /// Returns the justification of the header as set, or the column justification
+    pub fn header_justification(&self) -> Justification {
+        self.header_justification.unwrap_or(self.justification)
+    }
+
+    /// Sets where the column's cells sit in rows that another cell makes taller
+    /// (Top by default)
+    pub fn set_vertical_alignment(&mut self, v: VerticalAlignment) {
+        self.vertical_alignment = v;
+    }
+
+    /// Returns the vertical alignment of the column's cells
+    pub fn vertical_alignment(&self) -> VerticalAlignment {
+        self.vertical_alignment
+    }
+
+    /// Sets a header transform for this column, overriding the table-wide transform
+    pub fn set_header_transform(&mut self, t: HeaderTransform) {
+        self.header_transform = Some(t);
+    }
+
+    /// Sets whether the header may be truncated to the data width.
+    /// By default columns are widened to fit their header; with this enabled the
+    /// data (and truncate_at) controls the width and long headers end in "...".
+    pub fn set_truncate_header(&mut self, truncate: bool) {
+        self.truncate_header = truncate;
+    }
+
+    /// Sets how numeric values in this column are displayed. Cells keep their
+    /// original text; formatting applies when the table is rendered.
+    pub fn set_number_format(&mut self, format: NumberFormat) {
+        self.number_format = Some(format);
+    }
+
+    /// Returns the number format, if one was set
+    pub fn number_format(&self) -> Option<&NumberFormat> {
+        self.number_format.as_ref()
+    }
+
+    /// Sets how the number format rounds values (default RoundingMode::HalfUp)
+    pub fn set_rounding_mode(&mut self, mode: RoundingMode) {
+        self.rounding_mode = mode;
+    }
+
+    /// Returns the rounding mode used by the number format
+    pub fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+
+    /// Sets limits beyond which numeric values in this column are highlighted
+    pub fn set_thresholds(&mut self, thresholds: Thresholds) {
+        self.thresholds = Some(thresholds);
+    }
+
+    /// Returns the highlight thresholds, if any were set
+    pub fn thresholds(&self) -> Option<&Thresholds> {
+        self.thresholds.as_ref()
+    }
+
+    /// Sets a case transform for this column's rendered values, e.g. Uppercase for
+    /// status codes collected from sources that disagree on case. It is applied
+    /// after the number format, so widths and alignment follow the transformed text.
+    pub fn set_value_transform(&mut self, t: CaseTransform) {
+        self.value_transform = t;
+    }
+
+    /// Returns the case transform applied to rendered values
+    pub fn value_transform(&self) -> CaseTransform {
+        self.value_transform
+    }
+
+    /// Caps the column width at the given percentile (e.g. 95.0) of its value
+    /// lengths, so a few unusually long values are truncated instead of widening
+    /// the whole column. The cap is recomputed from the rows at each render and is
+    /// never below 3; a truncation width set on the column still applies.
+    ///
+    /// # Errors
+    /// - If percentile is not greater than 0 and at most 100
+    pub fn set_truncate_percentile(&mut self, percentile: f64) -> Result<(), String> {
+        if !(percentile > 0.0 && percentile <= 100.0) {
+            return Err(format!("Column::set_truncate_percentile: percentile {} is not in (0, 100]", percentile));
+        }
+        self.truncate_percentile = Some(percentile);
+        Ok(())
+    }
+
+    /// Returns the percentile the column width is capped at, if set
+    pub fn truncate_percentile(&self) -> Option<f64> {
+        self.truncate_percentile
+    }
+
+    /// Sets a function that shortens values too wide for the column, called with
+    /// the line and the column width in place of the built-in cut with "...".
+    /// Use it for domain-specific shortening such as the first 8 characters of a
+    /// UUID or just the domain of an email address. Results still too wide are cut
+    /// to the column width.
+    pub fn set_truncator(&mut self, f: impl Fn(&str, usize) -> String + Send + Sync + 'static) {
+        self.truncator = Some(Truncator(Arc::new(f)));
+    }
+
+    /// Removes a truncation function set with set_truncator
+    pub fn clear_truncator(&mut self) {
+        self.truncator = None;
+    }
+
+    /// Sets which part of a value too wide for the column is replaced by the ellipsis:
+    /// the end by default, the start to keep the tail, or the middle to keep both
+    /// ends, as for file paths and commit hashes
+    pub fn set_truncate_position(&mut self, position: TruncatePosition) {
+        self.truncate_position = position;
+    }
+
+    /// Returns which part of a value too wide for the column is cut
+    pub fn truncate_position(&self) -> TruncatePosition {
+        self.truncate_position
+    }
+
+    /// Sets the marker that replaces the cut part of truncated values and headers
+    /// ("..." by default), e.g. "…", "~", or "" to cut without one. Values are
+    /// shortened to leave room for the marker's width.
+    pub fn set_ellipsis(&mut self, ellipsis: impl Into<String>) {
+        self.ellipsis = ellipsis.into();
+    }
+
+    /// Returns the marker that replaces the cut part of truncated values
+    pub fn ellipsis(&self) -> &str {
+        &self.ellipsis
+    }
+
+    /// Sets the distance between tab stops used to expand tabs in values into
+    /// spaces (8 by default), so they line up the same in every terminal
+    ///
+    /// # Errors
+    /// - If width is 0 or more than 32
+    pub fn set_tab_width(&mut self, width: usize) -> Result<(), String> {
+        if width == 0 || width > MAX_TAB_WIDTH {
+            return Err(format!("Column::set_tab_width: width {} is not in 1..={}", width, MAX_TAB_WIDTH));
+        }
+        self.tab_width = width;
+        self.width_stale = true;
+        Ok(())
+    }
+
+    /// Returns the distance between tab stops
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Fixes the rendered width of the column regardless of its values, so the
+    /// layout doesn't shift as longer values arrive. Longer values and headers are
+    /// truncated to the width.
+    ///
+    /// # Errors
+    /// - If width is 0 or exceeds MAX_TRUNCATE_WIDTH (5000)
+    pub fn set_fixed_width(&mut self, width: usize) -> Result<(), String> {
+        if width == 0 || width > MAX_TRUNCATE_WIDTH {
+            return Err(format!("Column::set_fixed_width: width {} is not in 1..={}", width, MAX_TRUNCATE_WIDTH));
+        }
+        self.fixed_width = Some(width);
+        Ok(())
+    }
+
+    /// Lets the column width follow its values again after set_fixed_width
+    pub fn clear_fixed_width(&mut self) {
+        self.fixed_width = None;
+    }
+
+    /// Returns the fixed width, if one was set
+    pub fn fixed_width(&self) -> Option<usize> {
+        self.fixed_width
+    }
+
+    /// Sets the column width as a percentage of the table's maximum width (see
+    /// Table::set_max_width), less the spaces between columns. Values that don't
+    /// fit are truncated or wrapped as the overflow mode says. The percentage is
+    /// ignored while the table has no maximum width, and a fixed width takes
+    /// precedence.
+    ///
+    /// # Errors
+    /// - If percent is not greater than 0 and at most 100
+    pub fn set_width_percent(&mut self, percent: f64) -> Result<(), String> {
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(format!("Column::set_width_percent: percent {} is not in (0, 100]", percent));
+        }
+        self.width_percent = Some(percent);
+        Ok(())
+    }
+
+    /// Lets the column width follow its values again after set_width_percent
+    pub fn clear_width_percent(&mut self) {
+        self.width_percent = None;
+    }
+
+    /// Returns the width percentage, if one was set
+    pub fn width_percent(&self) -> Option<f64> {
+        self.width_percent
+    }
+
+    /// Sets a width the column is never rendered narrower than, however short its
+    /// values and header, so reports keep the same shape from run to run. It also
+    /// limits how far fit_to_width and a maximum table width narrow the column.
+    /// A fixed width takes precedence.
+    ///
+    /// # Errors
+    /// - If width exceeds MAX_TRUNCATE_WIDTH (5000)
+    pub fn set_min_width(&mut self, width: usize) -> Result<(), String> {
+        if width > MAX_TRUNCATE_WIDTH {
+            return Err(format!("Column::set_min_width: width {} exceeds maximum allowed ({})", width, MAX_TRUNCATE_WIDTH));
+        }
+        self.min_width = width;
+        Ok(())
+    }
+
+    /// Returns the minimum width (0 if none was set)
+    pub fn min_width(&self) -> usize {
+        self.min_width
+    }
+
+    /// Returns the narrowest width the column may be shrunk to when fitting the
+    /// table to a width
+    fn narrowest(&self) -> usize {
+        self.min_width.max(3)
+    }
+
+    /// Shortens a line to the width using the column's truncator, if any
+    fn truncate(&self, line: &str, width: usize) -> String {
+        match &self.truncator {
+            Some(truncator) if text_width(line) > width => {
+                let shortened = (truncator.0)(line, width);
+                take_width(&shortened, width).to_string()
+            }
+            _ => truncate_line_at(line, width, self.truncate_position, &self.ellipsis),
+        }
+    }
+
+    /// Returns a cell value as it should be displayed, applying redactions (the
+    /// column's, then the table's), the number format, the value transform, and
+    /// any threshold flag, with tabs expanded
+    fn display_value<'a>(&self, value: &'a str, table_redactions: &[Redaction]) -> Cow<'a, str> {
+        let redacted = redact::redact(value, self.redactions.iter().chain(table_redactions));
+        let formatted = match self.number_format.as_ref().and_then(|f| f.apply(&redacted, self.rounding_mode)) {
+            Some(formatted) => Cow::Owned(formatted),
+            None => redacted,
+        };
+        let formatted = match self.value_transform {
+            CaseTransform::None => formatted,
+            t => Cow::Owned(t.apply(&formatted)),
+        };
+        let flag = self.thresholds.as_ref().and_then(|t| t.check(value).and_then(|crossed| t.flag(crossed)));
+        let flagged = match flag {
+            Some(flag) => Cow::Owned(format!("{} {}", formatted, flag)),
+            None => formatted,
+        };
+        if flagged.contains('\t') {
+            Cow::Owned(expand_tabs(&flagged, self.tab_width))
+        } else {
+            flagged
+        }
+    }
+
+    /// Returns the width of the longest line of a value once its tabs are expanded
+    fn value_width(&self, value: &str) -> usize {
+        if value.contains('\t') {
+            cell_width(&expand_tabs(value, self.tab_width))
+        } else {
+            cell_width(value)
+        }
+    }
+
+    /// Describes the thresholds for the scale legend, e.g. "! below 95.0%", with
+    /// limits shown in the column's number format. Colored thresholds are left out
+    /// when color is off, since nothing in the table marks them.
+    fn scale_legend(&self, color: bool) -> Option<String> {
+        let thresholds = self.thresholds.as_ref()?;
+        let parts: Vec<String> = [(Crossed::Below, thresholds.below, "below"), (Crossed::Above, thresholds.above, "above")]
+            .into_iter()
+            .filter_map(|(crossed, limit, word)| {
+                let limit = limit?.to_string();
+                let mark = match (thresholds.color(crossed), thresholds.flag(crossed)) {
+                    (Some(_), _) if !color => return None,
+                    (Some(color), _) => color.paint("■"),
+                    (None, Some(flag)) => flag.to_string(),
+                    (None, None) => return None,
+                };
+                let limit = match &self.number_format {
+                    Some(format) => format.apply(&limit, self.rounding_mode).unwrap_or(limit),
+                    None => limit,
+                };
+                Some(format!("{} {} {}", mark, word, limit))
+            })
+            .collect();
+        if parts.is_empty() { None } else { Some(parts.join(", ")) }
+    }
+
+    /// Returns the terminal color for a cell value that crosses a Color-style threshold
+    fn threshold_color(&self, value: &str) -> Option<AnsiColor> {
+        let thresholds = self.thresholds.as_ref()?;
+        thresholds.color(thresholds.check(value)?)
+    }
+
     /// Updates the maximum length of the column based on the content.
     /// For multiline values, considers the longest line.
     fn update_max_length(&mut self, value: &str) {
-        for line in value.split('\n') {
-            let len = line.chars().count();
-            if len > self.max_length {
-                self.max_length = len;
-            }
+        self.multiline |= value.contains('\n');
+        let len = self.value_width(value);
+        if len > self.max_length {
+            self.max_length = len;
+        }
+    }
+
+    /// Returns the width needed for data whose longest line has the given length,
+    /// capped at the truncation width when truncation applies.
+    fn data_width(&self, max_length: usize) -> usize {
+        if self.truncate_at > 0 && max_length > self.truncate_at {
+            self.truncate_at
+        } else {
+            max_length
         }
     }
 
     /// Formats a single cell's content for display in the table.
     /// Handles:
     /// - Splitting multiline content into separate lines (split by \n)
-    /// - Truncating lines that exceed max width (adding "...")
+    /// - Wrapping or truncating lines that exceed max width (see Overflow)
     /// - Padding lines to match column width
-    /// - Applying left/right justification
+    /// - Applying the alignment chosen for the render
+    ///
     /// Returns a vector of formatted strings, one for each line in the cell.
-    fn format_cell(&self, cell_value: &str) -> Result<Vec<String>, String> {
+    fn format_cell(
+        &self,
+        cell_value: &str,
+        width: usize,
+        align: CellAlign,
+        table_redactions: &[Redaction],
+    ) -> Result<Vec<String>, String> {
+        let cell_value = self.display_value(cell_value, table_redactions);
         let lines: Vec<&str> = cell_value.split('\n').collect();
         if lines.len() > MAX_CELL_LINES {
             return Err(format!("Column::format_cell: number of lines ({}) exceeds maximum allowed ({})",
@@ -82,119 +673,1437 @@ impl Column {
         }
         
         Ok(lines.into_iter()
+            .flat_map(|line| self.wrap(line, width))
             .map(|line| {
-                let value_len = line.chars().count();
-                let mut result = if self.truncate_at > 0 && value_len > self.truncate_at {
-                    // Truncate the string if needed, leaving room for "..."
-                    let truncate_pos = self.truncate_at.saturating_sub(3);
-                    let mut truncated = line.chars().take(truncate_pos).collect::<String>();
-                    truncated.push_str("...");
-                    truncated
-                } else {
-                    line.to_string()
-                };
-
-                // Use max_length for width unless truncation is needed
-                let effective_width = if self.truncate_at > 0 && self.max_length > self.truncate_at {
-                    self.truncate_at
-                } else {
-                    self.max_length
-                };
-
-                // Pad the string based on justification
-                if result.chars().count() < effective_width {
-                    let padding = " ".repeat(effective_width - result.chars().count());
-                    match self.justification {
-                        Justification::Left => result.push_str(&padding),
-                        Justification::Right => result = format!("{}{}", padding, result),
-                    }
-                }
-
-                result
+                let mut padded = String::with_capacity(width);
+                self.push_padded(&mut padded, &line, width, align);
+                padded
             })
             .collect())
     }
 
-    /// Creates an empty string of spaces matching the column's width.
-    /// Used for padding multiline rows where some columns have fewer lines than others.
-    fn format_empty(&self) -> String {
-        let effective_width = if self.truncate_at > 0 && self.max_length > self.truncate_at {
-            self.truncate_at
+    /// Pads a header line to the given width, using the header justification if
+    /// one was set and otherwise the one chosen for the render
+    fn pad(&self, text: String, width: usize, justification: Justification) -> String {
+        let len = text_width(&text);
+        if len >= width {
+            return text;
+        }
+        let mut padded = String::with_capacity(width + text.len());
+        push_justified(&mut padded, &text, width - len, self.header_justification.unwrap_or(justification), 0);
+        padded
+    }
+
+    /// Appends a line to out, truncated and padded to the given width
+    fn push_padded(&self, out: &mut String, text: &str, width: usize, align: CellAlign) {
+        let len = text_width(text);
+        if len > width {
+            let truncated = self.truncate(text, width);
+            let padding = width.saturating_sub(text_width(&truncated));
+            push_justified(out, &truncated, padding, align.justification, 0);
+        } else {
+            push_justified(out, text, width - len, align.justification, align.fraction);
+        }
+    }
+
+    /// Formats the header text, truncating it to the width if header truncation is enabled.
+    /// Headers too narrow to hold more than the ellipsis are cut without one.
+    fn format_header(&self, text: String, width: usize, justification: Justification) -> String {
+        // Columns are only narrower than their header when truncation is enabled,
+        // the width is fixed or a percentage, or the table has a maximum width
+        let truncate = text_width(&text) > width;
+        if truncate && width <= text_width(&self.ellipsis) {
+            self.pad(take_width(&text, width).to_string(), width, justification)
+        } else if truncate {
+            self.pad(truncate_line_at(&text, width, TruncatePosition::End, &self.ellipsis), width, justification)
         } else {
-            self.max_length
+            self.pad(text, width, justification)
+        }
+    }
+}
+
+/// Appends text to out with padding spaces placed by the justification. Decimal
+/// text gets enough of them on the right to pad its decimal part to fraction.
+fn push_justified(out: &mut String, text: &str, padding: usize, justification: Justification, fraction: usize) {
+    match justification {
+        Justification::Left => {
+            out.push_str(text);
+            out.extend(std::iter::repeat_n(' ', padding));
+        }
+        Justification::Right => {
+            out.extend(std::iter::repeat_n(' ', padding));
+            out.push_str(text);
+        }
+        Justification::Center => {
+            out.extend(std::iter::repeat_n(' ', padding / 2));
+            out.push_str(text);
+            out.extend(std::iter::repeat_n(' ', padding - padding / 2));
+        }
+        Justification::Decimal => {
+            let after = fraction.saturating_sub(fraction_width(text)).min(padding);
+            out.extend(std::iter::repeat_n(' ', padding - after));
+            out.push_str(text);
+            out.extend(std::iter::repeat_n(' ', after));
+        }
+    }
+}
+
+/// Returns true if the value is a number
+fn is_number(value: &str) -> bool {
+    let value = value.trim();
+    // f64 also parses "inf" and "NaN", which are more likely words
+    value.bytes().any(|b| b.is_ascii_digit()) && value.parse::<f64>().is_ok()
+}
+
+/// Returns the width of a number's decimal part: from a decimal point next to a
+/// digit to the end of the line (so suffixes like "%" count), or 0 if there is none.
+/// Only visible characters count, so a '.' inside an escape sequence is skipped.
+fn fraction_width(line: &str) -> usize {
+    let chars: Vec<(usize, char)> = visible_chars(line).collect();
+    let digit_at = |idx: Option<usize>| idx.and_then(|i| chars.get(i)).is_some_and(|(_, c)| c.is_ascii_digit());
+    chars
+        .iter()
+        .enumerate()
+        .find(|&(idx, &(_, c))| c == '.' && (digit_at(idx.checked_sub(1)) || digit_at(Some(idx + 1))))
+        .map_or(0, |(_, &(pos, _))| text_width(&line[pos..]))
+}
+
+/// Centers text in the given width, with any odd space on the right
+fn center(text: &str, width: usize) -> String {
+    let space = width.saturating_sub(text_width(text));
+    format!("{}{}{}", " ".repeat(space / 2), text, " ".repeat(space - space / 2))
+}
+
+/// Returns the display width of a single line of text in terminal columns, with
+/// East Asian wide characters counting two and combining marks and ANSI escape
+/// sequences none. Pure ASCII text without escapes (the common case) is measured
+/// by byte length without decoding.
+fn text_width(s: &str) -> usize {
+    if is_plain(s) {
+        s.len()
+    } else {
+        visible_chars(s).map(|(_, c)| char_width(c)).sum()
+    }
+}
+
+/// Returns true if every character of s is one column wide
+fn is_plain(s: &str) -> bool {
+    s.is_ascii() && !s.contains('\x1b')
+}
+
+/// Returns the width of the longest line of a (possibly multiline) cell value
+fn cell_width(value: &str) -> usize {
+    value.split('\n').map(text_width).max().unwrap_or(0)
+}
+
+/// Returns the longest prefix of a line that fits in the given width
+/// (a wide character that would straddle the edge is left out)
+fn take_width(s: &str, width: usize) -> &str {
+    if is_plain(s) {
+        return &s[..width.min(s.len())];
+    }
+    let mut used = 0;
+    for (idx, c) in visible_chars(s) {
+        used += char_width(c);
+        if used > width {
+            return &s[..idx];
+        }
+    }
+    s
+}
+
+/// Returns the longest suffix of a line that fits in the given width
+fn take_width_end(s: &str, width: usize) -> &str {
+    if is_plain(s) {
+        return &s[s.len() - width.min(s.len())..];
+    }
+    let mut used = 0;
+    let visible: Vec<(usize, char)> = visible_chars(s).collect();
+    for &(idx, c) in visible.iter().rev() {
+        used += char_width(c);
+        if used > width {
+            return &s[idx + c.len_utf8()..];
+        }
+    }
+    s
+}
+
+/// Truncates a line to the given width, replacing the tail with "..." when it doesn't fit.
+/// Lines that already fit are returned unchanged, and widths too narrow for the
+/// ellipsis are cut without one.
+fn truncate_line(line: &str, width: usize) -> String {
+    truncate_line_at(line, width, TruncatePosition::End, "...")
+}
+
+/// Truncates a line to the given width, replacing the part at the position with
+/// the ellipsis (see truncate_line). ANSI escape sequences are never split: a
+/// kept head with any is ended with a reset, and the sequences of a cut-off head
+/// are replayed before the kept tail so it keeps its color.
+fn truncate_line_at(line: &str, width: usize, position: TruncatePosition, ellipsis: &str) -> String {
+    if text_width(line) <= width {
+        return line.to_string();
+    }
+    let reset = |head: &str| if head.contains('\x1b') { "\x1b[0m" } else { "" };
+    let replay = |tail: &str| escapes(&line[..line.len() - tail.len()]);
+    let ellipsis_width = text_width(ellipsis);
+    if width < ellipsis_width {
+        return match position {
+            TruncatePosition::Start => {
+                let tail = take_width_end(line, width);
+                format!("{}{}", replay(tail), tail)
+            }
+            _ => {
+                let head = take_width(line, width);
+                format!("{}{}", head, reset(head))
+            }
         };
-        " ".repeat(effective_width)
+    }
+    // Leave room for the ellipsis
+    let keep = width - ellipsis_width;
+    match position {
+        TruncatePosition::End => {
+            let head = take_width(line, keep);
+            format!("{}{}{}", head, reset(head), ellipsis)
+        }
+        TruncatePosition::Start => {
+            let tail = take_width_end(line, keep);
+            format!("{}{}{}", ellipsis, replay(tail), tail)
+        }
+        TruncatePosition::Middle => {
+            let head = take_width(line, keep - keep / 2);
+            let tail = take_width_end(line, keep / 2);
+            format!("{}{}{}{}{}", head, reset(head), ellipsis, replay(tail), tail)
+        }
     }
 }
 
-#[derive(Debug)]
-pub struct Table {
-    columns: Vec<Column>,
-    rows: Vec<Vec<String>>,
+/// Escapes text for use in HTML and SVG content and attribute values
+#[cfg(any(feature = "html", feature = "svg"))]
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
 }
 
-impl Table {
-    
-    /// Creates a new Table with the specified columns
-    /// 
-    /// # Errors
-    /// - If columns vector is empty
-    pub fn new(columns: Vec<Column>) -> Result<Self, String> {
-        if columns.is_empty() {
-            return Err("Table::new: table must have at least one column".to_string());
+/// Colors the text of a padded line, leaving the padding outside the color codes
+fn paint_content(line: &str, color: AnsiColor) -> String {
+    let content = line.trim_matches(' ');
+    if content.is_empty() {
+        return line.to_string();
+    }
+    let start = line.len() - line.trim_start_matches(' ').len();
+    let end = start + content.len();
+    format!("{}{}{}", &line[..start], color.paint(content), &line[end..])
+}
+
+/// Text transforms applied to column names when rendering headers
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HeaderTransform {
+    /// Render the column name as given
+    #[default]
+    None,
+    /// "request id" -> "REQUEST ID"
+    Uppercase,
+    /// "Request ID" -> "request id"
+    Lowercase,
+    /// "request id" -> "Request Id"
+    TitleCase,
+    /// "request_id" -> "Request Id"
+    SnakeToTitle,
+}
+
+impl HeaderTransform {
+    /// Applies the transform to a column name
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            HeaderTransform::None => name.to_string(),
+            HeaderTransform::Uppercase => name.to_uppercase(),
+            HeaderTransform::Lowercase => name.to_lowercase(),
+            HeaderTransform::TitleCase => title_case(name),
+            HeaderTransform::SnakeToTitle => title_case(&name.replace('_', " ")),
         }
-        
-        Ok(Table {
-            columns,
-            rows: Vec::new(),
-        })
     }
+}
 
-    /// Adds a row to the table.
-    /// 
-    /// # Errors
-    /// - If number of values doesn't match number of columns
-    /// - If table would exceed MAX_TABLE_ROWS (5,000,000)
-    /// - If any cell contains more than MAX_CELL_LINES (5000) lines
-    pub fn add_row(&mut self, row: Vec<String>) -> Result<(), String> {
-        if row.len() != self.columns.len() {
-            return Err(format!(
-                "Table::add_row: row has {} columns, expected {}",
-                row.len(),
-                self.columns.len()
-            ));
+/// Case transforms applied to cell values when rendering (Column::set_value_transform)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CaseTransform {
+    /// Render values as given
+    #[default]
+    None,
+    /// "ok" -> "OK"
+    Uppercase,
+    /// "FAILED" -> "failed"
+    Lowercase,
+    /// "in progress" -> "In Progress"
+    TitleCase,
+}
+
+impl CaseTransform {
+    /// Applies the transform to a value
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            CaseTransform::None => value.to_string(),
+            CaseTransform::Uppercase => value.to_uppercase(),
+            CaseTransform::Lowercase => value.to_lowercase(),
+            CaseTransform::TitleCase => title_case(value),
         }
+    }
+}
 
-        if self.rows.len() >= MAX_TABLE_ROWS {
-            return Err(format!(
-                "Table::add_row: cannot add more rows, maximum ({}) reached",
-                MAX_TABLE_ROWS
-            ));
+/// Capitalizes the first letter of each whitespace-separated word and lowercases the rest
+fn title_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut at_word_start = true;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            at_word_start = true;
+            result.push(c);
+        } else if at_word_start {
+            at_word_start = false;
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
         }
+    }
+    result
+}
 
-        // Validate multiline limits before updating anything
-        for value in &row {
-            let line_count = value.split('\n').count();
-            if line_count > MAX_CELL_LINES {
-                return Err(format!(
+/// Terminator written after each rendered line
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineEnding {
+    /// "\n"
+    #[default]
+    Lf,
+    /// "\r\n", for Windows tools and protocols that require it
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the terminator text
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Table-wide rendering settings
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RenderOptions {
+    /// Transform applied to headers of columns that don't set their own
+    pub header_transform: HeaderTransform,
+    /// Skip the padding after the last column so lines have no trailing spaces
+    pub trim_trailing_padding: bool,
+    /// Render a legend of column descriptions beneath the table
+    pub show_legend: bool,
+    /// Render a legend beneath the table of the colors and markers used by
+    /// column thresholds and the values they stand for
+    pub show_scale_legend: bool,
+    /// Leave out terminal colors, so values crossing Color-style thresholds are
+    /// shown plainly (for output that isn't going to a terminal)
+    pub no_color: bool,
+    /// Terminator written after each line
+    pub line_ending: LineEnding,
+    /// Leave the terminator off the table's last line, for embedding the table
+    /// in text that supplies its own
+    pub omit_final_line_ending: bool,
+    /// Total width of a rendered line the columns are shrunk to fit, if any
+    pub max_width: Option<usize>,
+    /// Lines drawn around and between the columns
+    pub border: BorderStyle,
+    /// Which lines of the border are drawn
+    pub border_lines: BorderLines,
+    /// Draw a rule between rows after every this many rows (0 for none)
+    pub row_separators: usize,
+    /// Spaces on either side of each cell's text, if not the border style's own
+    pub padding: Option<Padding>,
+    /// Text joining the columns, if not the border style's own line
+    pub column_separator: Option<String>,
+    /// Character of the rule under the header, if not the border style's own
+    pub header_rule_char: Option<char>,
+    /// Put an empty line between rows (where no rule is drawn)
+    pub blank_line_between_rows: bool,
+}
+
+/// Closure producing the rendered header text from a column name
+#[derive(Clone)]
+struct HeaderFormatter(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl std::fmt::Debug for HeaderFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("HeaderFormatter(..)")
+    }
+}
+
+/// Function shortening a line to a width
+type TruncateFn = dyn Fn(&str, usize) -> String + Send + Sync;
+
+/// Closure shortening a value to a column width, set with Column::set_truncator
+#[derive(Clone)]
+struct Truncator(Arc<TruncateFn>);
+
+impl std::fmt::Debug for Truncator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Truncator(..)")
+    }
+}
+
+/// Width decisions computed once per render and shared by every line
+struct Layout {
+    headers: Vec<String>,
+    widths: Vec<usize>,
+    aligns: Vec<CellAlign>,
+    /// Rule under the header, if drawn
+    separator: Option<String>,
+    frame: Frame,
+    /// Rules above the header and beneath the last row, when bordered
+    top: Option<String>,
+    bottom: Option<String>,
+    /// Rule between rows, when row separators are on
+    row_rule: Option<String>,
+    /// Empty line between rows, when on
+    blank_line: Option<String>,
+    /// Whether threshold colors are drawn: off with no_color or for a plain render
+    color: bool,
+}
+
+/// How a column's values are aligned in a render
+#[derive(Debug, Clone, Copy)]
+struct CellAlign {
+    /// The column justification, or the one AlignMode::Auto chose
+    justification: Justification,
+    /// Width of the widest decimal part in Decimal columns, 0 in others
+    fraction: usize,
+}
+
+impl Layout {
+    /// Returns the length of a rendered line without its line terminator
+    fn line_len(&self) -> usize {
+        self.widths.iter().sum::<usize>() + self.frame.overhead(self.widths.len())
+    }
+
+    /// Returns the length of a line of the columns joined by single spaces, as
+    /// the image renderers lay them out
+    #[cfg(any(feature = "png", feature = "svg"))]
+    fn content_len(&self) -> usize {
+        self.widths.iter().sum::<usize>() + self.widths.len().saturating_sub(1)
+    }
+
+    /// Returns the width inside the left and right edges of the frame
+    fn inner_len(&self) -> usize {
+        self.line_len() - text_width(&self.frame.left) - text_width(&self.frame.right)
+    }
+}
+
+/// A single data row along with any metadata attached to it or its cells.
+#[derive(Debug, Clone)]
+struct Row {
+    cells: Vec<String>,
+    metadata: Metadata,
+    cell_metadata: BTreeMap<usize, Metadata>,
+    // Table generation of the row's last change
+    changed: u64,
+}
+
+impl Row {
+    fn new(cells: Vec<String>) -> Self {
+        Row {
+            cells,
+            metadata: Metadata::new(),
+            cell_metadata: BTreeMap::new(),
+            changed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+    options: RenderOptions,
+    header_formatter: Option<HeaderFormatter>,
+    redactions: Vec<Redaction>,
+    sections: Vec<Section>,
+    title: Option<Title>,
+    footer: Option<Vec<String>>,
+    // Counter bumped by every change to the rows, and its value at the last
+    // change that removed or reordered rows
+    generation: u64,
+    reordered: u64,
+}
+
+impl Table {
+    
+    /// Creates a new Table with the specified columns
+    /// 
+    /// # Errors
+    /// - If columns vector is empty
+    pub fn new(columns: Vec<Column>) -> Result<Self, String> {
+        if columns.is_empty() {
+            return Err("Table::new: table must have at least one column".to_string());
+        }
+        
+        Ok(Table::with_columns(columns))
+    }
+
+    /// Creates a new Table with left-justified, non-truncated columns named by headers
+    ///
+    /// # Errors
+    /// - If headers is empty or any header is empty
+    pub fn with_headers<I, S>(headers: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Table::with_headers_justified(headers.into_iter().map(|h| (h, Justification::Left)))
+    }
+
+    /// Creates a new Table with non-truncated columns from (name, justification) pairs
+    ///
+    /// # Errors
+    /// - If headers is empty or any header is empty
+    pub fn with_headers_justified<I, S>(headers: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (S, Justification)>,
+        S: Into<String>,
+    {
+        let columns = headers
+            .into_iter()
+            .map(|(name, j)| Column::new(name, 0, j))
+            .collect::<Result<Vec<_>, _>>()?;
+        Table::new(columns)
+    }
+
+    /// Creates a table from columns already known to be non-empty
+    fn with_columns(columns: Vec<Column>) -> Self {
+        Table {
+            columns,
+            rows: Vec::new(),
+            options: RenderOptions::default(),
+            header_formatter: None,
+            redactions: Vec::new(),
+            sections: Vec::new(),
+            title: None,
+            footer: None,
+            generation: 0,
+            reordered: 0,
+        }
+    }
+
+    /// Adds a row to the table.
+    /// 
+    /// # Errors
+    /// - If number of values doesn't match number of columns
+    /// - If table would exceed MAX_TABLE_ROWS (5,000,000)
+    /// - If any cell contains more than MAX_CELL_LINES (5000) lines
+    pub fn add_row(&mut self, row: Vec<String>) -> Result<(), String> {
+        if row.len() != self.columns.len() {
+            return Err(format!(
+                "Table::add_row: row has {} columns, expected {}",
+                row.len(),
+                self.columns.len()
+            ));
+        }
+
+        if self.rows.len() >= MAX_TABLE_ROWS {
+            return Err(format!(
+                "Table::add_row: cannot add more rows, maximum ({}) reached",
+                MAX_TABLE_ROWS
+            ));
+        }
+
+        // Validate multiline limits before updating anything
+        for value in &row {
+            let line_count = value.split('\n').count();
+            if line_count > MAX_CELL_LINES {
+                return Err(format!(
                     "Table::add_row: cell contains {} lines, exceeding maximum allowed ({})",
                     line_count, MAX_CELL_LINES
                 ));
             }
         }
 
-        // Update max lengths for each column
-        for (col, value) in self.columns.iter_mut().zip(row.iter()) {
-            col.update_max_length(value);
-        }
+        // Update max lengths for each column
+        for (col, value) in self.columns.iter_mut().zip(row.iter()) {
+            col.update_max_length(value);
+        }
+
+        let mut row = Row::new(row);
+        row.changed = self.next_generation();
+        self.rows.push(row);
+        Ok(())
+    }
+
+    /// Returns the number of rows in the table
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Removes the row at the given index and returns its cell values.
+    /// Column widths are not rescanned here; if the row held a column's longest
+    /// value the width is recomputed once at the next render.
+    ///
+    /// # Errors
+    /// - If row is out of range
+    pub fn remove_row(&mut self, row: usize) -> Result<Vec<String>, String> {
+        if row >= self.rows.len() {
+            return Err(format!(
+                "Table::remove_row: row index {} out of range ({} rows)",
+                row, self.rows.len()
+            ));
+        }
+        let removed = self.rows.remove(row);
+        self.mark_widths_stale(&removed.cells);
+        self.remap_sections(|pos| if pos > row { pos - 1 } else { pos });
+        self.reordered = self.next_generation();
+        Ok(removed.cells)
+    }
+
+    /// Keeps only the rows for which the predicate returns true, in a single pass.
+    /// As with remove_row, affected column widths are recomputed at the next render.
+    pub fn retain_rows(&mut self, mut keep: impl FnMut(&[String]) -> bool) {
+        let mut removed = Vec::new();
+        let mut removed_at = Vec::new();
+        let mut idx = 0;
+        let columns = &self.columns;
+        self.rows.retain(|r| {
+            let kept = keep(&r.cells);
+            if !kept {
+                removed.push(columns.iter().zip(&r.cells).map(|(col, v)| col.value_width(v)).collect::<Vec<_>>());
+                removed_at.push(idx);
+            }
+            idx += 1;
+            kept
+        });
+        if !removed.is_empty() {
+            self.reordered = self.next_generation();
+            self.remap_sections(|pos| pos - removed_at.partition_point(|&at| at < pos));
+        }
+        for widths in removed {
+            for (col, width) in self.columns.iter_mut().zip(widths) {
+                if width >= col.max_length {
+                    col.width_stale = true;
+                }
+            }
+        }
+    }
+
+    /// Removes all rows and sections, resetting column widths to their headers
+    pub fn clear_rows(&mut self) {
+        self.rows.clear();
+        self.sections.clear();
+        self.reordered = self.next_generation();
+        for col in &mut self.columns {
+            col.max_length = 0;
+            col.width_stale = false;
+            col.multiline = false;
+        }
+    }
+
+    /// Keeps only the first len rows, dropping the rest
+    pub fn truncate_rows(&mut self, len: usize) {
+        if len >= self.rows.len() {
+            return;
+        }
+        let removed = self.rows.split_off(len);
+        for row in &removed {
+            self.mark_widths_stale(&row.cells);
+        }
+        self.remap_sections(|pos| pos.min(len));
+        self.reordered = self.next_generation();
+    }
+
+    /// Sorts the rows by the values of a column, keeping the original order of equal
+    /// values. Values that both parse as numbers compare numerically and sort before
+    /// text; other values compare as text. Row and cell metadata move with their rows.
+    ///
+    /// # Errors
+    /// - If column is out of range
+    pub fn sort_by_column(&mut self, column: usize, descending: bool) -> Result<(), String> {
+        if column >= self.columns.len() {
+            return Err(format!(
+                "Table::sort_by_column: column index {} out of range ({} columns)",
+                column, self.columns.len()
+            ));
+        }
+        self.rows.sort_by(|a, b| {
+            let (a, b) = (&a.cells[column], &b.cells[column]);
+            let ordering = match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                (Ok(x), Ok(y)) => x.total_cmp(&y),
+                (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+                (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+        self.reordered = self.next_generation();
+        Ok(())
+    }
+
+    /// Returns a new table with only the named columns, in the order given. Column
+    /// settings, render options, and row and cell metadata are carried over.
+    ///
+    /// # Errors
+    /// - If no names are given or a name does not match a column
+    pub fn select_columns(&self, names: &[&str]) -> Result<Table, String> {
+        let indices = names
+            .iter()
+            .map(|name| {
+                self.column_index(name)
+                    .ok_or_else(|| format!("Table::select_columns: no column named '{}'", name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if indices.is_empty() {
+            return Err("Table::select_columns: no columns selected".to_string());
+        }
+
+        let rows = self.rows
+            .iter()
+            .map(|row| Row {
+                cells: indices.iter().map(|&i| row.cells[i].clone()).collect(),
+                metadata: row.metadata.clone(),
+                cell_metadata: indices
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(new, old)| row.cell_metadata.get(old).map(|m| (new, m.clone())))
+                    .collect(),
+                changed: row.changed,
+            })
+            .collect();
+        Ok(Table {
+            columns: indices.iter().map(|&i| self.columns[i].clone()).collect(),
+            rows,
+            options: self.options.clone(),
+            header_formatter: self.header_formatter.clone(),
+            redactions: self.redactions.clone(),
+            sections: self.sections.clone(),
+            title: self.title.clone(),
+            footer: self.footer.as_ref().map(|footer| indices.iter().map(|&i| footer[i].clone()).collect()),
+            generation: self.generation,
+            reordered: self.reordered,
+        })
+    }
+
+    /// Renders only the given rows, without the header, using the same column
+    /// widths as a full render of the table. Useful for printing rows as they
+    /// arrive beneath a header that was already printed. Sections placed before
+    /// rows in the range are included.
+    ///
+    /// # Errors
+    /// - If the range is out of bounds
+    pub fn render_rows(&self, rows: std::ops::Range<usize>) -> Result<String, String> {
+        if rows.start > rows.end || rows.end > self.rows.len() {
+            return Err(format!(
+                "Table::render_rows: range {}..{} out of bounds ({} rows)",
+                rows.start, rows.end, self.rows.len()
+            ));
+        }
+        Ok(RowsView { table: self, rows }.to_string())
+    }
+
+    /// Renders the table as text with no ANSI escape sequences, for log files and
+    /// copy-paste: color is off (as with set_no_color), and color codes and
+    /// hyperlinks in cells and headers are removed, keeping the link text. Column
+    /// widths never count escape sequences, so the columns line up as in a normal
+    /// render.
+    pub fn render_plain(&self) -> String {
+        strip_escapes(&TextView { table: self, color: false }.to_string()).into_owned()
+    }
+
+    /// Returns the index of the column with the given name
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    /// Returns the table's generation: a counter that increases with every change
+    /// to its rows. Pass it to changed_rows_since later to find what changed.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the indices of rows added or changed (cells or metadata) after the
+    /// given generation, so a live display can redraw only those rows. If rows were
+    /// removed or reordered since then, positions have shifted and every row is
+    /// returned; compare row_count to find rows gone from the end.
+    pub fn changed_rows_since(&self, generation: u64) -> Vec<usize> {
+        if self.reordered > generation {
+            return (0..self.rows.len()).collect();
+        }
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.changed > generation)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    fn next_generation(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+
+    /// Marks columns whose longest value may have been among the removed cells
+    fn mark_widths_stale(&mut self, cells: &[String]) {
+        for (col, value) in self.columns.iter_mut().zip(cells) {
+            if col.value_width(value) >= col.max_length {
+                col.width_stale = true;
+            }
+        }
+    }
+
+    /// Returns the columns of the table in display order
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the cell values of the row at the given index, if it exists
+    pub fn row(&self, row: usize) -> Option<&[String]> {
+        self.rows.get(row).map(|r| r.cells.as_slice())
+    }
+
+    /// Attaches a metadata key/value pair to a row, replacing any previous value for the key.
+    /// Metadata is not rendered by Display but is available to structured renderers.
+    ///
+    /// # Errors
+    /// - If row is out of range
+    pub fn set_row_metadata(&mut self, row: usize, key: impl Into<String>, value: impl Into<String>) -> Result<(), String> {
+        let row_count = self.rows.len();
+        let generation = self.generation + 1;
+        let r = self.rows.get_mut(row).ok_or_else(|| format!(
+            "Table::set_row_metadata: row index {} out of range ({} rows)", row, row_count
+        ))?;
+        r.metadata.insert(key.into(), value.into());
+        r.changed = generation;
+        self.generation = generation;
+        Ok(())
+    }
+
+    /// Attaches a metadata key/value pair to a single cell, replacing any previous value for the key.
+    ///
+    /// # Errors
+    /// - If row or column is out of range
+    pub fn set_cell_metadata(&mut self, row: usize, column: usize, key: impl Into<String>, value: impl Into<String>) -> Result<(), String> {
+        if column >= self.columns.len() {
+            return Err(format!(
+                "Table::set_cell_metadata: column index {} out of range ({} columns)",
+                column, self.columns.len()
+            ));
+        }
+        let row_count = self.rows.len();
+        let generation = self.generation + 1;
+        let r = self.rows.get_mut(row).ok_or_else(|| format!(
+            "Table::set_cell_metadata: row index {} out of range ({} rows)", row, row_count
+        ))?;
+        r.cell_metadata.entry(column).or_default().insert(key.into(), value.into());
+        r.changed = generation;
+        self.generation = generation;
+        Ok(())
+    }
+
+    /// Returns the metadata attached to a row, or None if the row does not exist
+    pub fn row_metadata(&self, row: usize) -> Option<&Metadata> {
+        self.rows.get(row).map(|r| &r.metadata)
+    }
+
+    /// Returns the metadata attached to a cell, or None if the cell has none
+    pub fn cell_metadata(&self, row: usize, column: usize) -> Option<&Metadata> {
+        self.rows.get(row).and_then(|r| r.cell_metadata.get(&column))
+    }
+
+    /// Returns the table-wide render options
+    pub fn render_options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Replaces the table-wide render options
+    pub fn set_render_options(&mut self, options: RenderOptions) {
+        self.options = options;
+    }
+
+    /// Sets the header transform used for columns that don't set their own
+    pub fn set_header_transform(&mut self, t: HeaderTransform) {
+        self.options.header_transform = t;
+    }
+
+    /// Sets whether padding after the last column is skipped, avoiding trailing
+    /// spaces on every line (useful for diffs and copy/paste)
+    pub fn set_trim_trailing_padding(&mut self, trim: bool) {
+        self.options.trim_trailing_padding = trim;
+    }
+
+    /// Sets whether a legend of column descriptions ("Lat: request latency in ms")
+    /// is rendered beneath the table. Columns without a description are omitted.
+    pub fn set_show_legend(&mut self, show: bool) {
+        self.options.show_legend = show;
+    }
+
+    /// Sets whether a legend of threshold colors and markers ("Util: ■ above 80%")
+    /// is rendered beneath the table. Columns without thresholds are omitted.
+    pub fn set_show_scale_legend(&mut self, show: bool) {
+        self.options.show_scale_legend = show;
+    }
+
+    /// Sets whether terminal colors are left out of the rendered table
+    pub fn set_no_color(&mut self, no_color: bool) {
+        self.options.no_color = no_color;
+    }
+
+    /// Sets the terminator written after each line (default LineEnding::Lf)
+    pub fn set_line_ending(&mut self, ending: LineEnding) {
+        self.options.line_ending = ending;
+    }
+
+    /// Sets whether the table's last line is left without a terminator.
+    /// render_rows still ends every line, so its output can be appended to.
+    pub fn set_omit_final_line_ending(&mut self, omit: bool) {
+        self.options.omit_final_line_ending = omit;
+    }
+
+    /// Sets a total width for rendered lines. Wider tables are shrunk to fit by
+    /// narrowing each column in proportion to its width above 3 characters (or
+    /// its minimum width), with
+    /// values truncated or wrapped as the column's overflow mode says. Columns
+    /// with a fixed width keep it, so a table may still be wider than max_width.
+    /// It is also the total that column width percentages are taken of.
+    pub fn set_max_width(&mut self, width: usize) {
+        self.options.max_width = Some(width);
+    }
+
+    /// Lets the table be as wide as its columns again after set_max_width
+    pub fn clear_max_width(&mut self) {
+        self.options.max_width = None;
+    }
+
+    /// Returns the rendered width of each column as the table would be drawn now
+    pub fn widths(&self) -> Vec<usize> {
+        self.layout().widths
+    }
+
+    /// Fixes every column at its current rendered width, so later renders keep
+    /// the same layout as rows are added or changed (values that no longer fit are
+    /// truncated). Pass widths() to set_fixed_widths to line up sibling tables.
+    pub fn lock_widths(&mut self) {
+        let widths = self.widths();
+        for (col, width) in self.columns.iter_mut().zip(widths) {
+            col.fixed_width = Some(width.max(1));
+        }
+    }
+
+    /// Fixes the column widths to the given values, one per column
+    ///
+    /// # Errors
+    /// - If the number of widths doesn't match the number of columns
+    /// - If a width is 0 or exceeds MAX_TRUNCATE_WIDTH (5000); no width is changed
+    pub fn set_fixed_widths(&mut self, widths: &[usize]) -> Result<(), String> {
+        if widths.len() != self.columns.len() {
+            return Err(format!(
+                "Table::set_fixed_widths: got {} widths, expected {}",
+                widths.len(),
+                self.columns.len()
+            ));
+        }
+        if let Some(&width) = widths.iter().find(|&&w| w == 0 || w > MAX_TRUNCATE_WIDTH) {
+            return Err(format!("Table::set_fixed_widths: width {} is not in 1..={}", width, MAX_TRUNCATE_WIDTH));
+        }
+        for (col, &width) in self.columns.iter_mut().zip(widths) {
+            col.fixed_width = Some(width);
+        }
+        Ok(())
+    }
+
+    /// Lets every column width follow its values again
+    pub fn unlock_widths(&mut self) {
+        for col in &mut self.columns {
+            col.fixed_width = None;
+        }
+    }
+
+    /// Sets a closure that produces the rendered header text from each column name.
+    /// When set, the formatter takes precedence over any header transforms.
+    pub fn set_header_formatter(&mut self, f: impl Fn(&str) -> String + Send + Sync + 'static) {
+        self.header_formatter = Some(HeaderFormatter(Arc::new(f)));
+    }
+
+    /// Removes a header formatter set with set_header_formatter
+    pub fn clear_header_formatter(&mut self) {
+        self.header_formatter = None;
+    }
+
+    /// Returns the rendered header text for a column, applying the header
+    /// formatter or the column/table header transform to its text alias or name
+    fn header_text(&self, col: &Column) -> String {
+        let name = col.name_for(OutputFormat::Text);
+        if let Some(formatter) = &self.header_formatter {
+            return (formatter.0)(name);
+        }
+        col.header_transform
+            .unwrap_or(self.options.header_transform)
+            .apply(name)
+    }
+
+    /// Writes a single rendered line and its terminator, dropping trailing padding
+    /// if configured
+    fn write_line(&self, f: &mut std::fmt::Formatter, line: &str) -> std::fmt::Result {
+        if self.options.trim_trailing_padding {
+            f.write_str(line.trim_end_matches(' '))?;
+        } else {
+            f.write_str(line)?;
+        }
+        f.write_str(self.options.line_ending.as_str())
+    }
+
+    /// Computes the per-render layout: header text, column widths, and separator line
+    fn layout(&self) -> Layout {
+        let headers: Vec<String> = self.columns.iter().map(|col| self.header_text(col)).collect();
+        let frame = self.frame();
+        let mut widths = self.column_widths(&headers);
+        if let Some(max_width) = self.options.max_width {
+            self.shrink_widths(&mut widths, max_width.saturating_sub(frame.overhead(self.columns.len())));
+        }
+        let aligns = (0..self.columns.len()).map(|idx| self.cell_align(idx)).collect();
+        let separator = frame.header_rule(&widths);
+        let top = frame.top_rule(&widths);
+        let bottom = frame.bottom_rule(&widths);
+        let row_rule = (self.options.row_separators > 0).then(|| frame.row_rule(&widths));
+        let blank_line = self.options.blank_line_between_rows.then(|| frame.blank_line(&widths));
+        let color = !self.options.no_color;
+        Layout { headers, widths, aligns, separator, frame, top, bottom, row_rule, blank_line, color }
+    }
+
+    fn cell_align(&self, idx: usize) -> CellAlign {
+        let col = &self.columns[idx];
+        let numeric = || {
+            let mut filled = self.rows.iter().map(|row| row.cells[idx].trim()).filter(|v| !v.is_empty()).peekable();
+            filled.peek().is_some() && filled.all(is_number)
+        };
+        match col.justification {
+            Justification::Decimal => CellAlign { justification: Justification::Decimal, fraction: self.decimal_widths(idx).1 },
+            _ if col.align_mode == AlignMode::Auto && numeric() => CellAlign { justification: Justification::Right, fraction: 0 },
+            justification => CellAlign { justification, fraction: 0 },
+        }
+    }
+
+    /// Returns the enabled legend lines: "Header: description" for columns with
+    /// descriptions, then "Header: <mark> below <limit>, ..." for columns with
+    /// thresholds (the lines of a multiline header are joined by spaces)
+    fn legend_lines(&self, layout: &Layout) -> Vec<String> {
+        let headers: Vec<String> = layout.headers.iter().map(|header| header.replace('\n', " ")).collect();
+        let mut lines = Vec::new();
+        if self.options.show_legend {
+            lines.extend(self.columns
+                .iter()
+                .zip(&headers)
+                .filter_map(|(col, header)| col.description.as_ref().map(|d| format!("{}: {}", header, d))));
+        }
+        if self.options.show_scale_legend {
+            lines.extend(self.columns
+                .iter()
+                .zip(&headers)
+                .filter_map(|(col, header)| col.scale_legend(layout.color).map(|scale| format!("{}: {}", header, scale))));
+        }
+        lines
+    }
+
+    /// Writes the lines of the given rows using the layout, reusing one line
+    /// buffer for the whole render. When no column holds a multiline value, rows
+    /// are written straight into the buffer without splitting cells into lines.
+    fn write_rows(&self, f: &mut std::fmt::Formatter, layout: &Layout, rows: std::ops::Range<usize>) -> std::fmt::Result {
+        let mut line = String::with_capacity(layout.line_len());
+        let single_line = self.columns.iter().all(|col| !col.multiline);
+        let mut displayed = Vec::with_capacity(self.columns.len());
+        for (idx, row) in rows.clone().zip(&self.rows[rows]) {
+            if let Some(rule) = layout.row_rule.as_ref().filter(|_| self.rule_before(idx)) {
+                self.write_line(f, rule)?;
+            } else if let Some(blank) = layout.blank_line.as_ref().filter(|_| idx > 0) {
+                self.write_line(f, blank)?;
+            }
+            if single_line {
+                displayed.clear();
+                displayed.extend(self.columns.iter().zip(&row.cells).map(|(col, value)| col.display_value(value, &self.redactions)));
+                // A redaction mask or threshold flag can still add a line break,
+                // and wrapping columns break long values
+                let breaks = self.columns
+                    .iter()
+                    .zip(&displayed)
+                    .zip(&layout.widths)
+                    .any(|((col, value), &width)| value.contains('\n') || col.wraps(value, width));
+                if !breaks {
+                    self.write_single_line_row(f, layout, row, &displayed, &mut line)?;
+                    continue;
+                }
+            }
+
+            // Convert each cell into a vector of formatted lines
+            let formatted_cells: Vec<Vec<String>> = self.columns
+                .iter()
+                .zip(&row.cells)
+                .enumerate()
+                .map(|(idx, (col, value))| {
+                    let lines = self.cell_lines(layout, idx, value);
+                    match col.threshold_color(value).filter(|_| layout.color) {
+                        Some(color) => lines.iter().map(|line| paint_content(line, color)).collect(),
+                        None => lines,
+                    }
+                })
+                .collect();
+            // Above creates a vec of vecs of strings, where each inner vec is a vec of strings representing the lines of a cell
+            // It looks like this: [[line1, line2, line3], [line1, line2], [line1, line2, line3, line4]]
+            // Non multiline cells are represented as a vec of one element
+
+            // Find the maximum number of lines in any cell of this row
+            let max_lines = formatted_cells
+                .iter()
+                .map(|cell| cell.len())
+                .max()
+                .unwrap_or(1);
+
+            // Blank lines above each cell, from its vertical alignment
+            let offsets: Vec<usize> = self.columns
+                .iter()
+                .zip(&formatted_cells)
+                .map(|(col, cell)| {
+                    let spare = max_lines - cell.len();
+                    match col.vertical_alignment {
+                        VerticalAlignment::Top => 0,
+                        VerticalAlignment::Middle => spare / 2,
+                        VerticalAlignment::Bottom => spare,
+                    }
+                })
+                .collect();
+
+            // Print each line of the row
+            // For each line of the row, we need to print the corresponding line from each cell, or blank padding if the cell has fewer lines than the max
+            for line_idx in 0..max_lines {
+                line.clear();
+                for (idx, ((cell, &width), &offset)) in formatted_cells.iter().zip(&layout.widths).zip(&offsets).enumerate() {
+                    line.push_str(if idx > 0 { &layout.frame.separator } else { &layout.frame.left });
+                    match line_idx.checked_sub(offset).and_then(|pos| cell.get(pos)) {
+                        Some(text) => line.push_str(text),
+                        None => line.extend(std::iter::repeat_n(' ', width)),
+                    }
+                }
+                line.push_str(&layout.frame.right);
+                
+                // Write the line to the formatter
+                self.write_line(f, &line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a row whose displayed values are all single lines
+    fn write_single_line_row(
+        &self,
+        f: &mut std::fmt::Formatter,
+        layout: &Layout,
+        row: &Row,
+        displayed: &[Cow<str>],
+        line: &mut String,
+    ) -> std::fmt::Result {
+        line.clear();
+        let cells = self.columns.iter().zip(&row.cells).zip(displayed).zip(&layout.widths).zip(&layout.aligns);
+        for (idx, ((((col, value), text), &width), &align)) in cells.enumerate() {
+            line.push_str(if idx > 0 { &layout.frame.separator } else { &layout.frame.left });
+            match col.threshold_color(value).filter(|_| layout.color) {
+                Some(color) => {
+                    let mut cell = String::with_capacity(width);
+                    col.push_padded(&mut cell, text, width, align);
+                    line.push_str(&paint_content(&cell, color));
+                }
+                None => col.push_padded(line, text, width, align),
+            }
+        }
+        line.push_str(&layout.frame.right);
+        self.write_line(f, line)
+    }
+
+    /// Formats the value of a cell in the column at idx as padded lines, or a line
+    /// with the error if the value can't be formatted
+    fn cell_lines(&self, layout: &Layout, idx: usize, value: &str) -> Vec<String> {
+        self.columns[idx]
+            .format_cell(value, layout.widths[idx], layout.aligns[idx], &self.redactions)
+            .unwrap_or_else(|e| vec![e])
+    }
+
+    /// Returns the number of lines a row takes in the text render
+    fn row_height(&self, layout: &Layout, row: &Row) -> usize {
+        row.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| match self.columns[idx].overflow {
+                Overflow::Truncate => value.bytes().filter(|&b| b == b'\n').count() + 1,
+                _ => self.cell_lines(layout, idx, value).len(),
+            })
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the formatted header cells, a line of them for each line of the
+    /// tallest header (shorter headers are filled out with blank lines beneath)
+    fn header_rows(&self, layout: &Layout) -> Vec<Vec<String>> {
+        let lines: Vec<Vec<&str>> = layout.headers.iter().map(|header| header.split('\n').collect()).collect();
+        let height = lines.iter().map(Vec::len).max().unwrap_or(1);
+        (0..height)
+            .map(|line_idx| {
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, header)| {
+                        let text = header.get(line_idx).copied().unwrap_or("").to_string();
+                        self.columns[idx].format_header(text, layout.widths[idx], layout.aligns[idx].justification)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the number of lines the header takes: one per line of the
+    /// tallest column name
+    fn header_height(&self) -> usize {
+        self.columns.iter().map(|col| self.header_text(col).split('\n').count()).max().unwrap_or(1)
+    }
+
+    /// Returns true if the units row is rendered beneath the header
+    fn has_units(&self) -> bool {
+        self.columns.iter().any(|c| c.unit.is_some())
+    }
+
+    /// Estimates the length in bytes of the rendered table so callers can pre-allocate:
+    /// `String::with_capacity(table.estimated_render_len())` before writing the table.
+    /// Exact for ASCII content; multi-byte characters make it an underestimate and
+    /// trimmed trailing padding an overestimate.
+    pub fn estimated_render_len(&self) -> usize {
+        let layout = self.layout();
+        let ending_len = self.options.line_ending.as_str().len();
+        let line_len = layout.line_len() + ending_len;
+        let row_lines: usize = self.rows.iter().map(|row| self.row_height(&layout, row)).sum::<usize>() + self.between_row_lines();
+        let legend = self.legend_lines(&layout);
+        let legend_len = if legend.is_empty() {
+            0
+        } else {
+            ending_len + legend.iter().map(|l| l.len() + ending_len).sum::<usize>()
+        };
+        let body_lines = row_lines + self.section_lines() + self.footer_lines();
+        (self.header_line_count() + body_lines + usize::from(layout.bottom.is_some())) * line_len + legend_len
+    }
+
+    /// Lowers truncation widths, widest column first, until the rendered table is at
+    /// most max_width characters wide or every column is down to 3 characters (or
+    /// its minimum width).
+    /// Values too wide for a narrowed column are truncated or wrapped as its
+    /// overflow mode says, and its header is truncated. Columns with a fixed or
+    /// percentage width are left as they are.
+    pub fn fit_to_width(&mut self, max_width: usize) {
+        let mut previous = usize::MAX;
+        loop {
+            let layout = self.layout();
+            let (widths, total) = (&layout.widths, layout.line_len());
+            // Stop once narrowing no longer shrinks the line, e.g. when a width
+            // comes from something other than the values being truncated
+            if total <= max_width || total >= previous {
+                return;
+            }
+            previous = total;
+            let Some((idx, &widest)) = widths
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|&(i, &w)| self.declared_width(&self.columns[i]).is_none() && w > self.columns[i].narrowest())
+                .max_by_key(|&(_, w)| *w)
+            else {
+                return;
+            };
+            let second = widths.iter().enumerate().filter(|&(i, _)| i != idx).map(|(_, &w)| w).max().unwrap_or(0);
+            let mut target = widest.saturating_sub(total - max_width).max(second).max(self.columns[idx].narrowest());
+            if target == widest {
+                target -= 1;
+            }
+            let col = &mut self.columns[idx];
+            col.truncate_at = target;
+            col.truncate_header = true;
+        }
+    }
+
+    /// Narrows columns in proportion to their width above their narrowest until
+    /// they add up to at most max_width. Columns with a fixed or percentage width
+    /// are left as they are.
+    fn shrink_widths(&self, widths: &mut [usize], max_width: usize) {
+        let total = widths.iter().sum::<usize>();
+        let Some(excess) = total.checked_sub(max_width).filter(|&excess| excess > 0) else {
+            return;
+        };
+        let spare: Vec<usize> = widths
+            .iter()
+            .zip(&self.columns)
+            .map(|(&width, col)| match self.declared_width(col) {
+                Some(_) => 0,
+                None => width.saturating_sub(col.narrowest()),
+            })
+            .collect();
+        let total_spare: usize = spare.iter().sum();
+        if total_spare <= excess {
+            for (width, &spare) in widths.iter_mut().zip(&spare) {
+                *width -= spare;
+            }
+            return;
+        }
+        let mut cuts: Vec<usize> = spare.iter().map(|&spare| excess * spare / total_spare).collect();
+        // Rounding leaves a few characters over; take them from the widest columns
+        let mut left = excess - cuts.iter().sum::<usize>();
+        let mut order: Vec<usize> = (0..widths.len()).collect();
+        order.sort_by_key(|&idx| std::cmp::Reverse(spare[idx] - cuts[idx]));
+        for idx in order {
+            if left == 0 {
+                break;
+            }
+            if spare[idx] > cuts[idx] {
+                cuts[idx] += 1;
+                left -= 1;
+            }
+        }
+        for (width, cut) in widths.iter_mut().zip(cuts) {
+            *width -= cut;
+        }
+    }
+
+    /// Returns the nearest-rank percentile of a column's displayed value lengths
+    fn percentile_width(&self, idx: usize, percentile: f64) -> usize {
+        let col = &self.columns[idx];
+        let mut lengths: Vec<usize> = self.rows.iter().map(|r| cell_width(&col.display_value(&r.cells[idx], &self.redactions))).collect();
+        if lengths.is_empty() {
+            return 0;
+        }
+        let rank = (percentile / 100.0 * lengths.len() as f64).ceil() as usize;
+        let rank = rank.clamp(1, lengths.len());
+        *lengths.select_nth_unstable(rank - 1).1
+    }
+
+    /// Returns the widest integer and decimal parts (see fraction_width) of the
+    /// displayed lines of the column at idx
+    fn decimal_widths(&self, idx: usize) -> (usize, usize) {
+        let col = &self.columns[idx];
+        let mut widths = (0, 0);
+        for row in &self.rows {
+            for line in col.display_value(&row.cells[idx], &self.redactions).split('\n') {
+                let fraction = fraction_width(line);
+                widths.0 = widths.0.max(text_width(line).saturating_sub(fraction));
+                widths.1 = widths.1.max(fraction);
+            }
+        }
+        widths
+    }
+
+    /// Returns the width a column was given regardless of its contents: its fixed
+    /// width, or its percentage of the table's maximum width
+    fn declared_width(&self, col: &Column) -> Option<usize> {
+        col.fixed_width.or_else(|| {
+            let available = self.options.max_width?.saturating_sub(self.frame().overhead(self.columns.len()));
+            let width = (available as f64 * col.width_percent? / 100.0).floor() as usize;
+            Some(width.max(1))
+        })
+    }
 
-        self.rows.push(row);
-        Ok(())
+    /// Computes the rendered width of each column from its data and header.
+    /// Unless header truncation is enabled, a column is always at least as wide as its header.
+    /// Columns with a fixed or percentage width are that wide whatever their
+    /// contents, and Decimal columns fit their widest integer and decimal parts.
+    /// Columns with a number format, thresholds, or redactions are measured from their
+    /// displayed values.
+    fn column_widths(&self, headers: &[String]) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .zip(headers)
+            .map(|((idx, col), header)| {
+                if let Some(width) = self.declared_width(col) {
+                    return width;
+                }
+                let displayed = col.number_format.is_some()
+                    || col.thresholds.is_some()
+                    || col.value_transform != CaseTransform::None
+                    || !col.redactions.is_empty()
+                    || !self.redactions.is_empty();
+                let max_length = if col.justification == Justification::Decimal {
+                    let (integer, fraction) = self.decimal_widths(idx);
+                    integer + fraction
+                } else if displayed {
+                    self.rows.iter().map(|r| cell_width(&col.display_value(&r.cells[idx], &self.redactions))).max().unwrap_or(0)
+                } else if col.width_stale {
+                    self.rows.iter().map(|r| col.value_width(&r.cells[idx])).max().unwrap_or(0)
+                } else {
+                    col.max_length
+                };
+                let mut data_width = col.data_width(max_length);
+                if let Some(percentile) = col.truncate_percentile {
+                    data_width = data_width.min(self.percentile_width(idx, percentile).max(3));
+                }
+                let data_width = data_width.max(col.data_width(col.unit.as_deref().map_or(0, text_width)));
+                let data_width = data_width.max(col.data_width(self.footer.as_ref().map_or(0, |footer| cell_width(&footer[idx]))));
+                let header_len = cell_width(header);
+                let width = if col.truncate_header {
+                    data_width.max(header_len.min(3))
+                } else {
+                    data_width.max(header_len)
+                };
+                width.max(col.min_width)
+            })
+            .collect()
     }
+}
 
-    /// Returns the number of rows in the table
-    pub fn row_count(&self) -> usize {
-        self.rows.len()
+/// A range of rows rendered without the header, for Table::render_rows
+struct RowsView<'a> {
+    table: &'a Table,
+    rows: std::ops::Range<usize>,
+}
+
+impl std::fmt::Display for RowsView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let layout = self.table.layout();
+        self.table.write_body(f, &layout, self.rows.clone(), false)
+    }
+}
+
+/// The whole table with every line terminated, which Table's Display trims when
+/// the final line ending is omitted
+struct FullView<'a> {
+    table: &'a Table,
+    color: bool,
+}
+
+impl std::fmt::Display for FullView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.table.write_table(f, self.color)
+    }
+}
+
+/// The table as Display renders it, with threshold colors drawn or not whatever
+/// its no_color option says
+pub(crate) struct TextView<'a> {
+    pub(crate) table: &'a Table,
+    pub(crate) color: bool,
+}
+
+impl std::fmt::Display for TextView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let TextView { table, color } = *self;
+        if !table.options.omit_final_line_ending {
+            return table.write_table(f, color);
+        }
+        // Which line is last is only known once the table is written
+        let rendered = FullView { table, color }.to_string();
+        f.write_str(rendered.strip_suffix(table.options.line_ending.as_str()).unwrap_or(&rendered))
     }
 }
 
@@ -203,70 +2112,51 @@ impl Table {
 impl std::fmt::Display for Table {
     
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        TextView { table: self, color: !self.options.no_color }.fmt(f)
+    }
+}
+
+impl Table {
+    fn write_table(&self, f: &mut std::fmt::Formatter, color: bool) -> std::fmt::Result {
         if self.columns.is_empty() {
             return Ok(());
         }
 
-        // Format header
-        let header: Vec<String> = self.columns
-            .iter()
-            .map(|col| col.format_cell(&col.name).map_or_else(|e| e, |v| v[0].clone()))
-            .collect();
-        
-        writeln!(f, "{}", header.join(" "))?;
-
-        // Format separator using the same effective width logic as columns
-        let separator: Vec<String> = self.columns
-            .iter()
-            .map(|col| {
-                let effective_width = if col.truncate_at > 0 && col.max_length > col.truncate_at {
-                    col.truncate_at
-                } else {
-                    col.max_length
-                };
-                "=".repeat(effective_width)
-            })
-            .collect();
-        
-        writeln!(f, "{}", separator.join(" "))?;
+        let mut layout = self.layout();
+        layout.color = color;
+        self.write_title(f, &layout)?;
+        if let Some(top) = &layout.top {
+            self.write_line(f, top)?;
+        }
 
-        // Format rows with multiline support
-        for row in &self.rows {
-            
-            // Convert each cell into a vector of formatted lines
-            let formatted_cells: Vec<Vec<String>> = self.columns
+        // Format header
+        for header in self.header_rows(&layout) {
+            self.write_line(f, &layout.frame.join(&header))?;
+        }
+        if self.has_units() {
+            let units: Vec<String> = self.columns
                 .iter()
-                .zip(row)
-                .map(|(col, value)| col.format_cell(value).map_or_else(|e| vec![e], |v| v))
+                .zip(&layout.widths)
+                .map(|(col, &width)| center(take_width(col.unit.as_deref().unwrap_or(""), width), width))
                 .collect();
-            // Above creates a vec of vecs of strings, where each inner vec is a vec of strings representing the lines of a cell
-            // It looks like this: [[line1, line2, line3], [line1, line2], [line1, line2, line3, line4]]
-            // Non multiline cells are represented as a vec of one element
+            self.write_line(f, &layout.frame.join(&units))?;
+        }
+        if let Some(separator) = &layout.separator {
+            self.write_line(f, separator)?;
+        }
 
-            // Find the maximum number of lines in any cell of this row
-            let max_lines = formatted_cells
-                .iter()
-                .map(|cell| cell.len())
-                .max()
-                .unwrap_or(1);
+        self.write_body(f, &layout, 0..self.rows.len(), true)?;
+        self.write_footer(f, &layout)?;
+        if let Some(bottom) = &layout.bottom {
+            self.write_line(f, bottom)?;
+        }
 
-            // Print each line of the row
-            // For each line of the row, we need to print the corresponding line from each cell, or the empty string if the cell has fewer lines than the max
-            for line_idx in 0..max_lines {
-                let line: Vec<String> = formatted_cells
-                    .iter()
-                    .zip(self.columns.iter())
-                    .map(|(cell, col)| {
-                        if line_idx < cell.len() {
-                            cell[line_idx].clone()
-                        } else {
-                            col.format_empty()
-                        }
-                    })
-                    .collect();
-                
-                // Write the line to the formatter
-                writeln!(f, "{}", line.join(" "))?;
+        // Legend of column descriptions and threshold scales beneath the table
+        let legend = self.legend_lines(&layout);
+        if !legend.is_empty() {
+            self.write_line(f, "")?;
+            for line in legend {
+                self.write_line(f, &line)?;
             }
         }
 
@@ -440,4 +2330,630 @@ mod tests {
         ]).unwrap();
         assert_eq!(table.row_count(), 2, "Table should have 2 rows after second addition");
     }
+
+    #[test]
+    fn test_metadata() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Age", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+
+        table.add_row(vec!["John Doe".to_string(), "30".to_string()]).unwrap();
+        let before = table.to_string();
+
+        table.set_row_metadata(0, "id", "user-1").unwrap();
+        table.set_cell_metadata(0, 1, "class", "numeric").unwrap();
+
+        assert_eq!(table.row_metadata(0).unwrap().get("id").map(String::as_str), Some("user-1"));
+        assert_eq!(table.cell_metadata(0, 1).unwrap().get("class").map(String::as_str), Some("numeric"));
+        assert!(table.cell_metadata(0, 0).is_none());
+        assert!(table.set_row_metadata(1, "id", "x").is_err());
+        assert!(table.set_cell_metadata(0, 2, "id", "x").is_err());
+        assert_eq!(table.to_string(), before, "Metadata should not affect text output");
+    }
+
+    #[test]
+    fn test_header_transforms() {
+        let mut table = Table::new(vec![
+            Column::new("request_id", 0, Justification::Left).unwrap(),
+            Column::new("status code", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["abc".to_string(), "200".to_string()]).unwrap();
+
+        table.set_header_transform(HeaderTransform::SnakeToTitle);
+        assert!(table.to_string().starts_with("Request Id Status Code\n"));
+
+        table.columns[1].set_header_transform(HeaderTransform::Uppercase);
+        assert!(table.to_string().starts_with("Request Id STATUS CODE\n"));
+
+        // Formatter takes precedence and column width follows the rendered header
+        table.set_header_formatter(|name| name.chars().take(2).collect());
+        let output = table.to_string();
+        assert!(output.starts_with("re  st \n=== ===\n"), "{}", output);
+
+        table.clear_header_formatter();
+        println!("\n=== Header Transform Test ===\n\n{}\n", table);
+    }
+
+    #[test]
+    fn test_trim_trailing_padding() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Notes", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["John Doe".to_string(), "a\nlonger note".to_string()]).unwrap();
+        table.add_row(vec!["Jane Smith\nJr".to_string(), "b".to_string()]).unwrap();
+
+        assert!(table.to_string().lines().any(|l| l.ends_with(' ')));
+
+        table.set_trim_trailing_padding(true);
+        let output = table.to_string();
+        assert!(output.lines().all(|l| !l.ends_with(' ')), "{}", output);
+        assert!(output.contains("\nJr\n"));
+    }
+
+    #[test]
+    fn test_header_truncation() {
+        let mut table = Table::new(vec![
+            Column::new("Very Long Diagnostic Identifier", 8, Justification::Left).unwrap(),
+            Column::new("Status", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["E1234".to_string(), "ok".to_string()]).unwrap();
+        table.add_row(vec!["E123456789".to_string(), "ok".to_string()]).unwrap();
+
+        // By default the header controls the width
+        assert!(table.to_string().starts_with("Very Long Diagnostic Identifier Status\n"));
+
+        table.columns[0].set_truncate_header(true);
+        table.columns[1].set_truncate_header(true);
+        let output = table.to_string();
+        assert_eq!(output, "Very ... Sta\n======== ===\nE1234    ok \nE1234... ok \n");
+    }
+
+    #[test]
+    fn test_column_aliases() {
+        let mut col = Column::new("Quantity ordered", 0, Justification::Right).unwrap();
+        col.set_alias(OutputFormat::Text, "Qty");
+        col.set_alias(OutputFormat::Json, "quantity");
+
+        assert_eq!(col.name_for(OutputFormat::Text), "Qty");
+        assert_eq!(col.name_for(OutputFormat::Json), "quantity");
+        assert_eq!(col.name_for(OutputFormat::Html), "Quantity ordered");
+
+        let mut table = Table::new(vec![col]).unwrap();
+        table.add_row(vec!["12".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Qty\n===\n 12\n");
+    }
+
+    #[test]
+    fn test_legend() {
+        let mut lat = Column::new("Lat", 0, Justification::Right).unwrap();
+        lat.set_description("request latency in ms, p99");
+        let mut table = Table::new(vec![
+            Column::new("Endpoint", 0, Justification::Left).unwrap(),
+            lat,
+        ]).unwrap();
+        table.add_row(vec!["/api".to_string(), "12".to_string()]).unwrap();
+
+        assert!(!table.to_string().contains("request latency"));
+
+        table.set_show_legend(true);
+        assert!(table.to_string().ends_with("\n\nLat: request latency in ms, p99\n"));
+        println!("\n=== Legend Test ===\n\n{}\n", table);
+    }
+
+    #[test]
+    fn test_with_headers() {
+        let mut table = Table::with_headers(["Name", "Age", "City"]).unwrap();
+        table.add_row(vec!["John Doe".to_string(), "30".to_string(), "New York".to_string()]).unwrap();
+        assert_eq!(table.columns().len(), 3);
+        assert!(table.columns().iter().all(|c| c.justification() == Justification::Left));
+
+        let table = Table::with_headers_justified([("Item", Justification::Left), ("Qty", Justification::Right)]).unwrap();
+        assert_eq!(table.columns()[1].justification(), Justification::Right);
+
+        assert!(Table::with_headers(Vec::<String>::new()).is_err());
+        assert!(Table::with_headers(["Name", ""]).is_err());
+    }
+
+    #[test]
+    fn test_width_helpers() {
+        assert_eq!(text_width("hello"), 5);
+        assert_eq!(text_width("héllo"), 5);
+        assert_eq!(take_width("hello", 3), "hel");
+        assert_eq!(take_width("héllo", 3), "hél");
+        assert_eq!(take_width("hé", 10), "hé");
+        assert_eq!(truncate_line("héllo wörld", 8), "héllo...");
+        assert_eq!(truncate_line_at("héllo wörld", 8, TruncatePosition::Start, "..."), "...wörld");
+        assert_eq!(truncate_line_at("0123456789abcdef", 10, TruncatePosition::Middle, "..."), "0123...def");
+        assert_eq!(truncate_line_at("0123456789abcdef", 6, TruncatePosition::Middle, "…"), "012…ef");
+        assert_eq!(truncate_line_at("0123456789", 4, TruncatePosition::End, ""), "0123");
+        assert_eq!(take_width_end("héllo", 2), "lo");
+        assert_eq!(text_width("東京"), 4);
+        assert_eq!(take_width("東京都", 5), "東京");
+        assert_eq!(take_width_end("東京都", 3), "都");
+        assert_eq!(truncate_line("東京都港区", 7), "東京...");
+        assert_eq!(text_width("\x1b[31mred\x1b[0m"), 3);
+        assert_eq!(truncate_line("\x1b[31mcritical\x1b[0m", 5), "\x1b[31mcr\x1b[0m...");
+        assert_eq!(
+            truncate_line_at("\x1b[32mok\x1b[0m: all good", 6, TruncatePosition::Start, "..."),
+            "...\x1b[32m\x1b[0mood"
+        );
+
+        let mut table = Table::with_headers(["City", "Pop"]).unwrap();
+        table.add_row(vec!["東京".to_string(), "14M".to_string()]).unwrap();
+        table.add_row(vec!["Osaka".to_string(), "2.7M".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "City  Pop \n===== ====\n東京  14M \nOsaka 2.7M\n");
+    }
+
+    #[test]
+    fn test_row_removal() {
+        let mut table = Table::with_headers(["Name", "City"]).unwrap();
+        table.add_row(vec!["Bartholomew".to_string(), "Rome".to_string()]).unwrap();
+        table.add_row(vec!["Ann".to_string(), "Los Angeles".to_string()]).unwrap();
+        table.add_row(vec!["Bo".to_string(), "Paris".to_string()]).unwrap();
+
+        let removed = table.remove_row(0).unwrap();
+        assert_eq!(removed, ["Bartholomew", "Rome"]);
+        assert!(table.columns[0].width_stale);
+        assert!(!table.columns[1].width_stale);
+        assert!(table.to_string().starts_with("Name City       \n==== ===========\n"));
+
+        table.retain_rows(|cells| cells[1] != "Los Angeles");
+        assert_eq!(table.row_count(), 1);
+        assert_eq!(table.to_string(), "Name City \n==== =====\nBo   Paris\n");
+
+        assert!(table.remove_row(5).is_err());
+        table.clear_rows();
+        assert_eq!(table.to_string(), "Name City\n==== ====\n");
+    }
+
+    #[test]
+    fn test_estimated_render_len() {
+        let mut table = Table::with_headers(["Name", "Notes"]).unwrap();
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+
+        table.add_row(vec!["John Doe".to_string(), "line one\nline two".to_string()]).unwrap();
+        table.add_row(vec!["Jane".to_string(), "short".to_string()]).unwrap();
+        table.columns[1].set_description("free text");
+        table.set_show_legend(true);
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+    }
+
+    #[test]
+    fn test_number_format() {
+        let mut value = Column::new("Value", 0, Justification::Right).unwrap();
+        value.set_number_format(NumberFormat::Scientific(2));
+        let mut sig = Column::new("Sig", 0, Justification::Right).unwrap();
+        sig.set_number_format(NumberFormat::SignificantFigures(2));
+        let mut table = Table::new(vec![value, sig]).unwrap();
+        table.add_row(vec!["1234567.891".to_string(), "0.012345".to_string()]).unwrap();
+        table.add_row(vec!["n/a".to_string(), "98765".to_string()]).unwrap();
+
+        assert_eq!(table.to_string(), "   Value   Sig\n======== =====\n1.23e+06 0.012\n     n/a 99000\n");
+        // Cells keep their original text
+        assert_eq!(table.row(0).unwrap(), ["1234567.891", "0.012345"]);
+    }
+
+    #[test]
+    fn test_percent_thresholds() {
+        let mut rate = Column::new("Success", 0, Justification::Right).unwrap();
+        rate.set_number_format(NumberFormat::Percent(1));
+        rate.set_thresholds(Thresholds {
+            below: Some(0.95),
+            above: None,
+            style: ThresholdStyle::Flag { below: "!".to_string(), above: String::new() },
+        });
+        let mut util = Column::new("Util", 0, Justification::Left).unwrap();
+        util.set_thresholds(Thresholds {
+            below: None,
+            above: Some(0.8),
+            style: ThresholdStyle::Color { below: AnsiColor::Green, above: AnsiColor::Red },
+        });
+        let mut table = Table::new(vec![rate, util]).unwrap();
+        table.add_row(vec!["0.992".to_string(), "0.91".to_string()]).unwrap();
+        table.add_row(vec!["0.9".to_string(), "0.5".to_string()]).unwrap();
+
+        assert_eq!(table.to_string(), "Success Util\n======= ====\n  99.2% \x1b[31m0.91\x1b[0m\n90.0% ! 0.5 \n");
+    }
+
+    #[test]
+    fn test_scale_legend() {
+        let mut rate = Column::new("Success", 0, Justification::Right).unwrap();
+        rate.set_number_format(NumberFormat::Percent(0));
+        rate.set_thresholds(Thresholds {
+            below: Some(0.95),
+            above: None,
+            style: ThresholdStyle::Flag { below: "!".to_string(), above: String::new() },
+        });
+        let mut util = Column::new("Util", 0, Justification::Left).unwrap();
+        util.set_thresholds(Thresholds {
+            below: Some(0.2),
+            above: Some(0.8),
+            style: ThresholdStyle::Color { below: AnsiColor::Blue, above: AnsiColor::Red },
+        });
+        let mut table = Table::new(vec![rate, util, Column::new("Host", 0, Justification::Left).unwrap()]).unwrap();
+        table.add_row(vec!["0.99".to_string(), "0.5".to_string(), "a".to_string()]).unwrap();
+        table.set_show_scale_legend(true);
+
+        let rendered = table.to_string();
+        assert!(rendered.ends_with(
+            "\nSuccess: ! below 95%\nUtil: \x1b[34m■\x1b[0m below 0.2, \x1b[31m■\x1b[0m above 0.8\n"
+        ), "{}", rendered);
+        assert_eq!(table.estimated_render_len(), rendered.len());
+
+        // Without color, colored values are plain and their legend entries are dropped
+        table.add_row(vec!["0.90".to_string(), "0.9".to_string(), "b".to_string()]).unwrap();
+        table.set_no_color(true);
+        let rendered = table.to_string();
+        assert!(!rendered.contains('\x1b'), "{}", rendered);
+        assert!(rendered.ends_with("\nSuccess: ! below 95%\n"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let mut util = Column::new("Util", 0, Justification::Left).unwrap();
+        util.set_thresholds(Thresholds {
+            below: None,
+            above: Some(0.8),
+            style: ThresholdStyle::Color { below: AnsiColor::Blue, above: AnsiColor::Red },
+        });
+        let mut table = Table::new(vec![util, Column::new("\x1b[1mHost\x1b[0m", 0, Justification::Left).unwrap()]).unwrap();
+        table.add_row(vec!["0.9".to_string(), "\x1b]8;;http://web-1\x07web-1\x1b]8;;\x07".to_string()]).unwrap();
+        table.add_row(vec!["0.5".to_string(), "\x1b[32mdb\x1b[0m".to_string()]).unwrap();
+        assert!(table.to_string().contains('\x1b'));
+        assert_eq!(table.render_plain(), "Util Host \n==== =====\n0.9  web-1\n0.5  db   \n");
+        table.set_omit_final_line_ending(true);
+        assert_eq!(table.render_plain(), "Util Host \n==== =====\n0.9  web-1\n0.5  db   ");
+        assert!(table.to_string().contains("\x1b[31m"));
+    }
+
+    #[test]
+    fn test_truncate_percentile() {
+        let mut path = Column::new("Path", 0, Justification::Left).unwrap();
+        path.set_truncate_percentile(95.0).unwrap();
+        let mut table = Table::new(vec![path]).unwrap();
+        for i in 0..19 {
+            table.add_row(vec![format!("/tmp/{}", i % 10)]).unwrap();
+        }
+        table.add_row(vec![format!("/var/{}", "x".repeat(40))]).unwrap();
+        let rendered = table.to_string();
+        assert!(rendered.starts_with("Path  \n======\n/tmp/0\n"), "{}", rendered);
+        assert!(rendered.ends_with("\n/va...\n"), "{}", rendered);
+
+        assert!(Column::new("a", 0, Justification::Left).unwrap().set_truncate_percentile(0.0).is_err());
+    }
+
+    #[test]
+    fn test_truncator() {
+        let mut id = Column::new("Id", 8, Justification::Left).unwrap();
+        id.set_truncator(|value, width| value.chars().take(width).collect());
+        let mut email = Column::new("Email", 12, Justification::Left).unwrap();
+        email.set_truncator(|value, _| value.split_once('@').map_or(value, |(_, domain)| domain).to_string());
+        let mut table = Table::new(vec![id, email]).unwrap();
+        table.add_row(vec!["3f2a9c1e-77b0-4c1d".to_string(), "someone.long@example.com".to_string()]).unwrap();
+        table.add_row(vec!["short".to_string(), "a@b.io".to_string()]).unwrap();
+        assert_eq!(
+            table.to_string(),
+            "Id       Email       \n======== ============\n3f2a9c1e example.com \nshort    a@b.io      \n"
+        );
+    }
+
+    #[test]
+    fn test_value_transform() {
+        let mut status = Column::new("Status", 0, Justification::Right).unwrap();
+        status.set_value_transform(CaseTransform::Uppercase);
+        let mut table = Table::new(vec![status]).unwrap();
+        for value in ["ok", "Failed", "straße"] {
+            table.add_row(vec![value.to_string()]).unwrap();
+        }
+        assert_eq!(table.to_string(), " Status\n=======\n     OK\n FAILED\nSTRASSE\n");
+        assert_eq!(table.row(2).unwrap()[0], "straße");
+
+        table.columns[0].set_value_transform(CaseTransform::TitleCase);
+        assert!(table.to_string().ends_with("    Ok\nFailed\nStraße\n"));
+    }
+
+    #[test]
+    fn test_single_line_rows() {
+        let mut table = Table::with_headers(["Host", "Note"]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "first\nsecond".to_string()]).unwrap();
+        assert!(table.columns[1].multiline);
+        table.remove_row(0).unwrap();
+        assert!(table.columns[1].multiline);
+        table.clear_rows();
+        assert!(!table.columns[1].multiline);
+
+        table.add_row(vec!["web-1".to_string(), "ok".to_string()]).unwrap();
+        table.add_row(vec!["db-1".to_string(), "secret".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Host  Note  \n===== ======\nweb-1 ok    \ndb-1  secret\n");
+        // Values that only become multiline when displayed still take the multiline path
+        table.add_redaction("secret", "[hidden\nvalue]").unwrap();
+        assert_eq!(table.to_string(), "Host  Note   \n===== =======\nweb-1 ok     \ndb-1  [hidden\n      value] \n");
+    }
+
+    #[test]
+    fn test_center_justification() {
+        let mut table = Table::new(vec![
+            Column::new("Id", 0, Justification::Center).unwrap(),
+            Column::new("Status", 0, Justification::Center).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["7".to_string(), "ok".to_string()]).unwrap();
+        table.add_row(vec!["1234".to_string(), "failed\nretry".to_string()]).unwrap();
+        assert_eq!(
+            table.to_string(),
+            " Id  Status\n==== ======\n 7     ok  \n1234 failed\n     retry \n"
+        );
+    }
+
+    #[test]
+    fn test_decimal_justification() {
+        let mut table = Table::new(vec![Column::new("Amount", 0, Justification::Decimal).unwrap()]).unwrap();
+        for value in ["1000.5", "3.25", "12", "0.125%"] {
+            table.add_row(vec![value.to_string()]).unwrap();
+        }
+        assert_eq!(
+            table.to_string(),
+            "   Amount\n=========\n1000.5   \n   3.25  \n  12     \n   0.125%\n"
+        );
+
+        // A '.' in a hyperlink's hidden target is not a decimal point
+        let link = "\x1b]8;;http://10.0.0.1\x07host\x1b]8;;\x07";
+        let mut table = Table::new(vec![Column::new("Amount", 0, Justification::Decimal).unwrap()]).unwrap();
+        table.add_row(vec![link.to_string()]).unwrap();
+        table.add_row(vec!["1.5".to_string()]).unwrap();
+        assert_eq!(table.to_string(), format!("Amount\n======\n{}  \n   1.5\n", link));
+    }
+
+    #[test]
+    fn test_vertical_alignment() {
+        let mut table = Table::with_headers(["Note", "Mid", "Low"]).unwrap();
+        table.columns[1].set_vertical_alignment(VerticalAlignment::Middle);
+        table.columns[2].set_vertical_alignment(VerticalAlignment::Bottom);
+        table.add_row(vec!["a\nb\nc\nd".to_string(), "x".to_string(), "y".to_string()]).unwrap();
+        assert_eq!(
+            table.to_string(),
+            "Note Mid Low\n==== === ===\na           \nb    x      \nc           \nd        y  \n"
+        );
+    }
+
+    #[test]
+    fn test_header_justification() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Total", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.columns[1].set_header_justification(Justification::Center);
+        table.add_row(vec!["disk".to_string(), "1234567".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Name  Total \n==== =======\ndisk 1234567\n");
+        assert_eq!(table.columns[0].header_justification(), Justification::Left);
+    }
+
+    #[test]
+    fn test_auto_alignment() {
+        let mut table = Table::with_headers(["Host", "Cpu"]).unwrap();
+        for col in &mut table.columns {
+            col.set_align_mode(AlignMode::Auto);
+        }
+        table.add_row(vec!["web-1".to_string(), "7.5".to_string()]).unwrap();
+        table.add_row(vec!["db-1".to_string(), "".to_string()]).unwrap();
+        table.add_row(vec!["cache".to_string(), "12".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Host  Cpu\n===== ===\nweb-1 7.5\ndb-1     \ncache  12\n");
+        assert!(table.validate().is_empty());
+
+        table.add_row(vec!["dns".to_string(), "n/a".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Host  Cpu\n===== ===\nweb-1 7.5\ndb-1     \ncache 12 \ndns   n/a\n");
+    }
+
+    #[test]
+    fn test_ellipsis() {
+        let mut table = Table::new(vec![Column::new("Commit", 8, Justification::Left).unwrap()]).unwrap();
+        table.columns[0].set_ellipsis("…");
+        table.columns[0].set_truncate_position(TruncatePosition::Middle);
+        table.add_row(vec!["3f9a2c1d8e7b".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Commit  \n========\n3f9a…e7b\n");
+    }
+
+    #[test]
+    fn test_tab_expansion() {
+        let mut table = Table::with_headers(["Config", "On"]).unwrap();
+        table.add_row(vec!["a\tb".to_string(), "y".to_string()]).unwrap();
+        table.add_row(vec!["key\tvalue".to_string(), "n".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Config        On\n============= ==\na       b     y \nkey     value n \n");
+        table.columns[0].set_tab_width(4).unwrap();
+        assert_eq!(table.to_string(), "Config    On\n========= ==\na   b     y \nkey value n \n");
+        assert!(table.columns[0].set_tab_width(0).is_err());
+    }
+
+    #[test]
+    fn test_fit_to_width() {
+        let mut table = Table::with_headers(["Host", "Message"]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "connection refused by upstream".to_string()]).unwrap();
+        table.add_row(vec!["db-1".to_string(), "ok".to_string()]).unwrap();
+        table.columns[1].set_overflow(Overflow::Wrap);
+        table.fit_to_width(20);
+        assert_eq!(
+            table.to_string(),
+            "Host  Message       \n===== ==============\nweb-1 connection    \n      refused by    \n      upstream      \ndb-1  ok            \n"
+        );
+
+        let mut table = Table::with_headers(["Id", "Rate"]).unwrap();
+        table.columns[1].set_unit("requests/second");
+        table.add_row(vec!["1".to_string(), "20".to_string()]).unwrap();
+        table.fit_to_width(12);
+        assert_eq!(table.widths(), [2, 9]);
+        assert!(table.to_string().lines().all(|line| text_width(line) <= 12));
+    }
+
+    #[test]
+    fn test_max_width() {
+        let mut table = Table::with_headers(["Id", "Description", "Owner"]).unwrap();
+        table.add_row(vec!["1".to_string(), "rotate the database credentials".to_string(), "platform-team".to_string()]).unwrap();
+        table.columns[0].set_fixed_width(2).unwrap();
+        table.set_max_width(30);
+        assert_eq!(table.widths(), [2, 17, 9]);
+        assert_eq!(
+            table.to_string(),
+            "Id Description       Owner    \n== ================= =========\n1  rotate the dat... platfo...\n"
+        );
+        table.set_max_width(5);
+        assert_eq!(table.widths(), [2, 3, 3]);
+        table.clear_max_width();
+        assert_eq!(table.widths(), [2, 31, 13]);
+    }
+
+    #[test]
+    fn test_min_width() {
+        let mut table = Table::with_headers(["Id", "Status"]).unwrap();
+        table.add_row(vec!["1".to_string(), "ok".to_string()]).unwrap();
+        table.columns[0].set_min_width(5).unwrap();
+        assert_eq!(table.to_string(), "Id    Status\n===== ======\n1     ok    \n");
+        table.add_row(vec!["1234567".to_string(), "ok".to_string()]).unwrap();
+        assert_eq!(table.widths(), [7, 6]);
+
+        table.set_max_width(10);
+        assert_eq!(table.widths(), [5, 4]);
+        table.clear_max_width();
+        table.fit_to_width(10);
+        assert_eq!(table.widths(), [5, 3]);
+        assert!(table.columns[0].set_min_width(5001).is_err());
+    }
+
+    #[test]
+    fn test_width_percent() {
+        let mut table = Table::with_headers(["Path", "Size", "Note"]).unwrap();
+        table.add_row(vec!["/var/log/syslog".to_string(), "12K".to_string(), "rotated daily".to_string()]).unwrap();
+        table.columns[0].set_width_percent(50.0).unwrap();
+        table.columns[2].set_width_percent(30.0).unwrap();
+        // Ignored without a maximum width
+        assert_eq!(table.widths(), [15, 4, 13]);
+        table.set_max_width(22);
+        assert_eq!(table.widths(), [10, 4, 6]);
+        assert_eq!(table.to_string(), "Path       Size Note  \n========== ==== ======\n/var/lo... 12K  rot...\n");
+        assert!(table.columns[1].set_width_percent(0.0).is_err());
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let mut table = Table::with_headers(["a", "b"]).unwrap();
+        table.add_row(vec!["1".to_string(), "2".to_string()]).unwrap();
+        table.set_line_ending(LineEnding::CrLf);
+        assert_eq!(table.to_string(), "a b\r\n= =\r\n1 2\r\n");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+        table.set_omit_final_line_ending(true);
+        assert_eq!(table.to_string(), "a b\r\n= =\r\n1 2");
+        assert_eq!(table.render_rows(0..1).unwrap(), "1 2\r\n");
+    }
+
+    #[test]
+    fn test_lock_widths() {
+        let mut table = Table::with_headers(["Host", "Status"]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "up".to_string()]).unwrap();
+        table.lock_widths();
+        assert_eq!(table.widths(), [5, 6]);
+        table.add_row(vec!["cache-primary".to_string(), "degraded".to_string()]).unwrap();
+        assert_eq!(
+            table.to_string(),
+            "Host  Status\n===== ======\nweb-1 up    \nca... deg...\n"
+        );
+
+        let mut sibling = Table::with_headers(["Hostname", "Up"]).unwrap();
+        sibling.set_fixed_widths(&table.widths()).unwrap();
+        assert!(sibling.to_string().starts_with("Ho... Up    \n"));
+        assert!(sibling.set_fixed_widths(&[5]).is_err());
+        assert!(sibling.set_fixed_widths(&[5, 0]).is_err());
+        sibling.set_fixed_widths(&[1, 2]).unwrap();
+        sibling.add_row(vec!["db".to_string(), "yes".to_string()]).unwrap();
+        assert_eq!(sibling.to_string(), "H Up\n= ==\nd ye\n");
+
+        table.unlock_widths();
+        assert_eq!(table.widths(), [13, 8]);
+    }
+
+    #[test]
+    fn test_changed_rows_since() {
+        let mut table = Table::with_headers(["Host", "Status"]).unwrap();
+        for host in ["a", "b", "c"] {
+            table.add_row(vec![host.to_string(), "up".to_string()]).unwrap();
+        }
+        let start = table.generation();
+        assert_eq!(table.changed_rows_since(start), Vec::<usize>::new());
+
+        table.row_mut(1).unwrap().set("Status", "down").unwrap();
+        table.set_row_metadata(2, "class", "new").unwrap();
+        assert_eq!(table.changed_rows_since(start), [1, 2]);
+        let checkpoint = table.generation();
+        table.add_row(vec!["d".to_string(), "up".to_string()]).unwrap();
+        assert_eq!(table.changed_rows_since(checkpoint), [3]);
+
+        table.remove_row(0).unwrap();
+        assert_eq!(table.changed_rows_since(checkpoint), [0, 1, 2]);
+        assert_eq!(table.changed_rows_since(table.generation()), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_units_row() {
+        let mut latency = Column::new("Latency", 0, Justification::Right).unwrap();
+        latency.set_unit("ms");
+        let mut rate = Column::new("Rate", 0, Justification::Right).unwrap();
+        rate.set_unit("req/s");
+        let mut table = Table::new(vec![Column::new("Host", 0, Justification::Left).unwrap(), latency, rate]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "12".to_string(), "250".to_string()]).unwrap();
+
+        assert_eq!(table.to_string(), "\
+Host  Latency  Rate
+        ms    req/s
+===== ======= =====
+web-1      12   250
+");
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+    }
+
+    #[test]
+    fn test_sort_select_truncate() {
+        let mut table = Table::with_headers(["Name", "Size"]).unwrap();
+        for (name, size) in [("b", "10"), ("a", "9"), ("c", "n/a"), ("d", "100")] {
+            table.add_row(vec![name.to_string(), size.to_string()]).unwrap();
+        }
+        table.set_cell_metadata(0, 1, "class", "big").unwrap();
+
+        table.sort_by_column(1, false).unwrap();
+        let names: Vec<&str> = (0..4).map(|i| table.row(i).unwrap()[0].as_str()).collect();
+        assert_eq!(names, ["a", "b", "d", "c"]);
+        table.sort_by_column(0, true).unwrap();
+        assert_eq!(table.row(0).unwrap()[0], "d");
+        assert!(table.sort_by_column(2, false).is_err());
+
+        let selected = table.select_columns(&["Size", "Name"]).unwrap();
+        assert_eq!(selected.columns()[0].name(), "Size");
+        assert_eq!(selected.row(2).unwrap(), ["10", "b"]);
+        assert_eq!(selected.cell_metadata(2, 0).unwrap()["class"], "big");
+        assert!(table.select_columns(&["Missing"]).is_err());
+
+        table.truncate_rows(2);
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.to_string(), "Name Size\n==== ====\nd    100 \nc    n/a \n");
+    }
+
+    #[test]
+    fn test_render_rows() {
+        let mut table = Table::with_headers(["Name", "Notes"]).unwrap();
+        table.add_row(vec!["a".to_string(), "one\ntwo".to_string()]).unwrap();
+        table.add_row(vec!["bb".to_string(), "x".to_string()]).unwrap();
+        assert_eq!(table.render_rows(1..2).unwrap(), "bb   x    \n");
+        assert_eq!(table.render_rows(0..2).unwrap(), "a    one  \n     two  \nbb   x    \n");
+        assert!(table.render_rows(1..3).is_err());
+    }
+
+    #[test]
+    fn test_multiline_headers() {
+        let mut table = Table::new(vec![
+            Column::new("Host", 0, Justification::Left).unwrap(),
+            Column::new("Total\nCost (USD)", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "12".to_string()]).unwrap();
+        assert_eq!(table.to_string(), "Host       Total\n      Cost (USD)\n===== ==========\nweb-1         12\n");
+        assert_eq!(table.header_line_count(), 3);
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+    }
 }
@@ -0,0 +1,257 @@
+use std::io::BufRead;
+
+use crate::regex::Regex;
+use crate::{Column, Justification, Table};
+
+/// How Table::from_lines splits each input line into fields
+#[derive(Debug, Clone, PartialEq)]
+pub enum Splitter {
+    /// Fields separated by runs of whitespace; leading/trailing whitespace is ignored
+    Whitespace,
+    /// Fields separated by a single delimiter character; fields are trimmed
+    Delim(char),
+    /// A regular expression. If the pattern has capture groups, each group of the
+    /// first match is a field (lines that don't match are skipped); otherwise the
+    /// pattern is a delimiter to split on.
+    Regex(String),
+}
+
+/// A splitter ready to be applied to lines
+#[derive(Debug)]
+enum Compiled {
+    Whitespace,
+    Delim(char),
+    Split(Regex),
+    Captures(Regex),
+}
+
+impl Compiled {
+    fn new(splitter: Splitter) -> Result<Compiled, String> {
+        Ok(match splitter {
+            Splitter::Whitespace => Compiled::Whitespace,
+            Splitter::Delim(c) => Compiled::Delim(c),
+            Splitter::Regex(pattern) => {
+                let re = Regex::new(&pattern).map_err(|e| format!("invalid pattern '{}': {}", pattern, e))?;
+                if re.group_count() > 0 { Compiled::Captures(re) } else { Compiled::Split(re) }
+            }
+        })
+    }
+
+    /// Splits a line into at most `limit` fields (0 for no limit), with the last field
+    /// holding the remainder of the line. Returns None if the line should be skipped.
+    fn split(&self, line: &str, limit: usize) -> Option<Vec<String>> {
+        let fields = match self {
+            Compiled::Whitespace => {
+                let mut fields = Vec::new();
+                let mut rest = line.trim();
+                while !rest.is_empty() {
+                    if limit > 0 && fields.len() + 1 == limit {
+                        fields.push(rest.to_string());
+                        break;
+                    }
+                    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    fields.push(rest[..end].to_string());
+                    rest = rest[end..].trim_start();
+                }
+                fields
+            }
+            Compiled::Delim(delim) => {
+                let pieces: Vec<&str> = if limit > 0 {
+                    line.splitn(limit, *delim).collect()
+                } else {
+                    line.split(*delim).collect()
+                };
+                pieces.into_iter().map(|p| p.trim().to_string()).collect()
+            }
+            Compiled::Split(re) => re.splitn(line, limit).into_iter().map(str::to_string).collect(),
+            Compiled::Captures(re) => re
+                .captures_at(line, 0)?
+                .into_iter()
+                .skip(1)
+                .map(|group| group.map_or_else(String::new, |(s, e)| line[s..e].to_string()))
+                .collect(),
+        };
+        Some(fields)
+    }
+}
+
+impl Table {
+    /// Loads line-oriented text (command output, logs) by splitting each non-blank
+    /// line into fields. If headers is None the first non-blank line supplies the
+    /// column names. Lines with fewer fields than columns are padded with empty cells;
+    /// with more fields the remainder of the line goes into the last column.
+    /// Columns are left-justified and not truncated.
+    ///
+    /// # Errors
+    /// - If the regex pattern is invalid
+    /// - If reading fails or no header line is found
+    /// - If a header name is empty or the rows exceed the table limits
+    pub fn from_lines<R: BufRead>(reader: R, splitter: Splitter, headers: Option<&[&str]>) -> Result<Table, String> {
+        let compiled = Compiled::new(splitter).map_err(|e| format!("Table::from_lines: {}", e))?;
+
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| line.map_err(|e| format!("Table::from_lines: line {}: {}", idx + 1, e)))
+            .filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty()));
+
+        let names: Vec<String> = match headers {
+            Some(names) => names.iter().map(|n| n.to_string()).collect(),
+            None => {
+                let line = lines.next().ok_or_else(|| "Table::from_lines: no header line found".to_string())??;
+                compiled.split(&line, 0)
+                    .ok_or_else(|| "Table::from_lines: header line does not match the pattern".to_string())?
+            }
+        };
+        let columns = names
+            .into_iter()
+            .map(|name| Column::new(name, 0, Justification::Left))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::from_lines: {}", e))?;
+        let mut table = Table::new(columns)?;
+
+        let column_count = table.columns.len();
+        for line in lines {
+            let Some(mut row) = compiled.split(&line?, column_count) else { continue };
+            row.resize(column_count, String::new());
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+}
+
+/// An io::Write sink that splits incoming bytes into lines and fields and appends
+/// each line as a row of the wrapped table, so writer-based producers can feed a
+/// table directly. Blank lines are skipped, "\r\n" line endings are accepted, and
+/// field counts are reconciled as in Table::from_lines.
+#[derive(Debug)]
+pub struct TableSink {
+    table: Table,
+    splitter: Compiled,
+    buffer: Vec<u8>,
+}
+
+impl TableSink {
+    /// Creates a sink appending rows to the given table
+    ///
+    /// # Errors
+    /// - If the regex pattern is invalid
+    pub fn new(table: Table, splitter: Splitter) -> Result<TableSink, String> {
+        let splitter = Compiled::new(splitter).map_err(|e| format!("TableSink::new: {}", e))?;
+        Ok(TableSink { table, splitter, buffer: Vec::new() })
+    }
+
+    /// Returns the table with the rows added so far
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Adds any buffered final line without a trailing newline and returns the table
+    ///
+    /// # Errors
+    /// - If the final line is not valid UTF-8 or cannot be added
+    pub fn into_table(mut self) -> std::io::Result<Table> {
+        let rest = std::mem::take(&mut self.buffer);
+        self.add_line(&rest)?;
+        Ok(self.table)
+    }
+
+    fn add_line(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let line = std::str::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        let column_count = self.table.columns.len();
+        if let Some(mut row) = self.splitter.split(line, column_count) {
+            row.resize(column_count, String::new());
+            self.table.add_row(row).map_err(std::io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::io::Write for TableSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if let Some(last_newline) = self.buffer.iter().rposition(|&b| b == b'\n') {
+            let rest = self.buffer.split_off(last_newline + 1);
+            let complete = std::mem::replace(&mut self.buffer, rest);
+            for line in complete[..last_newline].split(|&b| b == b'\n') {
+                self.add_line(line)?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_lines_whitespace() {
+        let input = "PID TTY CMD\n  1 ?   /sbin/init splash\n\n 42 pts/0 bash\n";
+        let table = Table::from_lines(input.as_bytes(), Splitter::Whitespace, None).unwrap();
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["PID", "TTY", "CMD"]);
+        assert_eq!(table.row(0).unwrap(), ["1", "?", "/sbin/init splash"]);
+        assert_eq!(table.row(1).unwrap(), ["42", "pts/0", "bash"]);
+    }
+
+    #[test]
+    fn test_from_lines_delim() {
+        let input = "root:x:0:0:root:/root:/bin/bash\nshort:x\n";
+        let headers = ["user", "pw", "uid", "gid", "gecos", "home", "shell"];
+        let table = Table::from_lines(input.as_bytes(), Splitter::Delim(':'), Some(&headers)).unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(0).unwrap()[6], "/bin/bash");
+        assert_eq!(table.row(1).unwrap(), ["short", "x", "", "", "", "", ""]);
+    }
+
+    #[test]
+    fn test_from_lines_regex() {
+        let input = "a , b,c\nd,e , f\n";
+        let table = Table::from_lines(input.as_bytes(), Splitter::Regex(r"\s*,\s*".to_string()), Some(&["x", "y", "z"])).unwrap();
+        assert_eq!(table.row(0).unwrap(), ["a", "b", "c"]);
+        assert_eq!(table.row(1).unwrap(), ["d", "e", "f"]);
+
+        let log = "GET /index 200\nnoise line\nPOST /api 500\n";
+        let table = Table::from_lines(
+            log.as_bytes(),
+            Splitter::Regex(r"^(\w+) (\S+) (\d+)$".to_string()),
+            Some(&["Method", "Path", "Status"]),
+        ).unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(1).unwrap(), ["POST", "/api", "500"]);
+
+        assert!(Table::from_lines("".as_bytes(), Splitter::Regex("(".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn test_table_sink() {
+        use std::io::Write;
+
+        let table = Table::new(vec![
+            Column::new("Key", 0, Justification::Left).unwrap(),
+            Column::new("Value", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        let mut sink = TableSink::new(table, Splitter::Delim('=')).unwrap();
+
+        write!(sink, "alpha=1\r\nbe").unwrap();
+        assert_eq!(sink.table().row_count(), 1);
+        write!(sink, "ta=2=3\n\ngamma").unwrap();
+        assert_eq!(sink.table().row_count(), 2);
+
+        let table = sink.into_table().unwrap();
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.row(0).unwrap(), ["alpha", "1"]);
+        assert_eq!(table.row(1).unwrap(), ["beta", "2=3"]);
+        assert_eq!(table.row(2).unwrap(), ["gamma", ""]);
+    }
+}
@@ -0,0 +1,419 @@
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use ctable::{RenderOptions, Splitter, Table};
+
+const USAGE: &str = "\
+Usage: ctable [OPTIONS] [FILE]
+
+Reads a table from FILE (or standard input) and renders it as aligned text.
+
+Options:
+  -f, --format FORMAT    input format: aligned (default), csv, jsonl, markdown, tsv,
+                         whitespace
+  -w, --where FILTER     keep rows where COL=VALUE, COL!=VALUE, COL~TEXT (contains),
+                         COL<N, COL<=N, COL>N, or COL>=N; repeat to require several
+  -s, --sort COL[:desc]  sort rows by a column, numerically where values are numbers
+  -c, --columns A,B,C    show only these columns, in this order
+  -n, --limit N          show at most N rows (after filtering and sorting)
+  -F, --follow           keep watching FILE and show rows as they are appended;
+                         on a terminal the table is redrawn, otherwise new rows
+                         are printed beneath the rows already shown (or the whole
+                         table again when --sort or --where is set)
+  -p, --pager            show the table through $PAGER (default less -RS) when it
+                         doesn't fit on the terminal
+  -i, --interactive      browse the table in a full-screen viewer (scroll, sort with
+                         1-9, search with /); needs the interactive feature
+  -P, --profile FILE     render with the options of a profile file; a user profile
+                         at $XDG_CONFIG_HOME/ctable/profile.toml is applied over it
+  -h, --help             show this help
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Aligned,
+    Csv,
+    Jsonl,
+    Markdown,
+    Tsv,
+    Whitespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Contains,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A --where condition on one column
+#[derive(Debug, PartialEq)]
+struct Filter {
+    column: String,
+    op: Op,
+    value: String,
+}
+
+impl Filter {
+    /// Parses COL<op>VALUE, splitting at the first operator in the text
+    fn parse(text: &str) -> Result<Filter, String> {
+        const OPS: [(&str, Op); 7] = [
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("=", Op::Eq),
+            ("~", Op::Contains),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        let (pos, token, op) = OPS
+            .iter()
+            .filter_map(|&(token, op)| text.find(token).map(|pos| (pos, token, op)))
+            .min_by_key(|&(pos, token, _)| (pos, std::cmp::Reverse(token.len())))
+            .ok_or_else(|| format!("invalid filter '{}': expected COL=VALUE", text))?;
+        let column = text[..pos].trim();
+        if column.is_empty() {
+            return Err(format!("invalid filter '{}': missing column name", text));
+        }
+        Ok(Filter { column: column.to_string(), op, value: text[pos + token.len()..].trim().to_string() })
+    }
+
+    /// Tests a cell value. Ordering operators compare numerically when both sides
+    /// are numbers and as text otherwise.
+    fn matches(&self, cell: &str) -> bool {
+        let cell = cell.trim();
+        let ordering = match (cell.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(cell.cmp(self.value.as_str())),
+        };
+        match self.op {
+            Op::Eq => cell == self.value || ordering == Some(std::cmp::Ordering::Equal),
+            Op::Ne => cell != self.value && ordering != Some(std::cmp::Ordering::Equal),
+            Op::Contains => cell.contains(&self.value),
+            Op::Lt => ordering.is_some_and(|o| o.is_lt()),
+            Op::Le => ordering.is_some_and(|o| o.is_le()),
+            Op::Gt => ordering.is_some_and(|o| o.is_gt()),
+            Op::Ge => ordering.is_some_and(|o| o.is_ge()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    format: Format,
+    filters: Vec<Filter>,
+    sort: Option<(String, bool)>,
+    columns: Option<Vec<String>>,
+    limit: Option<usize>,
+    file: Option<String>,
+    profile: Option<String>,
+    follow: bool,
+    pager: bool,
+    interactive: bool,
+    help: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        format: Format::Aligned,
+        filters: Vec::new(),
+        sort: None,
+        columns: None,
+        limit: None,
+        file: None,
+        profile: None,
+        follow: false,
+        pager: false,
+        interactive: false,
+        help: false,
+    };
+    while let Some(arg) = args.next() {
+        // Accept both "--flag value" and "--flag=value"
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline.clone().or_else(|| args.next()).ok_or_else(|| format!("{} requires a value", name))
+        };
+        match flag.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "-F" | "--follow" | "--watch" => parsed.follow = true,
+            "-p" | "--pager" => parsed.pager = true,
+            "-i" | "--interactive" => parsed.interactive = true,
+            "-f" | "--format" => {
+                parsed.format = match value("--format")?.as_str() {
+                    "aligned" => Format::Aligned,
+                    "csv" => Format::Csv,
+                    "jsonl" | "ndjson" => Format::Jsonl,
+                    "markdown" | "md" => Format::Markdown,
+                    "tsv" => Format::Tsv,
+                    "whitespace" => Format::Whitespace,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "-P" | "--profile" => parsed.profile = Some(value("--profile")?),
+            "-w" | "--where" => parsed.filters.push(Filter::parse(&value("--where")?)?),
+            "-s" | "--sort" => {
+                let spec = value("--sort")?;
+                parsed.sort = Some(match spec.rsplit_once(':') {
+                    Some((column, "desc")) => (column.to_string(), true),
+                    Some((column, "asc")) => (column.to_string(), false),
+                    _ => (spec, false),
+                });
+            }
+            "-c" | "--columns" => {
+                let list = value("--columns")?;
+                parsed.columns = Some(list.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect());
+            }
+            "-n" | "--limit" => {
+                let n = value("--limit")?;
+                parsed.limit = Some(n.parse().map_err(|_| format!("invalid --limit '{}'", n))?);
+            }
+            _ if flag.starts_with('-') && flag != "-" => return Err(format!("unknown option '{}'", flag)),
+            _ if parsed.file.is_none() => parsed.file = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn read_table<R: BufRead>(mut reader: R, format: Format) -> Result<Table, String> {
+    match format {
+        Format::Jsonl => Table::from_jsonl(reader),
+        Format::Tsv => Table::from_lines(reader, Splitter::Delim('\t'), None),
+        Format::Whitespace => Table::from_lines(reader, Splitter::Whitespace, None),
+        Format::Aligned | Format::Csv | Format::Markdown => {
+            let mut text = String::new();
+            reader.read_to_string(&mut text).map_err(|e| e.to_string())?;
+            match format {
+                Format::Aligned => Table::from_aligned(&text),
+                Format::Csv => Table::from_csv(&text),
+                _ => Table::from_markdown(&text),
+            }
+        }
+    }
+}
+
+/// Applies the filters, sort, limit, and column selection, in that order
+fn transform(mut table: Table, args: &Args) -> Result<Table, String> {
+    let column = |table: &Table, name: &str| {
+        table.column_index(name).ok_or_else(|| format!("no column named '{}'", name))
+    };
+    let filters = args
+        .filters
+        .iter()
+        .map(|f| column(&table, &f.column).map(|idx| (idx, f)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !filters.is_empty() {
+        table.retain_rows(|row| filters.iter().all(|(idx, f)| f.matches(&row[*idx])));
+    }
+    if let Some((name, descending)) = &args.sort {
+        let idx = column(&table, name)?;
+        table.sort_by_column(idx, *descending)?;
+    }
+    if let Some(limit) = args.limit {
+        table.truncate_rows(limit);
+    }
+    if let Some(columns) = &args.columns {
+        let names: Vec<&str> = columns.iter().map(String::as_str).collect();
+        table = table.select_columns(&names)?;
+    }
+    Ok(table)
+}
+
+/// Builds the render options from the --profile file (or the defaults with
+/// trailing padding trimmed), with the user's profile applied over them
+fn render_options(args: &Args) -> Result<RenderOptions, String> {
+    let mut options = match &args.profile {
+        Some(path) => RenderOptions::load_profile(path)?,
+        None => RenderOptions { trim_trailing_padding: true, ..Default::default() },
+    };
+    options.apply_user_profile()?;
+    Ok(options)
+}
+
+/// Returns the text up to and including the last newline that ends a record, so
+/// a record that is still being written is left for the next read. In CSV a
+/// newline inside a quoted field does not end the record.
+fn complete_lines(text: &str, format: Format) -> &str {
+    let end = if format == Format::Csv {
+        let mut quoted = false;
+        let mut end = None;
+        for (pos, b) in text.bytes().enumerate() {
+            match b {
+                b'"' => quoted = !quoted,
+                b'\n' if !quoted => end = Some(pos),
+                _ => {}
+            }
+        }
+        end
+    } else {
+        text.rfind('\n')
+    };
+    end.map_or("", |end| &text[..=end])
+}
+
+/// Returns whether --follow can print only the new rows beneath those already
+/// shown. Sorting and filtering can place new rows among the earlier ones, so
+/// with either set the whole table is printed again.
+fn appends_rows(args: &Args) -> bool {
+    args.sort.is_none() && args.filters.is_empty()
+}
+
+/// Re-reads the file whenever its size changes. On a terminal the whole table is
+/// redrawn; otherwise rows beyond those already printed are written below them
+/// at the widths of the printed header, and the table is printed again from the
+/// header if the file shrinks, the columns change, or --sort or --where is set.
+fn follow(path: &str, args: &Args, options: &RenderOptions) -> Result<(), String> {
+    let redraw = std::io::stdout().is_terminal();
+    let append = appends_rows(args);
+    let mut last_len = None;
+    // Rows written so far in append mode, or None before the header is written
+    let mut printed: Option<usize> = None;
+    // Column widths of the printed header, kept for the rows appended beneath it
+    let mut widths: Vec<usize> = Vec::new();
+    loop {
+        let len = std::fs::metadata(path).map_err(|e| format!("{}: {}", path, e))?.len();
+        if last_len != Some(len) {
+            last_len = Some(len);
+            let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+            match read_table(complete_lines(&text, args.format).as_bytes(), args.format).and_then(|t| transform(t, args)) {
+                // Nothing usable yet (e.g. an empty file); keep waiting
+                Err(e) if printed.is_none() && !redraw => eprintln!("ctable: waiting for data: {}", e),
+                Err(e) => eprintln!("ctable: {}", e),
+                Ok(mut table) => {
+                    table.set_render_options(options.clone());
+                    let rows = table.row_count();
+                    let mut out = std::io::stdout().lock();
+                    let written = match printed {
+                        _ if redraw => write!(out, "\x1b[H\x1b[2J{}", table),
+                        // The columns are unchanged, so new rows line up with those above
+                        Some(done) if append && done <= rows && table.set_fixed_widths(&widths).is_ok() => {
+                            write!(out, "{}", table.render_rows(done..rows)?)
+                        }
+                        _ => {
+                            widths = table.widths();
+                            write!(out, "{}", table)
+                        }
+                    };
+                    written.and_then(|_| out.flush()).map_err(|e| e.to_string())?;
+                    printed = Some(rows);
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args(std::env::args().skip(1))?;
+    if args.help {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    let options = render_options(&args)?;
+    if args.follow {
+        return match args.file.as_deref() {
+            None | Some("-") => Err("--follow requires a FILE".to_string()),
+            Some(path) => follow(path, &args, &options),
+        };
+    }
+    let table = match args.file.as_deref() {
+        None | Some("-") => read_table(std::io::stdin().lock(), args.format)?,
+        Some(path) => {
+            let file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+            read_table(BufReader::new(file), args.format)?
+        }
+    };
+    let mut table = transform(table, &args)?;
+    table.set_render_options(options);
+    if args.interactive {
+        #[cfg(feature = "interactive")]
+        return table.view_interactive().map_err(|e| e.to_string());
+        #[cfg(not(feature = "interactive"))]
+        return Err("--interactive requires building with the interactive feature".to_string());
+    }
+    if args.pager {
+        table.print_paged().map_err(|e| e.to_string())?;
+    } else {
+        table.print().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ctable: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Args, String> {
+        parse_args(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&["--where", "Use%>=50", "-s", "Size:desc", "--columns=Name, Size", "-n", "3", "df.txt"]).unwrap();
+        assert_eq!(parsed.filters, [Filter { column: "Use%".to_string(), op: Op::Ge, value: "50".to_string() }]);
+        assert_eq!(parsed.sort, Some(("Size".to_string(), true)));
+        assert_eq!(parsed.columns, Some(vec!["Name".to_string(), "Size".to_string()]));
+        assert_eq!(parsed.limit, Some(3));
+        assert_eq!(parsed.file.as_deref(), Some("df.txt"));
+
+        assert!(args(&["--limit"]).is_err());
+        assert!(args(&["--bogus"]).is_err());
+        assert!(args(&["-w", "no operator"]).is_err());
+        assert_eq!(Filter::parse("a!=b").unwrap().op, Op::Ne);
+        assert!(args(&["--follow", "log.jsonl"]).unwrap().follow);
+        assert!(args(&["-p"]).unwrap().pager);
+        assert_eq!(args(&["--profile=team.toml"]).unwrap().profile.as_deref(), Some("team.toml"));
+    }
+
+    #[test]
+    fn test_transform() {
+        let input = "Name  Size State\nalpha   10 up\nbeta     2 down\ngamma  300 up\ndelta   40 up\n";
+        let table = read_table(input.as_bytes(), Format::Aligned).unwrap();
+        let parsed = args(&["-w", "State=up", "-w", "Size>5", "--sort", "Size:desc", "--limit", "2", "-c", "Name,Size"]).unwrap();
+        let table = transform(table, &parsed).unwrap();
+        assert_eq!(table.columns().len(), 2);
+        assert_eq!(table.row(0).unwrap(), ["gamma", "300"]);
+        assert_eq!(table.row(1).unwrap(), ["delta", "40"]);
+        assert_eq!(table.row_count(), 2);
+
+        let bad = args(&["-s", "Missing"]).unwrap();
+        assert!(transform(read_table(input.as_bytes(), Format::Aligned).unwrap(), &bad).is_err());
+
+        let csv = read_table("Name,Note\nalpha,\"up, then \"\"down\"\"\"\n".as_bytes(), Format::Csv).unwrap();
+        assert_eq!(csv.row(0).unwrap(), ["alpha", "up, then \"down\""]);
+        assert_eq!(args(&["-f", "csv"]).unwrap().format, Format::Csv);
+    }
+
+    #[test]
+    fn test_complete_lines() {
+        assert_eq!(complete_lines("a\nb\npartial", Format::Jsonl), "a\nb\n");
+        assert_eq!(complete_lines("a\n", Format::Jsonl), "a\n");
+        assert_eq!(complete_lines("partial", Format::Jsonl), "");
+        assert_eq!(complete_lines("a\n1,\"two\nlines\"\n2,\"open\n", Format::Csv), "a\n1,\"two\nlines\"\n");
+    }
+
+    #[test]
+    fn test_appends_rows() {
+        assert!(appends_rows(&args(&["-F", "log.csv", "-n", "10"]).unwrap()));
+        assert!(!appends_rows(&args(&["-F", "log.csv", "-s", "Size"]).unwrap()));
+        assert!(!appends_rows(&args(&["-F", "log.csv", "-w", "State=up"]).unwrap()));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{push_justified, text_width, Column, Justification, Table};
+
+/// Splits a markdown table line into trimmed cells, honoring escaped pipes (\|)
+/// and ignoring the optional leading and trailing pipe.
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = if line.ends_with('|') && !line.ends_with("\\|") {
+        &line[..line.len() - 1]
+    } else {
+        line
+    };
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    cells.push(current);
+    cells.into_iter().map(|c| unescape_cell(c.trim())).collect()
+}
+
+/// Converts <br> line breaks back into newlines
+fn unescape_cell(cell: &str) -> String {
+    cell.replace("<br />", "\n").replace("<br/>", "\n").replace("<br>", "\n")
+}
+
+/// Parses a delimiter row (e.g. `|:---|---:|`) into one justification per column.
+/// Returns None if the line is not a valid delimiter row.
+fn parse_delimiter_row(line: &str) -> Option<Vec<Justification>> {
+    if !line.contains('-') {
+        return None;
+    }
+    split_row(line)
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Justification::Center,
+                (false, true) => Justification::Right,
+                _ => Justification::Left,
+            })
+        })
+        .collect()
+}
+
+/// Escapes a cell for a markdown table: pipes are backslash-escaped and
+/// newlines become <br>
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace("\r\n", "\n").replace('\n', "<br>")
+}
+
+/// Returns the delimiter row cell for a justification, as wide as the column
+fn delimiter(justification: Justification, width: usize) -> String {
+    let dashes = |n: usize| "-".repeat(n);
+    match justification {
+        Justification::Left => dashes(width),
+        Justification::Center => format!(":{}:", dashes(width - 2)),
+        Justification::Right | Justification::Decimal => format!("{}:", dashes(width - 1)),
+    }
+}
+
+impl Table {
+    /// Renders the table as a GitHub-flavored markdown pipe table, for pull
+    /// requests and docs. The delimiter row carries each column's alignment
+    /// (`---:` for right-justified and Decimal columns, `:---:` for centered, and
+    /// the one AlignMode::Auto picks), and cells are padded to line up in the
+    /// source. Pipes in cells are escaped and newlines become `<br>`, which
+    /// from_markdown reads back. Headers use the same text as the text renderer.
+    pub fn to_markdown(&self) -> String {
+        let headers: Vec<String> = self.columns.iter().map(|col| escape_cell(&self.header_text(col))).collect();
+        let rows: Vec<Vec<String>> = self.rows
+            .iter()
+            .map(|row| row.cells.iter().enumerate().map(|(idx, cell)| escape_cell(&self.redacted(idx, cell))).collect())
+            .collect();
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(idx, header)| rows.iter().map(|row| text_width(&row[idx])).chain([text_width(header), 3]).max().unwrap_or(3))
+            .collect();
+        let justifications: Vec<Justification> = (0..self.columns.len()).map(|idx| self.cell_align(idx).justification).collect();
+
+        let mut out = String::new();
+        let mut push_line = |cells: &[String], align: bool| {
+            out.push('|');
+            for (idx, cell) in cells.iter().enumerate() {
+                out.push(' ');
+                let justification = if align { justifications[idx] } else { Justification::Left };
+                push_justified(&mut out, cell, widths[idx] - text_width(cell), justification, 0);
+                out.push_str(" |");
+            }
+            out.push('\n');
+        };
+        push_line(&headers, true);
+        let delimiters: Vec<String> = justifications.iter().zip(&widths).map(|(&j, &width)| delimiter(j, width)).collect();
+        push_line(&delimiters, false);
+        for row in &rows {
+            push_line(row, true);
+        }
+        out
+    }
+
+    /// Parses the first GitHub-flavored markdown pipe table found in the text.
+    /// The header row becomes the columns (not truncated), alignment markers in the
+    /// delimiter row set each column's justification (`---:` is Right), and `<br>`
+    /// inside cells becomes a newline. Rows with too few cells are padded with empty
+    /// cells and extra cells are ignored. The table ends at the first line without a pipe.
+    ///
+    /// # Errors
+    /// - If no header and delimiter row pair is found
+    /// - If a header cell is empty
+    /// - If the rows exceed the table limits
+    pub fn from_markdown(text: &str) -> Result<Table, String> {
+        let lines: Vec<&str> = text.lines().collect();
+        let start = lines
+            .windows(2)
+            .position(|pair| {
+                pair[0].contains('|')
+                    && parse_delimiter_row(pair[1]).is_some_and(|d| d.len() == split_row(pair[0]).len())
+            })
+            .ok_or_else(|| "Table::from_markdown: no markdown table found".to_string())?;
+
+        let headers = split_row(lines[start]);
+        let justifications = parse_delimiter_row(lines[start + 1]).unwrap_or_default();
+        let columns = headers
+            .into_iter()
+            .zip(justifications)
+            .map(|(name, j)| Column::new(name, 0, j))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::from_markdown: {}", e))?;
+
+        let mut table = Table::new(columns)?;
+        let column_count = table.columns.len();
+        for line in &lines[start + 2..] {
+            if !line.contains('|') {
+                break;
+            }
+            let mut row = split_row(line);
+            row.resize(column_count, String::new());
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_markdown() {
+        let text = "Some intro text\n\n\
+            | Name | Description | Amount |\n\
+            |:-----|-------------|-------:|\n\
+            | John | Uses a \\| pipe | 10.00 |\n\
+            | Jane | Two<br>lines |\n\
+            \n\
+            Trailing paragraph | with pipe\n";
+        let table = Table::from_markdown(text).unwrap();
+
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["Name", "Description", "Amount"]);
+        assert_eq!(table.columns()[0].justification(), Justification::Left);
+        assert_eq!(table.columns()[2].justification(), Justification::Right);
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(0).unwrap(), ["John", "Uses a | pipe", "10.00"]);
+        assert_eq!(table.row(1).unwrap(), ["Jane", "Two\nlines", ""]);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("State", 0, Justification::Center).unwrap(),
+            Column::new("Amount", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["a|b".to_string(), "ok".to_string(), "10.5".to_string()]).unwrap();
+        table.add_row(vec!["two\nlines".to_string(), "".to_string(), "3".to_string()]).unwrap();
+        let markdown = table.to_markdown();
+        assert_eq!(
+            markdown,
+            "| Name         | State | Amount |\n\
+             | ------------ | :---: | -----: |\n\
+             | a\\|b         |  ok   |   10.5 |\n\
+             | two<br>lines |       |      3 |\n"
+        );
+        let parsed = Table::from_markdown(&markdown).unwrap();
+        assert_eq!(parsed.row(1).unwrap(), ["two\nlines", "", "3"]);
+        assert_eq!(parsed.columns()[2].justification(), Justification::Right);
+    }
+
+    #[test]
+    fn test_from_markdown_without_outer_pipes() {
+        let table = Table::from_markdown("a | b\n--- | :---:\n1 | 2\n").unwrap();
+        assert_eq!(table.row(0).unwrap(), ["1", "2"]);
+        assert_eq!(table.columns()[1].justification(), Justification::Center);
+        assert!(Table::from_markdown("no table here\n| a |\n").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Justification, Table};
+
+/// Escapes a cell for MediaWiki table markup: pipes (and in headers, exclamation
+/// marks) become character references so they don't split cells, and newlines
+/// become <br />
+fn escape_cell(value: &str, header: bool) -> String {
+    let value = value.replace('|', "&#124;").replace("\r\n", "\n").replace('\n', "<br />");
+    if header {
+        value.replace('!', "&#33;")
+    } else {
+        value
+    }
+}
+
+/// Returns the cell attributes aligning a cell, or nothing for left-justified ones
+fn align_attribute(justification: Justification) -> &'static str {
+    match justification {
+        Justification::Left => "",
+        Justification::Center => "style=\"text-align: center;\" | ",
+        Justification::Right | Justification::Decimal => "style=\"text-align: right;\" | ",
+    }
+}
+
+impl Table {
+    /// Renders the table as MediaWiki markup (`{| class="wikitable"` ... `|}`) for
+    /// pasting into a wiki page: a `!` header row, then a `|-` line and a `|` line
+    /// per row. Right-justified, Decimal, and centered cells get a text-align style
+    /// (headers follow the header justification), pipes are escaped, and newlines
+    /// become <br />. Headers use the same text as the text renderer.
+    pub fn to_mediawiki(&self) -> String {
+        let justifications: Vec<Justification> = (0..self.columns.len()).map(|idx| self.cell_align(idx).justification).collect();
+        let mut out = String::from("{| class=\"wikitable\"\n");
+        let headers: Vec<String> = self.columns
+            .iter()
+            .zip(&justifications)
+            .map(|(col, &justification)| {
+                let align = align_attribute(col.header_justification.unwrap_or(justification));
+                format!("{}{}", align, escape_cell(&self.header_text(col), true))
+            })
+            .collect();
+        out.push_str("! ");
+        out.push_str(&headers.join(" !! "));
+        out.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = row.cells
+                .iter()
+                .enumerate()
+                .map(|(idx, cell)| format!("{}{}", align_attribute(justifications[idx]), escape_cell(&self.redacted(idx, cell), false)))
+                .collect();
+            out.push_str("|-\n| ");
+            out.push_str(&cells.join(" || "));
+            out.push('\n');
+        }
+        out.push_str("|}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+
+    #[test]
+    fn test_to_mediawiki() {
+        let mut table = Table::new(vec![
+            Column::new("Host!", 0, Justification::Left).unwrap(),
+            Column::new("Load", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web|1\nprimary".to_string(), "0.5".to_string()]).unwrap();
+        table.add_row(vec!["db!".to_string(), String::new()]).unwrap();
+        let right = "style=\"text-align: right;\" | ";
+        assert_eq!(
+            table.to_mediawiki(),
+            format!(
+                "{{| class=\"wikitable\"\n! Host&#33; !! {right}Load\n\
+                 |-\n| web&#124;1<br />primary || {right}0.5\n|-\n| db! || {right}\n|}}\n"
+            )
+        );
+    }
+}
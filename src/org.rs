@@ -0,0 +1,68 @@
+use crate::{push_justified, text_width, Table};
+
+/// Escapes a cell for an org-mode table, which has no line breaks in cells:
+/// pipes become `\vert{}` and newlines become spaces
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\vert{}").replace("\r\n", "\n").replace('\n', " ")
+}
+
+impl Table {
+    /// Renders the table as an org-mode table: a header row, a `|---+---|` rule,
+    /// and one row per line, padded the way org's table editor aligns them, so it
+    /// can be pasted into an Emacs buffer and edited with C-c C-c. Cells follow the
+    /// column justification. Headers use the same text as the text renderer.
+    pub fn to_org(&self) -> String {
+        let headers: Vec<String> = self.columns.iter().map(|col| escape_cell(&self.header_text(col))).collect();
+        let rows: Vec<Vec<String>> = self.rows
+            .iter()
+            .map(|row| row.cells.iter().enumerate().map(|(idx, cell)| escape_cell(&self.redacted(idx, cell))).collect())
+            .collect();
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(idx, header)| rows.iter().map(|row| text_width(&row[idx])).chain([text_width(header), 1]).max().unwrap_or(1))
+            .collect();
+
+        let push_line = |out: &mut String, cells: &[String], header: bool| {
+            out.push('|');
+            for (idx, cell) in cells.iter().enumerate() {
+                out.push(' ');
+                let justification = if header { self.columns[idx].header_justification() } else { self.cell_align(idx).justification };
+                push_justified(out, cell, widths[idx] - text_width(cell), justification, 0);
+                out.push_str(" |");
+            }
+            out.push('\n');
+        };
+        let mut out = String::new();
+        push_line(&mut out, &headers, true);
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width + 2)).collect();
+        out.push('|');
+        out.push_str(&rule.join("+"));
+        out.push_str("|\n");
+        for row in &rows {
+            push_line(&mut out, row, false);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_org() {
+        let mut table = Table::new(vec![
+            Column::new("Task", 0, Justification::Left).unwrap(),
+            Column::new("Hours", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["write a|b\ndocs".to_string(), "3".to_string()]).unwrap();
+        table.add_row(vec!["review".to_string(), "12".to_string()]).unwrap();
+        assert_eq!(
+            table.to_org(),
+            "| Task                 | Hours |\n|----------------------+-------|\n\
+             | write a\\vert{}b docs |     3 |\n| review               |    12 |\n"
+        );
+    }
+}
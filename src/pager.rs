@@ -0,0 +1,96 @@
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::console::{write_styled, Stream};
+use crate::{text_width, Table};
+
+/// Pager used when $PAGER is unset: -R passes colors through and -S scrolls long
+/// lines sideways instead of wrapping them
+const DEFAULT_PAGER: &str = "less -RS";
+
+/// Size of the terminal as (columns, rows), taken from $COLUMNS and $LINES when
+/// both are set and otherwise asked of the terminal
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    let env = |name| std::env::var(name).ok()?.trim().parse::<usize>().ok().filter(|&n| n > 0);
+    match (env("COLUMNS"), env("LINES")) {
+        (Some(columns), Some(rows)) => Some((columns, rows)),
+        _ => stty_size(),
+    }
+}
+
+#[cfg(unix)]
+fn stty_size() -> Option<(usize, usize)> {
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let output = Command::new("stty").arg("size").stdin(tty).stderr(Stdio::null()).output().ok()?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    let mut sizes = text.split_whitespace().map(|n| n.parse::<usize>().ok());
+    match (sizes.next()??, sizes.next()??) {
+        (0, _) | (_, 0) => None,
+        (rows, columns) => Some((columns, rows)),
+    }
+}
+
+#[cfg(not(unix))]
+fn stty_size() -> Option<(usize, usize)> {
+    None
+}
+
+/// Whether text can't be shown at once on a terminal of the given size. The last
+/// row is left for the prompt.
+fn exceeds(text: &str, (columns, rows): (usize, usize)) -> bool {
+    text.lines().count() >= rows || text.lines().any(|line| text_width(line) > columns)
+}
+
+/// Writes text to stdout, through the pager when stdout is a terminal the text
+/// doesn't fit on. Falls back to writing directly if the pager can't be started.
+pub(crate) fn page(text: &str) -> io::Result<()> {
+    let stdout = io::stdout();
+    if stdout.is_terminal() && terminal_size().is_some_and(|size| exceeds(text, size)) {
+        if let Some(result) = run_pager(text) {
+            return result;
+        }
+    }
+    write_styled(Stream::Stdout, text)
+}
+
+/// Runs $PAGER (or DEFAULT_PAGER) with text on its input, returning None if it
+/// couldn't be started
+fn run_pager(text: &str) -> Option<io::Result<()>> {
+    let command = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut words = command.split_whitespace();
+    let mut child = Command::new(words.next()?).args(words).stdin(Stdio::piped()).spawn().ok()?;
+    // Closing the pipe when the write finishes lets the pager see the end of input
+    let written = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(text.as_bytes()));
+    Some(match written {
+        // The user quit the pager before reading everything
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+        _ => child.wait().map(|_| ()),
+    })
+}
+
+impl Table {
+    /// Prints the table to stdout. When stdout is a terminal and the table is taller
+    /// or wider than it, the output goes through $PAGER (default "less -RS") so
+    /// long tables don't scroll away. Colors are left out as by print; the width
+    /// is not fitted, since the pager scrolls long lines sideways.
+    pub fn print_paged(&self) -> io::Result<()> {
+        page(&self.render_for(Stream::Stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeds() {
+        let text = "a b\n= =\n1 2\n";
+        assert!(!exceeds(text, (80, 24)));
+        assert!(exceeds(text, (80, 3)));
+        assert!(exceeds(text, (2, 24)));
+        assert!(!exceeds(text, (3, 4)));
+    }
+}
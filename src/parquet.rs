@@ -0,0 +1,255 @@
+//! Minimal Parquet writer: one row group, one uncompressed PLAIN-encoded data page
+//! per column, every column a required UTF-8 string (BYTE_ARRAY). File metadata is
+//! encoded with the Thrift compact protocol as the format requires.
+
+use std::io::Write;
+
+use crate::{OutputFormat, Table};
+
+const MAGIC: &[u8] = b"PAR1";
+
+// Parquet enum values
+const TYPE_BYTE_ARRAY: i32 = 6;
+const REPETITION_REQUIRED: i32 = 0;
+const CONVERTED_TYPE_UTF8: i32 = 0;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_TYPE_DATA: i32 = 0;
+
+// Thrift compact protocol type ids
+const CT_I32: u8 = 5;
+const CT_I64: u8 = 6;
+const CT_BINARY: u8 = 8;
+const CT_LIST: u8 = 9;
+const CT_STRUCT: u8 = 12;
+
+/// Thrift compact protocol encoder for the handful of structures Parquet needs
+struct Compact {
+    buf: Vec<u8>,
+    last_field: Vec<i16>,
+}
+
+impl Compact {
+    fn new() -> Self {
+        Compact { buf: Vec::new(), last_field: vec![0] }
+    }
+
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+
+    fn field_header(&mut self, id: i16, ty: u8) {
+        let last = self.last_field.last_mut().expect("struct stack is never empty");
+        let delta = id - *last;
+        *last = id;
+        if delta > 0 && delta <= 15 {
+            self.buf.push(((delta as u8) << 4) | ty);
+        } else {
+            self.buf.push(ty);
+            self.varint(((id << 1) ^ (id >> 15)) as u16 as u64);
+        }
+    }
+
+    fn i32_field(&mut self, id: i16, v: i32) {
+        self.field_header(id, CT_I32);
+        self.varint(((v << 1) ^ (v >> 31)) as u32 as u64);
+    }
+
+    fn i64_field(&mut self, id: i16, v: i64) {
+        self.field_header(id, CT_I64);
+        self.varint(((v << 1) ^ (v >> 63)) as u64);
+    }
+
+    fn binary(&mut self, bytes: &[u8]) {
+        self.varint(bytes.len() as u64);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn string_field(&mut self, id: i16, s: &str) {
+        self.field_header(id, CT_BINARY);
+        self.binary(s.as_bytes());
+    }
+
+    fn list_header(&mut self, id: i16, elem_type: u8, len: usize) {
+        self.field_header(id, CT_LIST);
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | elem_type);
+        } else {
+            self.buf.push(0xF0 | elem_type);
+            self.varint(len as u64);
+        }
+    }
+
+    fn i32_list_field(&mut self, id: i16, values: &[i32]) {
+        self.list_header(id, CT_I32, values.len());
+        for &v in values {
+            self.varint(((v << 1) ^ (v >> 31)) as u32 as u64);
+        }
+    }
+
+    fn struct_field(&mut self, id: i16) {
+        self.field_header(id, CT_STRUCT);
+        self.begin_struct();
+    }
+
+    /// Starts a struct that is a list element (no field header)
+    fn begin_struct(&mut self) {
+        self.last_field.push(0);
+    }
+
+    fn end_struct(&mut self) {
+        self.buf.push(0);
+        self.last_field.pop();
+    }
+}
+
+/// Location and sizes of a written column chunk, needed for the footer
+struct ChunkInfo {
+    offset: u64,
+    size: u64,
+}
+
+impl Table {
+    /// Writes the table as a Parquet file with one required UTF-8 string column
+    /// per table column (named by each column's Parquet alias or name).
+    /// Data is stored in a single uncompressed row group.
+    pub fn to_parquet<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let to_io = |e: String| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        let num_rows = self.rows.len() as i64;
+
+        writer.write_all(MAGIC)?;
+        let mut offset = MAGIC.len() as u64;
+        let mut chunks = Vec::with_capacity(self.columns.len());
+
+        for idx in 0..self.columns.len() {
+            let mut data = Vec::new();
+            for row in &self.rows {
+                let value = self.redacted(idx, &row.cells[idx]);
+                let value = value.as_bytes();
+                let len = u32::try_from(value.len())
+                    .map_err(|_| to_io(format!("Table::to_parquet: cell in row too large ({} bytes)", value.len())))?;
+                data.extend_from_slice(&len.to_le_bytes());
+                data.extend_from_slice(value);
+            }
+            let page_size = i32::try_from(data.len())
+                .map_err(|_| to_io(format!("Table::to_parquet: column {} exceeds the maximum page size", idx)))?;
+
+            let mut header = Compact::new();
+            header.i32_field(1, PAGE_TYPE_DATA);
+            header.i32_field(2, page_size);
+            header.i32_field(3, page_size);
+            header.struct_field(5);
+            header.i32_field(1, num_rows as i32);
+            header.i32_field(2, ENCODING_PLAIN);
+            header.i32_field(3, ENCODING_RLE);
+            header.i32_field(4, ENCODING_RLE);
+            header.end_struct();
+            header.buf.push(0);
+
+            writer.write_all(&header.buf)?;
+            writer.write_all(&data)?;
+            let size = (header.buf.len() + data.len()) as u64;
+            chunks.push(ChunkInfo { offset, size });
+            offset += size;
+        }
+
+        let mut meta = Compact::new();
+        meta.i32_field(1, 1);
+
+        // Schema: a root group followed by one leaf per column
+        meta.list_header(2, CT_STRUCT, self.columns.len() + 1);
+        meta.begin_struct();
+        meta.string_field(4, "schema");
+        meta.i32_field(5, self.columns.len() as i32);
+        meta.end_struct();
+        for col in &self.columns {
+            meta.begin_struct();
+            meta.i32_field(1, TYPE_BYTE_ARRAY);
+            meta.i32_field(3, REPETITION_REQUIRED);
+            meta.string_field(4, col.name_for(OutputFormat::Parquet));
+            meta.i32_field(6, CONVERTED_TYPE_UTF8);
+            meta.end_struct();
+        }
+
+        meta.i64_field(3, num_rows);
+
+        // A single row group holding every column chunk
+        let total_size: u64 = chunks.iter().map(|c| c.size).sum();
+        meta.list_header(4, CT_STRUCT, 1);
+        meta.begin_struct();
+        meta.list_header(1, CT_STRUCT, chunks.len());
+        for (col, chunk) in self.columns.iter().zip(&chunks) {
+            meta.begin_struct();
+            meta.i64_field(2, chunk.offset as i64);
+            meta.struct_field(3);
+            meta.i32_field(1, TYPE_BYTE_ARRAY);
+            meta.i32_list_field(2, &[ENCODING_PLAIN, ENCODING_RLE]);
+            meta.list_header(3, CT_BINARY, 1);
+            meta.binary(col.name_for(OutputFormat::Parquet).as_bytes());
+            meta.i32_field(4, CODEC_UNCOMPRESSED);
+            meta.i64_field(5, num_rows);
+            meta.i64_field(6, chunk.size as i64);
+            meta.i64_field(7, chunk.size as i64);
+            meta.i64_field(9, chunk.offset as i64);
+            meta.end_struct();
+            meta.end_struct();
+        }
+        meta.i64_field(2, total_size as i64);
+        meta.i64_field(3, num_rows);
+        meta.end_struct();
+
+        meta.string_field(6, concat!("ctable version ", env!("CARGO_PKG_VERSION")));
+        meta.buf.push(0);
+
+        writer.write_all(&meta.buf)?;
+        writer.write_all(&(meta.buf.len() as u32).to_le_bytes())?;
+        writer.write_all(MAGIC)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_compact_encoding() {
+        let mut c = Compact::new();
+        c.i32_field(1, 1);
+        c.i32_field(3, -2);
+        c.i64_field(20, 300);
+        c.end_struct();
+        assert_eq!(c.buf, [0x15, 0x02, 0x25, 0x03, 0x06, 0x28, 0xD8, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn test_to_parquet_layout() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Qty", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["Widget".to_string(), "3".to_string()]).unwrap();
+        table.add_row(vec!["Gadget".to_string(), "10".to_string()]).unwrap();
+
+        let mut out = Vec::new();
+        table.to_parquet(&mut out).unwrap();
+
+        assert_eq!(&out[..4], MAGIC);
+        assert_eq!(&out[out.len() - 4..], MAGIC);
+        let footer_len = u32::from_le_bytes(out[out.len() - 8..out.len() - 4].try_into().unwrap()) as usize;
+        let footer = &out[out.len() - 8 - footer_len..out.len() - 8];
+        assert_eq!(footer[0], 0x15, "footer starts with the version field");
+        assert_eq!(*footer.last().unwrap(), 0, "footer ends with a struct stop");
+
+        // Values are stored PLAIN: 4-byte little-endian length followed by the bytes
+        let needle = [&6u32.to_le_bytes()[..], b"Widget"].concat();
+        assert!(out.windows(needle.len()).any(|w| w == needle.as_slice()));
+        assert!(out.windows(3).any(|w| w == b"Qty"));
+    }
+}
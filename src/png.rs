@@ -0,0 +1,471 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::width::{char_width, visible_chars};
+use crate::Table;
+
+/// Appearance settings for Table::to_png. Colors are RGB triples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngOptions {
+    /// Size of one font pixel in image pixels; glyphs are 5x7 font pixels in a 6x10 cell
+    pub scale: u32,
+    /// Space around the table in image pixels
+    pub padding: u32,
+    pub background: [u8; 3],
+    pub text_color: [u8; 3],
+    /// Background behind the header row
+    pub header_background: Option<[u8; 3]>,
+    /// Background for every other data row
+    pub stripe_background: Option<[u8; 3]>,
+    /// Color of the rule under the header
+    pub rule_color: [u8; 3],
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            scale: 2,
+            padding: 8,
+            background: [0xff, 0xff, 0xff],
+            text_color: [0x00, 0x00, 0x00],
+            header_background: Some([0xf0, 0xf0, 0xf0]),
+            stripe_background: Some([0xf7, 0xf7, 0xf7]),
+            rule_color: [0x88, 0x88, 0x88],
+        }
+    }
+}
+
+/// Largest width or height write_png draws, in pixels
+const MAX_DIMENSION: u32 = 65_535;
+/// Largest number of pixels write_png draws (about 300 MB of RGB)
+const MAX_PIXELS: u64 = 100_000_000;
+
+const GLYPH_WIDTH: u32 = 5;
+const CELL_WIDTH: u32 = 6;
+const CELL_HEIGHT: u32 = 10;
+
+/// 5x7 bitmaps for printable ASCII (' ' through '~'), one byte per row from the
+/// top with the leftmost pixel in bit 4. Other characters are drawn as '?'.
+const FONT: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // '!'
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // '#'
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // '$'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // '%'
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // '&'
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // "'"
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // '('
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // ')'
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // '*'
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ','
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // '.'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // '/'
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // '0'
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // '1'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // '2'
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // '3'
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // '4'
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // '5'
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // '6'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // '7'
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // '8'
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ';'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // '<'
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // '='
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // '>'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // '@'
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'A'
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // 'B'
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // 'C'
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // 'D'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // 'E'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // 'F'
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // 'G'
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'H'
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'I'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // 'J'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // 'K'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // 'L'
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // 'M'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // 'N'
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'O'
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // 'P'
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // 'Q'
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // 'R'
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // 'S'
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // 'T'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'V'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // 'W'
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // 'X'
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // 'Y'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // 'Z'
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // '['
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // '\\'
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ']'
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // '_'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // 'a'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // 'b'
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // 'c'
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // 'd'
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // 'e'
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // 'f'
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'g'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // 'h'
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // 'i'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // 'j'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // 'k'
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'l'
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // 'm'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // 'n'
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // 'o'
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // 'p'
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // 'q'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // 'r'
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // 's'
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // 't'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // 'w'
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // 'x'
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'y'
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // 'z'
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // '{'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // '|'
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // '}'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // '~'
+];
+
+fn glyph(c: char) -> &'static [u8; 7] {
+    match c {
+        ' '..='~' => &FONT[c as usize - 0x20],
+        _ => &FONT['?' as usize - 0x20],
+    }
+}
+
+/// Parses "#rrggbb" or "#rgb" metadata colors
+fn parse_color(s: &str) -> Option<[u8; 3]> {
+    let hex = s.trim().strip_prefix('#')?;
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize, len: usize| u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok();
+    match hex.len() {
+        6 => Some([channel(0, 2)?, channel(1, 2)?, channel(2, 2)?]),
+        3 => Some([channel(0, 1)? * 17, channel(1, 1)? * 17, channel(2, 1)? * 17]),
+        _ => None,
+    }
+}
+
+/// An RGB image being drawn
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, color: [u8; 3]) -> Canvas {
+        let pixels = color.repeat(width as usize * height as usize);
+        Canvas { width, height, pixels }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 3]) {
+        let x_end = x.saturating_add(w).min(self.width) as usize;
+        let x_start = (x as usize).min(x_end);
+        for row in y..y.saturating_add(h).min(self.height) {
+            let line = row as usize * self.width as usize;
+            let (start, end) = ((line + x_start) * 3, (line + x_end) * 3);
+            for pixel in self.pixels[start..end].chunks_exact_mut(3) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draws a line of text with its top-left character cell at (x, y).
+    /// Characters take as many cells as their terminal width, so columns line up
+    /// as in the text render: a wide character is drawn in the first of its two
+    /// cells, zero-width characters are skipped, and so are ANSI escape sequences.
+    /// Bold text is drawn twice, one image pixel apart.
+    fn draw_text(&mut self, x: u32, y: u32, text: &str, scale: u32, color: [u8; 3], bold: bool) {
+        let mut cells = 0;
+        for (_, c) in visible_chars(text) {
+            let width = char_width(c);
+            if width == 0 {
+                continue;
+            }
+            let cell_x = x + cells * CELL_WIDTH * scale;
+            cells += width as u32;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        let px = cell_x + col * scale;
+                        let py = y + (row as u32 + 1) * scale;
+                        self.fill_rect(px, py, scale + u32::from(bold), scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_png<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // 8-bit RGB, deflate, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_chunk(&mut writer, b"IHDR", &header)?;
+
+        // Each scanline is preceded by filter type 0 (none)
+        let stride = self.width as usize * 3;
+        let mut raw = Vec::with_capacity((stride + 1) * self.height as usize);
+        for line in self.pixels.chunks_exact(stride.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(line);
+        }
+        write_chunk(&mut writer, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut writer, b"IEND", &[])
+    }
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "PNG chunk too large"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    let crc = crc32(&[kind.as_slice(), data]);
+    writer.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps data in a zlib stream of uncompressed deflate blocks. Table images are
+/// mostly flat color, but keeping the encoder trivial matters more than size here.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = 0xffff;
+    let mut out = Vec::with_capacity(data.len() + data.len() / BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+fn crc32(parts: &[&[u8]]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    };
+    let mut crc = !0u32;
+    for &byte in parts.iter().flat_map(|p| p.iter()) {
+        crc = TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+impl Table {
+    /// Renders the table to a PNG file using a built-in 5x7 bitmap font, laid out
+    /// like the text renderer. See write_png for details.
+    ///
+    /// # Errors
+    /// - If the file cannot be created or written
+    /// - If the image would be too large
+    pub fn to_png<P: AsRef<Path>>(&self, path: P, options: &PngOptions) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_png(&mut writer, options)?;
+        writer.flush()
+    }
+
+    /// Writes the table as a PNG image, laid out with the same widths, justification,
+    /// and truncation as the text renderer, with a bold header and a rule below it.
+    /// Only the header and the rows are drawn: the title, units row, sections,
+    /// footer, border, and legend of the text render are left out. Characters
+    /// outside printable ASCII are drawn as '?' (wide ones still take two columns)
+    /// and terminal colors are dropped. A "background" entry in
+    /// row or cell metadata (cell wins) in "#rrggbb" or "#rgb" form fills that row
+    /// or cell; otherwise rows alternate with the stripe color.
+    ///
+    /// # Errors
+    /// - If writing fails
+    /// - If the image would be wider or taller than 65,535 pixels, or have more
+    ///   than 100 million pixels
+    pub fn write_png<W: Write>(&self, writer: W, options: &PngOptions) -> io::Result<()> {
+        let layout = self.layout();
+        let scale = options.scale.max(1);
+        let pad = options.padding;
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "Table::write_png: image too large");
+
+        let formatted: Vec<Vec<Vec<String>>> = self.rows
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| self.cell_lines(&layout, idx, value))
+                    .collect()
+            })
+            .collect();
+        let header_rows = self.header_rows(&layout);
+        let text_lines: usize = header_rows.len() + formatted
+            .iter()
+            .map(|cells| cells.iter().map(Vec::len).max().unwrap_or(1))
+            .sum::<usize>();
+
+        let char_w = CELL_WIDTH * scale;
+        let line_h = CELL_HEIGHT * scale;
+        let table_w = u32::try_from(layout.content_len()).ok().and_then(|n| n.checked_mul(char_w)).ok_or_else(too_large)?;
+        let table_h = u32::try_from(text_lines).ok().and_then(|n| n.checked_mul(line_h)).ok_or_else(too_large)?;
+        let padded = |size: u32| pad.checked_mul(2).and_then(|both| size.checked_add(both)).filter(|&n| n > 0 && n <= MAX_DIMENSION);
+        let width = padded(table_w).ok_or_else(too_large)?;
+        let height = padded(table_h).ok_or_else(too_large)?;
+        if u64::from(width) * u64::from(height) > MAX_PIXELS {
+            return Err(too_large());
+        }
+        let mut canvas = Canvas::new(width, height, options.background);
+
+        // Left edge of each column in pixels (columns are separated by one space)
+        let mut xs = Vec::with_capacity(layout.widths.len());
+        let mut chars = 0;
+        for &width in &layout.widths {
+            xs.push(pad + chars as u32 * char_w);
+            chars += width + 1;
+        }
+        let mut y = pad;
+
+        // Header
+        if let Some(bg) = options.header_background {
+            canvas.fill_rect(pad, y, table_w, line_h * header_rows.len() as u32, bg);
+        }
+        for header in &header_rows {
+            for (text, &x) in header.iter().zip(&xs) {
+                canvas.draw_text(x, y, text, scale, options.text_color, true);
+            }
+            y += line_h;
+        }
+        canvas.fill_rect(pad, y - scale, table_w, scale, options.rule_color);
+
+        // Rows
+        for (row_idx, (row, cells)) in self.rows.iter().zip(&formatted).enumerate() {
+            let row_h = cells.iter().map(Vec::len).max().unwrap_or(1) as u32 * line_h;
+            let row_bg = row.metadata.get("background").and_then(|c| parse_color(c))
+                .or(if row_idx % 2 == 1 { options.stripe_background } else { None });
+            if let Some(bg) = row_bg {
+                canvas.fill_rect(pad, y, table_w, row_h, bg);
+            }
+            for (col_idx, ((cell, &x), &width)) in cells.iter().zip(&xs).zip(&layout.widths).enumerate() {
+                if let Some(bg) = row.cell_metadata.get(&col_idx).and_then(|m| m.get("background")).and_then(|c| parse_color(c)) {
+                    canvas.fill_rect(x, y, width as u32 * char_w, row_h, bg);
+                }
+                for (line_idx, line) in cell.iter().enumerate() {
+                    canvas.draw_text(x, y + line_idx as u32 * line_h, line, scale, options.text_color, false);
+                }
+            }
+            y += row_h;
+        }
+
+        canvas.write_png(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_write_png() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Load", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["alpha".to_string(), "12".to_string()]).unwrap();
+        table.add_row(vec!["two\nlines".to_string(), "7".to_string()]).unwrap();
+        table.set_cell_metadata(0, 1, "background", "#ff0000").unwrap();
+
+        let options = PngOptions { scale: 1, padding: 2, ..PngOptions::default() };
+        let mut png = Vec::new();
+        table.write_png(&mut png, &options).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+        assert_eq!(&png[12..16], b"IHDR");
+        // "alpha   12" is 10 characters; header plus three text lines
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 10 * CELL_WIDTH + 4);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 4 * CELL_HEIGHT + 4);
+        let ihdr_crc = u32::from_be_bytes(png[29..33].try_into().unwrap());
+        assert_eq!(ihdr_crc, crc32(&[&png[12..29]]));
+        // The highlighted cell's color appears in the uncompressed image data
+        assert!(png.windows(3).any(|w| w == [0xff, 0, 0]));
+
+        // Too wide, and within the dimension limit but over the pixel limit
+        let mut wide = Table::with_headers(["Text"]).unwrap();
+        wide.add_row(vec!["x".repeat(12_000)]).unwrap();
+        let err = wide.write_png(&mut Vec::new(), &options).unwrap_err();
+        assert_eq!(err.to_string(), "Table::write_png: image too large");
+        let mut big = Table::with_headers(["Text"]).unwrap();
+        big.add_row(vec!["x".repeat(100)]).unwrap();
+        let huge = PngOptions { scale: 100, ..PngOptions::default() };
+        assert!(big.write_png(&mut Vec::new(), &huge).is_err());
+        assert!(big.write_png(&mut Vec::new(), &PngOptions { padding: u32::MAX, ..options }).is_err());
+    }
+
+    #[test]
+    fn test_png_helpers() {
+        assert_eq!(crc32(&[b"123456789"]), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(parse_color("#0a0B0c"), Some([10, 11, 12]));
+        assert_eq!(parse_color("#f00"), Some([255, 0, 0]));
+        assert_eq!(parse_color("red"), None);
+        assert_eq!(glyph('é'), glyph('?'));
+
+        // Wide, zero-width, and escaped text keeps the later characters in their columns
+        let draw = |text: &str| {
+            let mut canvas = Canvas::new(4 * CELL_WIDTH, CELL_HEIGHT, [255, 255, 255]);
+            canvas.draw_text(0, 0, text, 1, [0, 0, 0], false);
+            canvas.pixels
+        };
+        assert_eq!(draw("\x1b[31m日x\x1b[0m"), draw("? x"));
+        assert_eq!(draw("e\u{301}x"), draw("ex"));
+    }
+}
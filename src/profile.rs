@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+
+use crate::{toml, BorderStyle, HeaderTransform, LineEnding, Padding, RenderOptions, Table};
+
+/// Profile names of the header transforms
+const HEADER_TRANSFORMS: [(HeaderTransform, &str); 5] = [
+    (HeaderTransform::None, "none"),
+    (HeaderTransform::Uppercase, "uppercase"),
+    (HeaderTransform::Lowercase, "lowercase"),
+    (HeaderTransform::TitleCase, "title_case"),
+    (HeaderTransform::SnakeToTitle, "snake_to_title"),
+];
+
+/// Profile names of the line endings
+const LINE_ENDINGS: [(LineEnding, &str); 2] = [(LineEnding::Lf, "lf"), (LineEnding::CrLf, "crlf")];
+
+/// Profile names of the border styles
+const BORDER_STYLES: [(BorderStyle, &str); 6] = [
+    (BorderStyle::None, "none"),
+    (BorderStyle::Unicode, "unicode"),
+    (BorderStyle::Ascii, "ascii"),
+    (BorderStyle::Rounded, "rounded"),
+    (BorderStyle::Double, "double"),
+    (BorderStyle::Heavy, "heavy"),
+];
+
+/// Looks up a value by its profile name, ignoring case
+fn lookup<T: Copy>(names: &[(T, &str)], key: &str, value: &str) -> Result<T, String> {
+    names
+        .iter()
+        .find(|(_, name)| value.eq_ignore_ascii_case(name))
+        .map(|(item, _)| *item)
+        .ok_or_else(|| format!("unknown {} '{}'", key, value))
+}
+
+/// Returns the profile name of a value
+fn name_of<T: PartialEq>(names: &[(T, &'static str)], item: &T) -> &'static str {
+    names.iter().find(|(t, _)| t == item).map_or("", |(_, name)| name)
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    value.parse().map_err(|_| format!("{} must be true or false, not '{}'", key, value))
+}
+
+impl RenderOptions {
+    /// Writes the options as a TOML profile with one key per option, e.g.
+    /// `header_transform = "uppercase"` and `show_legend = true` (max_width is
+    /// left out when unset, border when it is a custom character set, and the
+    /// padding keys when the border's own padding is used)
+    pub fn to_profile(&self) -> String {
+        let quoted = |name: &str| format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut entries = vec![
+            ("header_transform", quoted(name_of(&HEADER_TRANSFORMS, &self.header_transform))),
+            ("trim_trailing_padding", self.trim_trailing_padding.to_string()),
+            ("show_legend", self.show_legend.to_string()),
+            ("show_scale_legend", self.show_scale_legend.to_string()),
+            ("no_color", self.no_color.to_string()),
+            ("line_ending", quoted(name_of(&LINE_ENDINGS, &self.line_ending))),
+            ("omit_final_line_ending", self.omit_final_line_ending.to_string()),
+            ("border_top", self.border_lines.top.to_string()),
+            ("border_header", self.border_lines.header.to_string()),
+            ("border_bottom", self.border_lines.bottom.to_string()),
+            ("border_sides", self.border_lines.sides.to_string()),
+            ("border_columns", self.border_lines.columns.to_string()),
+            ("row_separators", self.row_separators.to_string()),
+            ("blank_line_between_rows", self.blank_line_between_rows.to_string()),
+        ];
+        if let Some(width) = self.max_width {
+            entries.push(("max_width", width.to_string()));
+        }
+        if !matches!(self.border, BorderStyle::Custom(_)) {
+            entries.push(("border", quoted(name_of(&BORDER_STYLES, &self.border))));
+        }
+        if let Some(separator) = &self.column_separator {
+            entries.push(("column_separator", quoted(separator)));
+        }
+        if let Some(c) = self.header_rule_char {
+            entries.push(("header_rule_char", quoted(&c.to_string())));
+        }
+        if let Some(padding) = self.padding {
+            entries.push(("padding_left", padding.left.to_string()));
+            entries.push(("padding_right", padding.right.to_string()));
+        }
+        entries.iter().map(|(key, value)| format!("{} = {}\n", key, value)).collect()
+    }
+
+    /// Reads options from a TOML profile (see to_profile). Options the profile
+    /// leaves out keep their defaults.
+    ///
+    /// # Errors
+    /// - If the profile is not valid TOML, has an unknown key, or has an invalid value
+    pub fn from_profile(text: &str) -> Result<Self, String> {
+        let mut options = RenderOptions::default();
+        options.apply(text).map_err(|e| format!("RenderOptions::from_profile: {}", e))?;
+        Ok(options)
+    }
+
+    /// Overrides these options with the ones a TOML profile sets, leaving the
+    /// rest as they are. Use it to layer a user's profile over a shared one.
+    ///
+    /// # Errors
+    /// - If the profile is not valid TOML, has an unknown key, or has an invalid value
+    pub fn apply_profile(&mut self, text: &str) -> Result<(), String> {
+        self.apply(text).map_err(|e| format!("RenderOptions::apply_profile: {}", e))
+    }
+
+    fn apply(&mut self, text: &str) -> Result<(), String> {
+        // Validate every key before changing anything
+        let mut options = self.clone();
+        for (key, value) in toml::key_values(text)? {
+            match key.as_str() {
+                "header_transform" => options.header_transform = lookup(&HEADER_TRANSFORMS, &key, &value)?,
+                "trim_trailing_padding" => options.trim_trailing_padding = parse_bool(&key, &value)?,
+                "show_legend" => options.show_legend = parse_bool(&key, &value)?,
+                "show_scale_legend" => options.show_scale_legend = parse_bool(&key, &value)?,
+                "no_color" => options.no_color = parse_bool(&key, &value)?,
+                "line_ending" => options.line_ending = lookup(&LINE_ENDINGS, &key, &value)?,
+                "omit_final_line_ending" => options.omit_final_line_ending = parse_bool(&key, &value)?,
+                "max_width" => {
+                    let width = value.parse().map_err(|_| format!("max_width must be a whole number, not '{}'", value))?;
+                    options.max_width = Some(width);
+                }
+                "border" => options.border = lookup(&BORDER_STYLES, &key, &value)?,
+                "border_top" => options.border_lines.top = parse_bool(&key, &value)?,
+                "border_header" => options.border_lines.header = parse_bool(&key, &value)?,
+                "border_bottom" => options.border_lines.bottom = parse_bool(&key, &value)?,
+                "border_sides" => options.border_lines.sides = parse_bool(&key, &value)?,
+                "border_columns" => options.border_lines.columns = parse_bool(&key, &value)?,
+                "row_separators" => {
+                    options.row_separators =
+                        value.parse().map_err(|_| format!("row_separators must be a whole number, not '{}'", value))?;
+                }
+                "blank_line_between_rows" => options.blank_line_between_rows = parse_bool(&key, &value)?,
+                "column_separator" => options.column_separator = Some(value),
+                "header_rule_char" => {
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => options.header_rule_char = Some(c),
+                        _ => return Err(format!("header_rule_char must be a single character, not '{}'", value)),
+                    }
+                }
+                "padding_left" | "padding_right" => {
+                    let spaces = value.parse().map_err(|_| format!("{} must be a whole number, not '{}'", key, value))?;
+                    let padding = options.padding.get_or_insert_with(Padding::default);
+                    if key == "padding_left" {
+                        padding.left = spaces;
+                    } else {
+                        padding.right = spaces;
+                    }
+                }
+                _ => return Err(format!("unknown key '{}'", key)),
+            }
+        }
+        *self = options;
+        Ok(())
+    }
+
+    /// Loads options from a profile file
+    ///
+    /// # Errors
+    /// - If the file can't be read or is not a valid profile
+    pub fn load_profile(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("RenderOptions::load_profile: {}: {}", path.display(), e))?;
+        RenderOptions::from_profile(&text).map_err(|e| format!("{} ({})", e, path.display()))
+    }
+
+    /// Saves the options as a profile file, replacing any existing file
+    ///
+    /// # Errors
+    /// - If the file can't be written
+    pub fn save_profile(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_profile())
+            .map_err(|e| format!("RenderOptions::save_profile: {}: {}", path.display(), e))
+    }
+
+    /// Returns the path of the user's profile: ctable/profile.toml in
+    /// $XDG_CONFIG_HOME, or in ~/.config when that isn't set. The file need not exist.
+    pub fn user_profile_path() -> Option<PathBuf> {
+        let config = match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME").filter(|dir| !dir.is_empty())?).join(".config"),
+        };
+        Some(config.join("ctable").join("profile.toml"))
+    }
+
+    /// Overrides these options with the user's profile (see user_profile_path),
+    /// returning whether one was found
+    ///
+    /// # Errors
+    /// - If the profile exists but can't be read or is not a valid profile
+    pub fn apply_user_profile(&mut self) -> Result<bool, String> {
+        let Some(path) = RenderOptions::user_profile_path().filter(|path| path.is_file()) else {
+            return Ok(false);
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("RenderOptions::apply_user_profile: {}: {}", path.display(), e))?;
+        self.apply(&text)
+            .map_err(|e| format!("RenderOptions::apply_user_profile: {}: {}", path.display(), e))?;
+        Ok(true)
+    }
+}
+
+impl Table {
+    /// Replaces the render options with those of a profile file
+    ///
+    /// # Errors
+    /// - If the file can't be read or is not a valid profile
+    pub fn load_profile(&mut self, path: impl AsRef<Path>) -> Result<(), String> {
+        self.options = RenderOptions::load_profile(path)?;
+        Ok(())
+    }
+
+    /// Saves the render options as a profile file
+    ///
+    /// # Errors
+    /// - If the file can't be written
+    pub fn save_profile(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        self.options.save_profile(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BorderLines;
+
+    #[test]
+    fn test_profile() {
+        let options = RenderOptions {
+            header_transform: HeaderTransform::SnakeToTitle,
+            show_legend: true,
+            line_ending: LineEnding::CrLf,
+            max_width: Some(100),
+            border: BorderStyle::Unicode,
+            border_lines: BorderLines { top: false, ..Default::default() },
+            row_separators: 3,
+            padding: Some(Padding { left: 2, right: 0 }),
+            column_separator: Some(" \"|\" ".to_string()),
+            header_rule_char: Some('─'),
+            blank_line_between_rows: true,
+            ..Default::default()
+        };
+        let text = options.to_profile();
+        assert!(text.starts_with("header_transform = \"snake_to_title\"\n"));
+        assert_eq!(RenderOptions::from_profile(&text).unwrap(), options);
+
+        let mut layered = options.clone();
+        layered.apply_profile("# personal overrides\nno_color = true\n").unwrap();
+        assert_eq!(layered, RenderOptions { no_color: true, ..options });
+
+        assert_eq!(
+            layered.apply_profile("no_color = false\nshow_legnd = true\n").unwrap_err(),
+            "RenderOptions::apply_profile: unknown key 'show_legnd'"
+        );
+        assert!(layered.no_color);
+        assert!(RenderOptions::from_profile("show_legend = 1\n").is_err());
+        assert!(layered.apply_profile(&format!("no_color = {}", "[".repeat(100_000))).unwrap_err().contains("nested"));
+
+        let path = std::env::temp_dir().join(format!("ctable-profile-{}.toml", std::process::id()));
+        let mut table = Table::with_headers(["a"]).unwrap();
+        table.set_render_options(layered.clone());
+        table.save_profile(&path).unwrap();
+        table.set_render_options(RenderOptions::default());
+        table.load_profile(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(table.render_options(), &layered);
+    }
+}
@@ -0,0 +1,126 @@
+use std::borrow::Cow;
+
+use crate::regex::Regex;
+use crate::{Column, Table};
+
+/// Pattern matching email addresses, for use with add_redaction
+pub const EMAIL_PATTERN: &str = r"[\w.+\-]+@[\w\-]+(?:\.[\w\-]+)+";
+
+/// Pattern matching IPv4 addresses, for use with add_redaction
+pub const IPV4_PATTERN: &str = r"\b\d{1,3}(?:\.\d{1,3}){3}\b";
+
+/// Pattern matching long unbroken runs of letters, digits, '_' and '-' (32 or
+/// more) as found in API keys and access tokens, for use with add_redaction
+pub const TOKEN_PATTERN: &str = r"\b[\w\-]{32,}";
+
+/// A pattern whose matches are replaced by a mask when values are rendered
+#[derive(Debug, Clone)]
+pub(crate) struct Redaction {
+    pattern: Regex,
+    mask: String,
+}
+
+impl Redaction {
+    fn new(method: &str, pattern: &str, mask: String) -> Result<Self, String> {
+        let pattern = Regex::new(pattern).map_err(|e| format!("{}: invalid pattern '{}': {}", method, pattern, e))?;
+        Ok(Redaction { pattern, mask })
+    }
+}
+
+/// Applies each redaction in turn, borrowing the value when nothing matches
+pub(crate) fn redact<'a, 'r>(value: &'a str, redactions: impl IntoIterator<Item = &'r Redaction>) -> Cow<'a, str> {
+    let mut value = Cow::Borrowed(value);
+    for redaction in redactions {
+        if let Cow::Owned(replaced) = redaction.pattern.replace_all(&value, &redaction.mask) {
+            value = Cow::Owned(replaced);
+        }
+    }
+    value
+}
+
+impl Column {
+    /// Masks matches of a pattern (see the regex module for the supported syntax)
+    /// in this column's rendered values, e.g. `add_redaction(EMAIL_PATTERN, "[REDACTED]")`.
+    /// The stored values are unchanged; every renderer and exporter shows the mask.
+    ///
+    /// # Errors
+    /// - If the pattern is invalid
+    pub fn add_redaction(&mut self, pattern: &str, mask: impl Into<String>) -> Result<(), String> {
+        self.redactions.push(Redaction::new("Column::add_redaction", pattern, mask.into())?);
+        Ok(())
+    }
+
+    /// Removes the redactions added with add_redaction
+    pub fn clear_redactions(&mut self) {
+        self.redactions.clear();
+    }
+}
+
+impl Table {
+    /// Masks matches of a pattern in the rendered values of every column, after
+    /// any redactions of the column itself, e.g. `add_redaction(IPV4_PATTERN, "••••")`.
+    /// The stored values are unchanged; every renderer and exporter shows the mask.
+    ///
+    /// # Errors
+    /// - If the pattern is invalid
+    pub fn add_redaction(&mut self, pattern: &str, mask: impl Into<String>) -> Result<(), String> {
+        self.redactions.push(Redaction::new("Table::add_redaction", pattern, mask.into())?);
+        Ok(())
+    }
+
+    /// Removes the table-wide redactions added with add_redaction
+    pub fn clear_redactions(&mut self) {
+        self.redactions.clear();
+    }
+
+    /// Returns a cell value of the column at idx with column and table redactions applied
+    // Only the exporters below call this; with none of them enabled it is unused
+    #[cfg_attr(
+        not(any(
+            feature = "asciidoc",
+            feature = "chat",
+            feature = "delimited",
+            feature = "html",
+            feature = "jira",
+            feature = "json",
+            feature = "latex",
+            feature = "markdown",
+            feature = "mediawiki",
+            feature = "org",
+            feature = "parquet",
+            feature = "rst",
+            feature = "sql",
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn redacted<'a>(&self, idx: usize, value: &'a str) -> Cow<'a, str> {
+        redact(value, self.columns[idx].redactions.iter().chain(&self.redactions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Justification;
+
+    #[test]
+    fn test_redaction() {
+        let mut user = Column::new("User", 0, Justification::Left).unwrap();
+        user.add_redaction(EMAIL_PATTERN, "[REDACTED]").unwrap();
+        let mut table = Table::new(vec![user, Column::new("Client", 0, Justification::Left).unwrap()]).unwrap();
+        table.add_row(vec!["ada@example.com".to_string(), "10.0.0.12 via proxy".to_string()]).unwrap();
+        table.add_redaction(IPV4_PATTERN, "••••").unwrap();
+
+        assert_eq!(
+            table.to_string(),
+            "User       Client        \n========== ==============\n[REDACTED] •••• via proxy\n"
+        );
+        assert_eq!(table.row(0).unwrap()[0], "ada@example.com");
+        #[cfg(feature = "html")]
+        assert!(table.to_html().contains("<td>[REDACTED]</td>"));
+
+        let key = format!("key={}", "aZ3_".repeat(10));
+        assert_eq!(redact(&key, &[Redaction::new("test", TOKEN_PATTERN, "***".to_string()).unwrap()]), "key=***");
+        assert!(table.add_redaction("(", "x").is_err());
+    }
+}
@@ -0,0 +1,659 @@
+//! A small regular expression engine used by the importers and transforms that
+//! accept patterns. Supports literals, `.`, classes (`[a-z]`, `[^,]`), the
+//! shorthands `\d \w \s` (and their negations), anchors `^ $ \b \B`, groups
+//! (capturing and `(?:...)`), alternation, and the quantifiers `* + ? {n} {n,}
+//! {n,m}` with lazy `?` variants. Matching is bounded backtracking: within one
+//! search every (instruction, position) state is explored at most once, so a
+//! search takes time proportional to the program length times the text it
+//! examines. Patterns are rejected if a repetition count is above 1000, groups
+//! nest more than 200 deep, or the expanded program grows past 100,000
+//! instructions.
+
+#[derive(Debug, Clone, PartialEq)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Digit(negated) => c.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => is_word(c) != negated,
+            ClassItem::Space(negated) => c.is_whitespace() != negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Class {
+    items: Vec<ClassItem>,
+    negated: bool,
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        self.items.iter().any(|item| item.matches(c)) != self.negated
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Assertion {
+    Start,
+    End,
+    WordBoundary,
+    NotWordBoundary,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize>, greedy: bool },
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    /// Try the first target, then the second
+    Split(usize, usize),
+    Jmp(usize),
+    Save(usize),
+    Match,
+}
+
+/// Largest count a `{n}`, `{n,}` or `{n,m}` repetition may give
+const MAX_REPEAT: usize = 1000;
+
+/// Deepest nesting of groups a pattern may have
+const MAX_NESTING: usize = 200;
+
+/// Most instructions a compiled pattern may have (repetitions copy their body)
+const MAX_PROGRAM_LEN: usize = 100_000;
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// A compiled regular expression
+#[derive(Debug, Clone)]
+pub(crate) struct Regex {
+    prog: Vec<Inst>,
+    groups: usize,
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    groups: usize,
+    /// Number of groups enclosing the current position
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn alternation(&mut self) -> Result<Node, String> {
+        let mut branches = vec![self.concat()?];
+        while self.chars.peek() == Some(&'|') {
+            self.chars.next();
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 { branches.pop().unwrap_or(Node::Empty) } else { Node::Alt(branches) })
+    }
+
+    fn concat(&mut self) -> Result<Node, String> {
+        let mut nodes = Vec::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        match self.chars.next() {
+            Some('(') => {
+                if self.depth == MAX_NESTING {
+                    return Err(format!("groups are nested more than {} deep", MAX_NESTING));
+                }
+                let index = if self.chars.peek() == Some(&'?') {
+                    self.chars.next();
+                    if self.chars.next() != Some(':') {
+                        return Err("unsupported group syntax, only (?:...) is allowed".to_string());
+                    }
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+                self.depth += 1;
+                let inner = self.alternation()?;
+                self.depth -= 1;
+                if self.chars.next() != Some(')') {
+                    return Err("missing closing parenthesis".to_string());
+                }
+                Ok(Node::Group(Box::new(inner), index))
+            }
+            Some('[') => self.class(),
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Assert(Assertion::Start)),
+            Some('$') => Ok(Node::Assert(Assertion::End)),
+            Some('\\') => self.escape(),
+            Some(c @ ('*' | '+' | '?')) => Err(format!("nothing to repeat before '{}'", c)),
+            Some(c) => Ok(Node::Char(c)),
+            None => Err("unexpected end of pattern".to_string()),
+        }
+    }
+
+    fn escape(&mut self) -> Result<Node, String> {
+        let c = self.chars.next().ok_or_else(|| "pattern ends with a backslash".to_string())?;
+        let class = |item| Node::Class(Class { items: vec![item], negated: false });
+        Ok(match c {
+            'd' => class(ClassItem::Digit(false)),
+            'D' => class(ClassItem::Digit(true)),
+            'w' => class(ClassItem::Word(false)),
+            'W' => class(ClassItem::Word(true)),
+            's' => class(ClassItem::Space(false)),
+            'S' => class(ClassItem::Space(true)),
+            'b' => Node::Assert(Assertion::WordBoundary),
+            'B' => Node::Assert(Assertion::NotWordBoundary),
+            'n' => Node::Char('\n'),
+            't' => Node::Char('\t'),
+            'r' => Node::Char('\r'),
+            c => Node::Char(c),
+        })
+    }
+
+    fn class_char(&mut self) -> Result<ClassItem, String> {
+        match self.chars.next() {
+            Some('\\') => match self.chars.next() {
+                Some('d') => Ok(ClassItem::Digit(false)),
+                Some('D') => Ok(ClassItem::Digit(true)),
+                Some('w') => Ok(ClassItem::Word(false)),
+                Some('W') => Ok(ClassItem::Word(true)),
+                Some('s') => Ok(ClassItem::Space(false)),
+                Some('S') => Ok(ClassItem::Space(true)),
+                Some('n') => Ok(ClassItem::Range('\n', '\n')),
+                Some('t') => Ok(ClassItem::Range('\t', '\t')),
+                Some('r') => Ok(ClassItem::Range('\r', '\r')),
+                Some(c) => Ok(ClassItem::Range(c, c)),
+                None => Err("unterminated character class".to_string()),
+            },
+            Some(c) => Ok(ClassItem::Range(c, c)),
+            None => Err("unterminated character class".to_string()),
+        }
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.chars.peek() == Some(&'^');
+        if negated {
+            self.chars.next();
+        }
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            match self.chars.peek() {
+                None => return Err("unterminated character class".to_string()),
+                Some(']') if !first => {
+                    self.chars.next();
+                    break;
+                }
+                _ => {}
+            }
+            first = false;
+            let item = self.class_char()?;
+            if let ClassItem::Range(lo, _) = item {
+                let mut lookahead = self.chars.clone();
+                if lookahead.next() == Some('-') && lookahead.peek().is_some_and(|&c| c != ']') {
+                    self.chars.next();
+                    match self.class_char()? {
+                        ClassItem::Range(hi, _) if hi >= lo => items.push(ClassItem::Range(lo, hi)),
+                        _ => return Err(format!("invalid range starting at '{}' in character class", lo)),
+                    }
+                    continue;
+                }
+            }
+            items.push(item);
+        }
+        Ok(Node::Class(Class { items, negated }))
+    }
+
+    /// Parses a `{n}`, `{n,}` or `{n,m}` suffix, leaving the input untouched if
+    /// what follows is not a valid counted repetition
+    fn counted(&mut self) -> Option<(usize, Option<usize>)> {
+        let mut lookahead = self.chars.clone();
+        lookahead.next();
+        let mut text = String::new();
+        for c in lookahead.by_ref() {
+            if c == '}' {
+                let (min, max) = match text.split_once(',') {
+                    None => {
+                        let n = text.parse().ok()?;
+                        (n, Some(n))
+                    }
+                    Some((min, "")) => (min.parse().ok()?, None),
+                    Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+                };
+                self.chars = lookahead;
+                return Some((min, max));
+            }
+            text.push(c);
+        }
+        None
+    }
+
+    fn quantifier(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = match self.chars.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.counted() {
+                Some(range) => {
+                    if range.1.is_some_and(|max| max < range.0) {
+                        return Err(format!("invalid repetition {{{},{:?}}}", range.0, range.1));
+                    }
+                    if range.0.max(range.1.unwrap_or(0)) > MAX_REPEAT {
+                        return Err(format!("repetition count above {}", MAX_REPEAT));
+                    }
+                    return Ok(self.finish_repeat(atom, range.0, range.1));
+                }
+                None => return Ok(atom),
+            },
+            _ => return Ok(atom),
+        };
+        self.chars.next();
+        Ok(self.finish_repeat(atom, min, max))
+    }
+
+    fn finish_repeat(&mut self, atom: Node, min: usize, max: Option<usize>) -> Node {
+        let greedy = if self.chars.peek() == Some(&'?') {
+            self.chars.next();
+            false
+        } else {
+            true
+        };
+        Node::Repeat { node: Box::new(atom), min, max, greedy }
+    }
+}
+
+/// Returns the number of instructions a node compiles to, saturating on overflow
+fn program_len(node: &Node) -> usize {
+    match node {
+        Node::Empty => 0,
+        Node::Char(_) | Node::Any | Node::Class(_) | Node::Assert(_) => 1,
+        Node::Group(inner, index) => program_len(inner).saturating_add(if index.is_some() { 2 } else { 0 }),
+        Node::Concat(nodes) => nodes.iter().fold(0, |len, n| len.saturating_add(program_len(n))),
+        Node::Alt(branches) => branches.iter().fold(0, |len, n| len.saturating_add(program_len(n)).saturating_add(2)),
+        Node::Repeat { node, min, max, .. } => {
+            let copies = max.map_or(min.saturating_add(1), |max| max.max(*min));
+            program_len(node).saturating_add(1).saturating_mul(copies).saturating_add(1)
+        }
+    }
+}
+
+fn compile(node: &Node, prog: &mut Vec<Inst>) {
+    match node {
+        Node::Empty => {}
+        Node::Char(c) => prog.push(Inst::Char(*c)),
+        Node::Any => prog.push(Inst::Any),
+        Node::Class(class) => prog.push(Inst::Class(class.clone())),
+        Node::Assert(a) => prog.push(Inst::Assert(*a)),
+        Node::Group(inner, None) => compile(inner, prog),
+        Node::Group(inner, Some(index)) => {
+            prog.push(Inst::Save(index * 2));
+            compile(inner, prog);
+            prog.push(Inst::Save(index * 2 + 1));
+        }
+        Node::Concat(nodes) => nodes.iter().for_each(|n| compile(n, prog)),
+        Node::Alt(branches) => {
+            let mut jumps = Vec::new();
+            for (i, branch) in branches.iter().enumerate() {
+                if i + 1 < branches.len() {
+                    let split = prog.len();
+                    prog.push(Inst::Split(split + 1, 0));
+                    compile(branch, prog);
+                    jumps.push(prog.len());
+                    prog.push(Inst::Jmp(0));
+                    let next = prog.len();
+                    prog[split] = Inst::Split(split + 1, next);
+                } else {
+                    compile(branch, prog);
+                }
+            }
+            let end = prog.len();
+            for j in jumps {
+                prog[j] = Inst::Jmp(end);
+            }
+        }
+        Node::Repeat { node, min, max, greedy } => {
+            for _ in 0..*min {
+                compile(node, prog);
+            }
+            let split = |body: usize, exit: usize| if *greedy { Inst::Split(body, exit) } else { Inst::Split(exit, body) };
+            match max {
+                None => {
+                    let start = prog.len();
+                    prog.push(Inst::Jmp(0));
+                    compile(node, prog);
+                    prog.push(Inst::Jmp(start));
+                    let exit = prog.len();
+                    prog[start] = split(start + 1, exit);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(prog.len());
+                        prog.push(Inst::Jmp(0));
+                        compile(node, prog);
+                    }
+                    let exit = prog.len();
+                    for s in splits {
+                        prog[s] = split(s + 1, exit);
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum Job {
+    Explore(usize, usize),
+    Restore(usize, Option<usize>),
+}
+
+/// The (instruction, position) states a search has explored, as a bitmap over
+/// the whole text. The words that were set are remembered, so the map can be
+/// cleared for the next search without touching the rest of it.
+struct Visited {
+    bits: Vec<u64>,
+    set: Vec<usize>,
+    insts: usize,
+}
+
+impl Visited {
+    fn new(insts: usize, text: &str) -> Visited {
+        Visited { bits: vec![0; (insts * (text.len() + 1)).div_ceil(64)], set: Vec::new(), insts }
+    }
+
+    /// Marks a state, returning false if it was already marked
+    fn insert(&mut self, pc: usize, pos: usize) -> bool {
+        let key = pos * self.insts + pc;
+        let (word, bit) = (key / 64, 1 << (key % 64));
+        if self.bits[word] & bit != 0 {
+            return false;
+        }
+        if self.bits[word] == 0 {
+            self.set.push(word);
+        }
+        self.bits[word] |= bit;
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.set.drain(..) {
+            self.bits[word] = 0;
+        }
+    }
+}
+
+impl Regex {
+    /// Compiles a pattern
+    pub(crate) fn new(pattern: &str) -> Result<Regex, String> {
+        let mut parser = Parser { chars: pattern.chars().peekable(), groups: 0, depth: 0 };
+        let node = parser.alternation()?;
+        if parser.chars.next().is_some() {
+            return Err("unmatched closing parenthesis".to_string());
+        }
+        if program_len(&node) > MAX_PROGRAM_LEN {
+            return Err("pattern is too large once its repetitions are expanded".to_string());
+        }
+        let mut prog = vec![Inst::Save(0)];
+        compile(&node, &mut prog);
+        prog.push(Inst::Save(1));
+        prog.push(Inst::Match);
+        Ok(Regex { prog, groups: parser.groups })
+    }
+
+    /// Returns the number of capturing groups in the pattern
+    pub(crate) fn group_count(&self) -> usize {
+        self.groups
+    }
+
+    /// Finds the leftmost match starting at or after the byte offset `start`, returning
+    /// the byte range of the whole match followed by the range of each capturing group
+    pub(crate) fn captures_at(&self, text: &str, start: usize) -> Option<Vec<Option<(usize, usize)>>> {
+        self.search(text, start, &mut Visited::new(self.prog.len(), text))
+    }
+
+    /// Finds the leftmost match at or after `start` (see captures_at), recording
+    /// the states explored in `visited`. A state explored from an earlier
+    /// starting position is known to fail, so the map is shared across them.
+    fn search(&self, text: &str, start: usize, visited: &mut Visited) -> Option<Vec<Option<(usize, usize)>>> {
+        let mut slots: Vec<Option<usize>> = vec![None; (self.groups + 1) * 2];
+
+        let mut pos = start;
+        loop {
+            if self.backtrack(text, pos, visited, &mut slots) {
+                return Some(
+                    slots
+                        .chunks(2)
+                        .map(|pair| match (pair[0], pair[1]) {
+                            (Some(s), Some(e)) => Some((s, e)),
+                            _ => None,
+                        })
+                        .collect(),
+                );
+            }
+            match text[pos..].chars().next() {
+                Some(c) => pos += c.len_utf8(),
+                None => return None,
+            }
+        }
+    }
+
+    /// Runs the program from a single starting position. States already visited
+    /// are known to fail.
+    fn backtrack(&self, text: &str, start: usize, visited: &mut Visited, slots: &mut [Option<usize>]) -> bool {
+        let mut stack = vec![Job::Explore(0, start)];
+        while let Some(job) = stack.pop() {
+            let (mut pc, mut pos) = match job {
+                Job::Explore(pc, pos) => (pc, pos),
+                Job::Restore(slot, value) => {
+                    slots[slot] = value;
+                    continue;
+                }
+            };
+            loop {
+                if !visited.insert(pc, pos) {
+                    break;
+                }
+
+                let next_char = text[pos..].chars().next();
+                match &self.prog[pc] {
+                    Inst::Match => return true,
+                    Inst::Char(expected) => match next_char {
+                        Some(c) if c == *expected => {
+                            pc += 1;
+                            pos += c.len_utf8();
+                        }
+                        _ => break,
+                    },
+                    Inst::Any => match next_char {
+                        Some(c) if c != '\n' => {
+                            pc += 1;
+                            pos += c.len_utf8();
+                        }
+                        _ => break,
+                    },
+                    Inst::Class(class) => match next_char {
+                        Some(c) if class.matches(c) => {
+                            pc += 1;
+                            pos += c.len_utf8();
+                        }
+                        _ => break,
+                    },
+                    Inst::Assert(assertion) => {
+                        let before = text[..pos].chars().next_back().is_some_and(is_word);
+                        let after = next_char.is_some_and(is_word);
+                        let ok = match assertion {
+                            Assertion::Start => pos == 0,
+                            Assertion::End => pos == text.len(),
+                            Assertion::WordBoundary => before != after,
+                            Assertion::NotWordBoundary => before == after,
+                        };
+                        if !ok {
+                            break;
+                        }
+                        pc += 1;
+                    }
+                    Inst::Split(first, second) => {
+                        stack.push(Job::Explore(*second, pos));
+                        pc = *first;
+                    }
+                    Inst::Jmp(target) => pc = *target,
+                    Inst::Save(slot) => {
+                        stack.push(Job::Restore(*slot, slots[*slot]));
+                        slots[*slot] = Some(pos);
+                        pc += 1;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the byte ranges of all non-overlapping matches
+    pub(crate) fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        let mut visited = Visited::new(self.prog.len(), text);
+        let mut pos = 0;
+        while pos <= text.len() {
+            let Some((start, end)) = self.search(text, pos, &mut visited).and_then(|caps| caps[0]) else { break };
+            // States explored by a successful search are not known to fail
+            visited.clear();
+            matches.push((start, end));
+            pos = if end > start {
+                end
+            } else {
+                // Step past an empty match so the search always advances
+                match text[end..].chars().next() {
+                    Some(c) => end + c.len_utf8(),
+                    None => break,
+                }
+            };
+        }
+        matches
+    }
+
+    /// Replaces every non-overlapping match with the replacement text, borrowing the
+    /// text when nothing matches
+    pub(crate) fn replace_all<'t>(&self, text: &'t str, replacement: &str) -> std::borrow::Cow<'t, str> {
+        let matches = self.find_all(text);
+        if matches.is_empty() {
+            return std::borrow::Cow::Borrowed(text);
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end) in matches {
+            out.push_str(&text[last..start]);
+            out.push_str(replacement);
+            last = end;
+        }
+        out.push_str(&text[last..]);
+        std::borrow::Cow::Owned(out)
+    }
+
+    /// Splits the text around matches, returning at most `limit` pieces (0 for no limit);
+    /// the last piece holds the remainder of the text
+    #[cfg_attr(not(feature = "streaming"), allow(dead_code))]
+    pub(crate) fn splitn<'t>(&self, text: &'t str, limit: usize) -> Vec<&'t str> {
+        let mut pieces = Vec::new();
+        let mut last = 0;
+        for (start, end) in self.find_all(text) {
+            if limit > 0 && pieces.len() + 1 >= limit {
+                break;
+            }
+            if end == 0 || start == text.len() {
+                continue;
+            }
+            pieces.push(&text[last..start]);
+            last = end;
+        }
+        pieces.push(&text[last..]);
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'t>(pattern: &str, text: &'t str) -> Option<&'t str> {
+        Regex::new(pattern).unwrap().captures_at(text, 0).and_then(|caps| caps[0]).map(|(s, e)| &text[s..e])
+    }
+
+    #[test]
+    fn test_matching() {
+        assert_eq!(find(r"\d+", "abc 123 45"), Some("123"));
+        assert_eq!(find(r"[a-c]+x?", "zzbcaxq"), Some("bcax"));
+        assert_eq!(find(r"[^,]+", ",,ab,c"), Some("ab"));
+        assert_eq!(find(r"^ab|cd$", "xabcd"), Some("cd"));
+        assert_eq!(find(r"a.*b", "a1b2b"), Some("a1b2b"));
+        assert_eq!(find(r"a.*?b", "a1b2b"), Some("a1b"));
+        assert_eq!(find(r"x{2,3}", "xxxxx"), Some("xxx"));
+        assert_eq!(find(r"\bcat\b", "concat cat"), Some("cat"));
+        assert_eq!(find(r"(?:ab)+", "ababa"), Some("abab"));
+        assert_eq!(find(r"é+", "café!"), Some("é"));
+        assert_eq!(find(r"a{2}", "a"), None);
+        assert_eq!(find(r"(a*)*b", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaac"), None);
+        assert!(Regex::new("(ab").is_err());
+        assert!(Regex::new("*a").is_err());
+        assert!(Regex::new("[z-a]").is_err());
+        assert!(Regex::new("a{1000}").is_ok());
+        assert_eq!(Regex::new("(a{4000000000})").unwrap_err(), "repetition count above 1000");
+        assert!(Regex::new("((a{1000}){1000}){1000}").is_err());
+        assert!(Regex::new(&"(".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn test_captures_and_split() {
+        let re = Regex::new(r"(\w+)@(\w+)\.com").unwrap();
+        let text = "mail bob@example.com now";
+        let caps = re.captures_at(text, 0).unwrap();
+        assert_eq!(re.group_count(), 2);
+        assert_eq!(caps[1].map(|(s, e)| &text[s..e]), Some("bob"));
+        assert_eq!(caps[2].map(|(s, e)| &text[s..e]), Some("example"));
+
+        let comma = Regex::new(r"\s*,\s*").unwrap();
+        assert_eq!(comma.splitn("a , b,c", 0), ["a", "b", "c"]);
+        assert_eq!(comma.splitn("a , b,c", 2), ["a", "b,c"]);
+    }
+
+    #[test]
+    fn test_find_all_many_matches() {
+        // One bitmap serves the whole scan; allocating one per match made this quadratic
+        let text = "10.0.0.1 x ".repeat(50_000);
+        let re = Regex::new(r"\b\d{1,3}(?:\.\d{1,3}){3}\b").unwrap();
+        let matches = re.find_all(&text);
+        assert_eq!(matches.len(), 50_000);
+        assert_eq!(matches[49_999], (549_989, 549_997));
+        assert_eq!(re.replace_all("a 1.2.3.4 b 5.6.7.8", "*"), "a * b *");
+    }
+}
@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+use std::ops::Index;
+
+use crate::{Column, Metadata, Row, Table, MAX_CELL_LINES};
+
+/// Read access to one row's cells by column name or position, e.g. `row["Host"]`
+/// or `row.get("Host")`. Returned by Table::row_ref and Table::iter_rows and
+/// passed to the closures of retain_rows_by and sort_rows_by.
+#[derive(Debug, Clone, Copy)]
+pub struct RowRef<'a> {
+    columns: &'a [Column],
+    row: &'a Row,
+}
+
+impl<'a> RowRef<'a> {
+    /// Returns the value in the named column, or None if there is no such column
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        let idx = self.columns.iter().position(|col| col.name == name)?;
+        Some(&self.row.cells[idx])
+    }
+
+    /// Returns the value in the column at the given position, if it exists
+    pub fn get_at(&self, column: usize) -> Option<&'a str> {
+        self.row.cells.get(column).map(String::as_str)
+    }
+
+    /// Returns the row's values in column order
+    pub fn cells(&self) -> &'a [String] {
+        &self.row.cells
+    }
+
+    /// Returns the metadata attached to the row
+    pub fn metadata(&self) -> &'a Metadata {
+        &self.row.metadata
+    }
+
+    /// Returns the number of cells (one per column)
+    pub fn len(&self) -> usize {
+        self.row.cells.len()
+    }
+
+    /// Returns true if the row has no cells
+    pub fn is_empty(&self) -> bool {
+        self.row.cells.is_empty()
+    }
+}
+
+impl Index<&str> for RowRef<'_> {
+    type Output = str;
+
+    /// Panics if there is no column with the name; use get to check
+    fn index(&self, name: &str) -> &str {
+        self.get(name).unwrap_or_else(|| panic!("RowRef: no column named '{}'", name))
+    }
+}
+
+impl Index<usize> for RowRef<'_> {
+    type Output = str;
+
+    fn index(&self, column: usize) -> &str {
+        &self.row.cells[column]
+    }
+}
+
+/// Write access to one row's cells by column name or position, returned by
+/// Table::row_mut. Column widths are kept up to date as cells change.
+#[derive(Debug)]
+pub struct RowMut<'a> {
+    columns: &'a mut [Column],
+    row: &'a mut Row,
+    generation: &'a mut u64,
+}
+
+impl RowMut<'_> {
+    /// Returns a read-only view of the row
+    pub fn as_ref(&self) -> RowRef<'_> {
+        RowRef { columns: self.columns, row: self.row }
+    }
+
+    /// Returns the value in the named column, or None if there is no such column
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let idx = self.columns.iter().position(|col| col.name == name)?;
+        Some(&self.row.cells[idx])
+    }
+
+    /// Replaces the value in the named column, returning the previous value
+    ///
+    /// # Errors
+    /// - If there is no column with the name
+    /// - If the value contains more than MAX_CELL_LINES (5000) lines
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> Result<String, String> {
+        let idx = self.columns
+            .iter()
+            .position(|col| col.name == name)
+            .ok_or_else(|| format!("RowMut::set: no column named '{}'", name))?;
+        self.set_at(idx, value)
+    }
+
+    /// Replaces the value in the column at the given position, returning the
+    /// previous value
+    ///
+    /// # Errors
+    /// - If column is out of range
+    /// - If the value contains more than MAX_CELL_LINES (5000) lines
+    pub fn set_at(&mut self, column: usize, value: impl Into<String>) -> Result<String, String> {
+        let count = self.columns.len();
+        let col = self.columns
+            .get_mut(column)
+            .ok_or_else(|| format!("RowMut::set_at: column index {} out of range ({} columns)", column, count))?;
+        let value = value.into();
+        let line_count = value.split('\n').count();
+        if line_count > MAX_CELL_LINES {
+            return Err(format!(
+                "RowMut::set_at: cell contains {} lines, exceeding maximum allowed ({})",
+                line_count, MAX_CELL_LINES
+            ));
+        }
+        let old = std::mem::replace(&mut self.row.cells[column], value);
+        // A shrinking value may have been the column's longest
+        if col.value_width(&old) >= col.max_length {
+            col.width_stale = true;
+        }
+        col.update_max_length(&self.row.cells[column]);
+        *self.generation += 1;
+        self.row.changed = *self.generation;
+        Ok(old)
+    }
+}
+
+impl Index<&str> for RowMut<'_> {
+    type Output = str;
+
+    /// Panics if there is no column with the name; use get to check
+    fn index(&self, name: &str) -> &str {
+        self.get(name).unwrap_or_else(|| panic!("RowMut: no column named '{}'", name))
+    }
+}
+
+impl Index<usize> for RowMut<'_> {
+    type Output = str;
+
+    fn index(&self, column: usize) -> &str {
+        &self.row.cells[column]
+    }
+}
+
+impl Table {
+    /// Returns a view of the row at the given index with access by column name
+    pub fn row_ref(&self, row: usize) -> Option<RowRef<'_>> {
+        Some(RowRef { columns: &self.columns, row: self.rows.get(row)? })
+    }
+
+    /// Returns the row at the given index for changing cells by column name
+    pub fn row_mut(&mut self, row: usize) -> Option<RowMut<'_>> {
+        Some(RowMut { columns: &mut self.columns, row: self.rows.get_mut(row)?, generation: &mut self.generation })
+    }
+
+    /// Iterates over the rows in order, with access by column name
+    pub fn iter_rows(&self) -> impl Iterator<Item = RowRef<'_>> {
+        self.rows.iter().map(|row| RowRef { columns: &self.columns, row })
+    }
+
+    /// Like retain_rows, with the predicate given a RowRef so it can read cells
+    /// by column name: `table.retain_rows_by(|row| row["Status"] != "done")`
+    pub fn retain_rows_by(&mut self, mut keep: impl FnMut(RowRef) -> bool) {
+        let keep: Vec<bool> = self.iter_rows().map(&mut keep).collect();
+        let mut keep = keep.into_iter();
+        self.retain_rows(|_| keep.next().unwrap_or(true));
+    }
+
+    /// Sorts the rows (stably) with a comparison of RowRefs, e.g.
+    /// `table.sort_rows_by(|a, b| a["Host"].cmp(&b["Host"]))`
+    pub fn sort_rows_by(&mut self, mut compare: impl FnMut(RowRef, RowRef) -> Ordering) {
+        let columns = &self.columns;
+        self.rows.sort_by(|a, b| compare(RowRef { columns, row: a }, RowRef { columns, row: b }));
+        self.reordered = self.next_generation();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::with_headers(["Host", "Status"]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "up".to_string()]).unwrap();
+        table.add_row(vec!["db-10".to_string(), "down".to_string()]).unwrap();
+        table.add_row(vec!["cache".to_string(), "up".to_string()]).unwrap();
+        table
+    }
+
+    #[test]
+    fn test_row_ref() {
+        let mut table = table();
+        let row = table.row_ref(1).unwrap();
+        assert_eq!((&row["Host"], &row[1]), ("db-10", "down"));
+        assert_eq!(row.get("Missing"), None);
+        assert_eq!(table.iter_rows().filter(|row| &row["Status"] == "up").count(), 2);
+
+        table.sort_rows_by(|a, b| a["Host"].cmp(&b["Host"]));
+        table.retain_rows_by(|row| row.get("Status") == Some("up"));
+        let hosts: Vec<&str> = table.iter_rows().filter_map(|row| row.get("Host")).collect();
+        assert_eq!(hosts, ["cache", "web-1"]);
+    }
+
+    #[test]
+    fn test_row_mut() {
+        let mut table = table();
+        let mut row = table.row_mut(1).unwrap();
+        assert_eq!(row.set("Status", "recovering").unwrap(), "down");
+        assert_eq!(&row["Status"], "recovering");
+        assert!(row.set("Missing", "x").is_err());
+        assert!(row.set_at(2, "x").is_err());
+        assert!(table.to_string().starts_with("Host  Status    \n===== ==========\n"));
+
+        table.row_mut(1).unwrap().set("Status", "up").unwrap();
+        assert!(table.to_string().starts_with("Host  Status\n===== ======\n"));
+    }
+}
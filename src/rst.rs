@@ -0,0 +1,98 @@
+use crate::{text_width, Table};
+
+/// Splits each cell into its lines
+fn split_lines(cells: &[String]) -> Vec<Vec<&str>> {
+    cells.iter().map(|cell| cell.split('\n').collect()).collect()
+}
+
+/// Appends a border line such as `+-----+----+`, with fill as the horizontal
+fn push_border(out: &mut String, widths: &[usize], fill: char) {
+    out.push('+');
+    for &width in widths {
+        out.extend(std::iter::repeat_n(fill, width + 2));
+        out.push('+');
+    }
+    out.push('\n');
+}
+
+/// Appends the lines of a row of cells, each split on its newlines and padded
+/// to its column width, with shorter cells filled out by blank lines
+fn push_row(out: &mut String, cells: &[Vec<&str>], widths: &[usize]) {
+    let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+    for line in 0..height {
+        out.push('|');
+        for (cell, &width) in cells.iter().zip(widths) {
+            let text = cell.get(line).copied().unwrap_or("");
+            out.push(' ');
+            out.push_str(text);
+            out.extend(std::iter::repeat_n(' ', width - text_width(text) + 1));
+            out.push('|');
+        }
+        out.push('\n');
+    }
+}
+
+impl Table {
+    /// Renders the table as a reStructuredText grid table (`+---+` borders, with
+    /// `+===+` under the header), for Sphinx and other docutils documentation.
+    /// Cells with several lines span that many lines of their row. Text is
+    /// left-justified since grid tables carry no alignment, and headers use the
+    /// same text as the text renderer.
+    pub fn to_rst(&self) -> String {
+        let headers: Vec<String> = self.columns.iter().map(|col| self.header_text(col).replace("\r\n", "\n")).collect();
+        let rows: Vec<Vec<String>> = self.rows
+            .iter()
+            .map(|row| row.cells.iter().enumerate().map(|(idx, cell)| self.redacted(idx, cell).replace("\r\n", "\n")).collect())
+            .collect();
+        let header_lines = split_lines(&headers);
+        let row_lines: Vec<Vec<Vec<&str>>> = rows.iter().map(|row| split_lines(row)).collect();
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|idx| {
+                std::iter::once(&header_lines[idx])
+                    .chain(row_lines.iter().map(|row| &row[idx]))
+                    .flatten()
+                    .map(|line| text_width(line))
+                    .max()
+                    .unwrap_or(0)
+                    .max(1)
+            })
+            .collect();
+
+        let mut out = String::new();
+        push_border(&mut out, &widths, '-');
+        push_row(&mut out, &header_lines, &widths);
+        push_border(&mut out, &widths, '=');
+        for row in &row_lines {
+            push_row(&mut out, row, &widths);
+            push_border(&mut out, &widths, '-');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_rst() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Notes", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "first line\nsecond".to_string()]).unwrap();
+        table.add_row(vec!["db".to_string(), String::new()]).unwrap();
+        assert_eq!(
+            table.to_rst(),
+            "+-------+------------+\n\
+             | Name  | Notes      |\n\
+             +=======+============+\n\
+             | web-1 | first line |\n\
+             |       | second     |\n\
+             +-------+------------+\n\
+             | db    |            |\n\
+             +-------+------------+\n"
+        );
+    }
+}
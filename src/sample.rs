@@ -0,0 +1,112 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::Table;
+
+/// SplitMix64, a small fast generator that is plenty for picking sample rows
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in 0..bound (bound must be non-zero)
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+impl Table {
+    /// Returns a table of n rows picked at random, in their original order, for
+    /// previewing large tables. Every row is equally likely to be picked; tables
+    /// with n rows or fewer are copied whole. Use sample_seeded for a repeatable pick.
+    pub fn sample(&self, n: usize) -> Table {
+        // RandomState is seeded randomly per process, which saves a dependency
+        let seed = RandomState::new().build_hasher().finish();
+        self.sample_seeded(n, seed)
+    }
+
+    /// Like sample, but the same seed always picks the same rows of a table
+    pub fn sample_seeded(&self, n: usize, seed: u64) -> Table {
+        let mut rng = SplitMix64(seed);
+        // Reservoir sampling: row i replaces a random pick with probability n / (i + 1)
+        let mut picked: Vec<usize> = (0..n.min(self.rows.len())).collect();
+        for idx in n..self.rows.len() {
+            let slot = rng.below(idx + 1);
+            if slot < n {
+                picked[slot] = idx;
+            }
+        }
+        picked.sort_unstable();
+        self.with_rows(picked)
+    }
+
+    /// Returns a table of every k-th row, starting with the first, for an evenly
+    /// spread preview of a large table
+    ///
+    /// # Errors
+    /// - If k is 0
+    pub fn sample_every(&self, k: usize) -> Result<Table, String> {
+        if k == 0 {
+            return Err("Table::sample_every: step must be at least 1".to_string());
+        }
+        Ok(self.with_rows((0..self.rows.len()).step_by(k)))
+    }
+
+    /// Copies the table with only the rows at the given (ascending) indices, with
+    /// sections kept in place among them. Column widths are recomputed from the
+    /// copied rows at the first render.
+    fn with_rows(&self, indices: impl IntoIterator<Item = usize>) -> Table {
+        let indices: Vec<usize> = indices.into_iter().collect();
+        let mut table = Table::with_columns(self.columns.clone());
+        table.options = self.options.clone();
+        table.header_formatter = self.header_formatter.clone();
+        table.redactions = self.redactions.clone();
+        table.sections = self.sections.clone();
+        table.remap_sections(|pos| indices.partition_point(|&idx| idx < pos));
+        table.rows = indices.iter().map(|&idx| self.rows[idx].clone()).collect();
+        for col in &mut table.columns {
+            col.width_stale = true;
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(count: usize) -> Table {
+        let mut table = Table::with_headers(["N"]).unwrap();
+        for n in 0..count {
+            table.add_row(vec![n.to_string()]).unwrap();
+        }
+        table
+    }
+
+    fn values(table: &Table) -> Vec<usize> {
+        table.iter_rows().map(|row| row[0].parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sample() {
+        let table = numbered(1000);
+        let sample = table.sample(10);
+        let picked = values(&sample);
+        assert_eq!(picked.len(), 10);
+        assert!(picked.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(values(&table.sample_seeded(10, 7)), values(&table.sample_seeded(10, 7)));
+        assert_eq!(table.sample(2000).row_count(), 1000);
+
+        // Widths follow the sampled rows, not the whole table
+        let mut wide = numbered(3);
+        wide.add_row(vec!["a much longer value".to_string()]).unwrap();
+        assert_eq!(wide.sample_every(2).unwrap().to_string(), "N\n=\n0\n2\n");
+        assert!(wide.sample_every(0).is_err());
+    }
+}
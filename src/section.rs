@@ -0,0 +1,160 @@
+use crate::{text_width, truncate_line, Layout, Table};
+
+/// How a section row is drawn across the table
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SectionStyle {
+    /// Center the text in the table width instead of starting at the left edge
+    pub centered: bool,
+    /// Character filling the width around the text, which is then set off by a
+    /// space on each side, e.g. '=' for "=== Production ==="
+    pub fill: Option<char>,
+    /// Draw a line of this character across the table above the section
+    pub rule_above: Option<char>,
+    /// Draw a line of this character across the table below the section
+    pub rule_below: Option<char>,
+}
+
+/// A line of text spanning every column, placed before a row
+#[derive(Debug, Clone)]
+pub(crate) struct Section {
+    /// Index of the row the section comes before (the row count for one after
+    /// the last row)
+    row: usize,
+    text: String,
+    style: SectionStyle,
+}
+
+impl Table {
+    /// Adds a section row spanning the whole table width, rendered after the rows
+    /// added so far: section titles, notes, or error banners. Lines of multiline
+    /// text are drawn one beneath the other and cut to the table width. Sections
+    /// are only drawn by the text renderer; other formats list the rows alone.
+    pub fn add_section(&mut self, text: impl Into<String>) {
+        self.add_section_with(text, SectionStyle::default());
+    }
+
+    /// Adds a section row drawn with the given style (see add_section)
+    pub fn add_section_with(&mut self, text: impl Into<String>, style: SectionStyle) {
+        self.sections.push(Section { row: self.rows.len(), text: text.into(), style });
+    }
+
+    /// Returns the number of section rows
+    pub fn section_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Removes all section rows
+    pub fn clear_sections(&mut self) {
+        self.sections.clear();
+    }
+
+    /// Moves sections after rows were removed. new_position maps a section's old
+    /// row index to its new one; sections keep their place relative to the rows
+    /// that remain, and sorting leaves them where they are.
+    pub(crate) fn remap_sections(&mut self, new_position: impl Fn(usize) -> usize) {
+        for section in &mut self.sections {
+            section.row = new_position(section.row);
+        }
+    }
+
+    /// Writes the rows in the range with the sections placed among them. Sections
+    /// after the last row of the range are included only if trailing is set.
+    pub(crate) fn write_body(
+        &self,
+        f: &mut std::fmt::Formatter,
+        layout: &Layout,
+        rows: std::ops::Range<usize>,
+        trailing: bool,
+    ) -> std::fmt::Result {
+        let mut start = rows.start;
+        let placed = self.sections.iter().filter(|s| rows.contains(&s.row) || (trailing && s.row == rows.end));
+        for section in placed {
+            self.write_rows(f, layout, start..section.row)?;
+            start = section.row;
+            self.write_section(f, layout, section)?;
+        }
+        self.write_rows(f, layout, start..rows.end)
+    }
+
+    fn write_section(&self, f: &mut std::fmt::Formatter, layout: &Layout, section: &Section) -> std::fmt::Result {
+        let width = layout.inner_len();
+        let style = &section.style;
+        let framed = |text: String| format!("{}{}{}", layout.frame.left, text, layout.frame.right);
+        if let Some(rule) = style.rule_above {
+            self.write_line(f, &framed(String::from_iter(std::iter::repeat_n(rule, width))))?;
+        }
+        for line in section.text.split('\n') {
+            self.write_line(f, &framed(section_line(line, width, style)))?;
+        }
+        if let Some(rule) = style.rule_below {
+            self.write_line(f, &framed(String::from_iter(std::iter::repeat_n(rule, width))))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of lines the sections add to a full render
+    pub(crate) fn section_lines(&self) -> usize {
+        self.sections
+            .iter()
+            .map(|s| s.text.split('\n').count() + usize::from(s.style.rule_above.is_some()) + usize::from(s.style.rule_below.is_some()))
+            .sum()
+    }
+}
+
+/// Lays out one line of section text across the width
+fn section_line(text: &str, width: usize, style: &SectionStyle) -> String {
+    let text = match style.fill {
+        Some(fill) if fill != ' ' && !text.is_empty() => format!(" {} ", text),
+        _ => text.to_string(),
+    };
+    let text = truncate_line(&text, width);
+    let space = width.saturating_sub(text_width(&text));
+    let before = if style.centered { space / 2 } else { 0 };
+    let fill = style.fill.unwrap_or(' ');
+    let mut line = String::with_capacity(width + text.len());
+    line.extend(std::iter::repeat_n(fill, before));
+    line.push_str(&text);
+    line.extend(std::iter::repeat_n(fill, space - before));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let mut table = Table::with_headers(["Host", "Status"]).unwrap();
+        let banner = SectionStyle { centered: true, fill: Some('='), ..Default::default() };
+        table.add_section_with("Production", banner);
+        table.add_row(vec!["web-1".to_string(), "up".to_string()]).unwrap();
+        table.add_row(vec!["web-2".to_string(), "up".to_string()]).unwrap();
+        table.add_section("Staging");
+        table.add_row(vec!["stage-1".to_string(), "down".to_string()]).unwrap();
+        table.add_section_with("1 host down", SectionStyle { rule_above: Some('-'), ..Default::default() });
+
+        assert_eq!(
+            table.to_string(),
+            "Host    Status\n\
+             ======= ======\n\
+             = Production =\n\
+             web-1   up    \n\
+             web-2   up    \n\
+             Staging       \n\
+             stage-1 down  \n\
+             --------------\n\
+             1 host down   \n"
+        );
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+        assert_eq!(table.render_rows(1..3).unwrap(), "web-2   up    \nStaging       \nstage-1 down  \n");
+
+        table.remove_row(1).unwrap();
+        table.retain_rows(|row| row[1] == "down");
+        assert_eq!(
+            table.to_string(),
+            "Host    Status\n======= ======\n= Production =\nStaging       \nstage-1 down  \n--------------\n1 host down   \n"
+        );
+        table.clear_rows();
+        assert_eq!(table.section_count(), 0);
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(feature = "json")]
+use crate::json::{self, JsonValue};
+use crate::{Column, Justification, Table};
+
+/// A column layout described as data, so it can come from a config file or a
+/// command-line flag instead of code. The string form is "name[:justification][:width]",
+/// e.g. "desc:left:40" or "amount:right".
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub justification: Justification,
+    /// Truncation width, or 0 for no truncation (as in Column::new)
+    pub width: usize,
+}
+
+impl ColumnSpec {
+    /// Creates a spec for a left-justified, non-truncated column
+    pub fn new(name: impl Into<String>) -> Self {
+        ColumnSpec { name: name.into(), justification: Justification::Left, width: 0 }
+    }
+
+    /// Parses one column spec such as "desc:left:40". The justification ("left"/"l",
+    /// "right"/"r", "center"/"c", or "decimal"/"d") and width may be given in either order,
+    /// and either may be left out.
+    ///
+    /// # Errors
+    /// - If the name is empty
+    /// - If a part after the name is neither a justification nor a width
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split(':').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        if name.is_empty() {
+            return Err(format!("ColumnSpec::parse: missing column name in '{}'", spec));
+        }
+        let mut column = ColumnSpec::new(name);
+        for part in parts {
+            if let Some(justification) = parse_justification(part) {
+                column.justification = justification;
+            } else if let Ok(width) = part.parse() {
+                column.width = width;
+            } else {
+                return Err(format!(
+                    "ColumnSpec::parse: '{}' in '{}' is not a justification or width",
+                    part, spec
+                ));
+            }
+        }
+        Ok(column)
+    }
+
+    /// Parses a comma-separated list of column specs
+    ///
+    /// # Errors
+    /// - If any spec is invalid (see parse)
+    pub fn parse_list(specs: &str) -> Result<Vec<Self>, String> {
+        specs.split(',').map(ColumnSpec::parse).collect()
+    }
+
+    /// Loads column specs from JSON: an array of objects (or an object whose
+    /// "columns" key holds one) with a "name" and optional "justification" and
+    /// "width", e.g. `[{"name": "desc", "width": 40}]`
+    ///
+    /// # Errors
+    /// - If the text is not valid JSON or not in the shape above
+    /// - If an entry has an unknown key or an invalid value
+    #[cfg(feature = "json")]
+    pub fn from_json(text: &str) -> Result<Vec<Self>, String> {
+        let error = |e: String| format!("ColumnSpec::from_json: {}", e);
+        let entries = match json::parse(text).map_err(error)? {
+            JsonValue::Array(entries) => entries,
+            JsonValue::Object(mut fields) => match fields.iter().position(|(key, _)| key == "columns") {
+                Some(pos) => match fields.swap_remove(pos).1 {
+                    JsonValue::Array(entries) => entries,
+                    _ => return Err(error("\"columns\" is not an array".to_string())),
+                },
+                None => return Err(error("expected an array of columns or a \"columns\" key".to_string())),
+            },
+            _ => return Err(error("expected an array of columns".to_string())),
+        };
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(idx, entry)| match entry {
+                JsonValue::Object(fields) => {
+                    let fields = fields.into_iter().map(|(key, value)| (key, value.to_cell()));
+                    ColumnSpec::from_fields(fields).map_err(|e| error(format!("column {}: {}", idx, e)))
+                }
+                _ => Err(error(format!("column {} is not an object", idx))),
+            })
+            .collect()
+    }
+
+    /// Loads column specs from the `[[columns]]` entries of a TOML document, each
+    /// with a name and optional justification and width:
+    ///
+    /// ```toml
+    /// [[columns]]
+    /// name = "desc"
+    /// width = 40
+    /// ```
+    ///
+    /// # Errors
+    /// - If the document is not valid TOML or has no `[[columns]]` entries
+    /// - If an entry has an unknown key or an invalid value
+    #[cfg(feature = "toml")]
+    pub fn from_toml(text: &str) -> Result<Vec<Self>, String> {
+        let error = |e: String| format!("ColumnSpec::from_toml: {}", e);
+        let table = Table::from_toml(text, "columns").map_err(error)?;
+        let keys: Vec<&str> = table.columns().iter().map(Column::name).collect();
+        (0..table.row_count())
+            .filter_map(|idx| table.row(idx).map(|row| (idx, row)))
+            .map(|(idx, row)| {
+                // Keys missing from this entry are empty cells
+                let fields = keys
+                    .iter()
+                    .zip(row)
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(key, value)| (key.to_string(), value.clone()));
+                ColumnSpec::from_fields(fields).map_err(|e| error(format!("column {}: {}", idx, e)))
+            })
+            .collect()
+    }
+
+    #[cfg(any(feature = "json", feature = "toml"))]
+    fn from_fields(fields: impl IntoIterator<Item = (String, String)>) -> Result<Self, String> {
+        let mut name = None;
+        let mut column = ColumnSpec::new("");
+        for (key, value) in fields {
+            match key.as_str() {
+                "name" => name = Some(value),
+                "justification" => {
+                    column.justification = parse_justification(&value)
+                        .ok_or_else(|| format!("invalid justification '{}'", value))?;
+                }
+                "width" => column.width = value.parse().map_err(|_| format!("invalid width '{}'", value))?,
+                _ => return Err(format!("unknown key '{}'", key)),
+            }
+        }
+        column.name = name.filter(|n| !n.is_empty()).ok_or("missing name")?;
+        Ok(column)
+    }
+
+    /// Creates the column this spec describes
+    ///
+    /// # Errors
+    /// - If the name is empty or the width too large (see Column::new)
+    pub fn to_column(&self) -> Result<Column, String> {
+        Column::new(self.name.clone(), self.width, self.justification)
+    }
+}
+
+fn parse_justification(text: &str) -> Option<Justification> {
+    match text.to_ascii_lowercase().as_str() {
+        "left" | "l" => Some(Justification::Left),
+        "right" | "r" => Some(Justification::Right),
+        "center" | "centre" | "c" => Some(Justification::Center),
+        "decimal" | "d" => Some(Justification::Decimal),
+        _ => None,
+    }
+}
+
+impl Table {
+    /// Creates an empty table from a comma-separated column spec such as
+    /// "name:left, desc:left:40, amount:right" (see ColumnSpec::parse)
+    ///
+    /// # Errors
+    /// - If any spec is invalid or describes an invalid column
+    pub fn from_spec(spec: &str) -> Result<Table, String> {
+        Table::from_column_specs(&ColumnSpec::parse_list(spec)?)
+    }
+
+    /// Creates an empty table with a column for each spec
+    ///
+    /// # Errors
+    /// - If specs is empty or any spec describes an invalid column
+    pub fn from_column_specs(specs: &[ColumnSpec]) -> Result<Table, String> {
+        Table::new(specs.iter().map(ColumnSpec::to_column).collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_spec() {
+        let table = Table::from_spec("name:left, desc:left:40, amount:right").unwrap();
+        let columns = table.columns();
+        assert_eq!(columns.len(), 3);
+        assert_eq!((columns[1].name(), columns[1].truncate_at), ("desc", 40));
+        assert_eq!(columns[2].justification(), Justification::Right);
+
+        assert_eq!(
+            ColumnSpec::parse("id:12:R").unwrap(),
+            ColumnSpec { name: "id".to_string(), justification: Justification::Right, width: 12 }
+        );
+        assert_eq!(ColumnSpec::parse("state:c").unwrap().justification, Justification::Center);
+        assert!(ColumnSpec::parse(":left").is_err());
+        assert_eq!(
+            Table::from_spec("a, b:wide").unwrap_err(),
+            "ColumnSpec::parse: 'wide' in ' b:wide' is not a justification or width"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "json", feature = "toml"))]
+    fn test_column_specs_from_config() {
+        let expected = vec![
+            ColumnSpec { name: "desc".to_string(), justification: Justification::Left, width: 40 },
+            ColumnSpec { name: "amount".to_string(), justification: Justification::Right, width: 0 },
+        ];
+        let json = r#"{"columns": [{"name": "desc", "width": 40}, {"name": "amount", "justification": "right"}]}"#;
+        assert_eq!(ColumnSpec::from_json(json).unwrap(), expected);
+
+        let toml = "[[columns]]\nname = \"desc\"\nwidth = 40\n\n[[columns]]\nname = \"amount\"\njustification = \"right\"\n";
+        assert_eq!(ColumnSpec::from_toml(toml).unwrap(), expected);
+
+        assert_eq!(
+            ColumnSpec::from_json(r#"[{"name": "a", "widht": 3}]"#).unwrap_err(),
+            "ColumnSpec::from_json: column 0: unknown key 'widht'"
+        );
+        assert_eq!(
+            ColumnSpec::from_toml("[[columns]]\nwidth = 3\n").unwrap_err(),
+            "ColumnSpec::from_toml: column 0: missing name"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{OutputFormat, Table};
+
+/// Quotes an identifier with double quotes (doubling any inside). Dotted
+/// names such as "app.users" are quoted part by part.
+fn quote_identifier(name: &str) -> String {
+    name.split('.').map(|part| format!("\"{}\"", part.replace('"', "\"\""))).collect::<Vec<_>>().join(".")
+}
+
+/// Quotes a value as a string literal, doubling any single quotes inside
+fn quote_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+impl Table {
+    /// Renders the rows as SQL INSERT statements, one per line, for turning data
+    /// into reproducible fixtures: `INSERT INTO "table" ("a", "b") VALUES ('1', 'x');`.
+    /// Identifiers are double-quoted (a dotted table name is quoted part by part)
+    /// and columns use their SQL alias or name. Every value is written as a string
+    /// literal, which databases convert to the column type on insert. A table with
+    /// no rows gives an empty string.
+    ///
+    /// # Errors
+    /// - If the table name is empty or has an empty part
+    pub fn to_sql_inserts(&self, table_name: &str) -> Result<String, String> {
+        if table_name.split('.').any(str::is_empty) {
+            return Err(format!("Table::to_sql_inserts: invalid table name '{}'", table_name));
+        }
+        let columns: Vec<String> = self.columns.iter().map(|col| quote_identifier(col.name_for(OutputFormat::Sql))).collect();
+        let prefix = format!("INSERT INTO {} ({}) VALUES (", quote_identifier(table_name), columns.join(", "));
+        let mut out = String::new();
+        for row in &self.rows {
+            let values: Vec<String> = row.cells.iter().enumerate().map(|(idx, cell)| quote_value(&self.redacted(idx, cell))).collect();
+            out.push_str(&prefix);
+            out.push_str(&values.join(", "));
+            out.push_str(");\n");
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_sql_inserts() {
+        let mut table = Table::new(vec![
+            Column::new("User \"id\"", 0, Justification::Right).unwrap(),
+            Column::new("Name", 0, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.columns[1].set_alias(OutputFormat::Sql, "name");
+        table.add_row(vec!["1".to_string(), "O'Brien".to_string()]).unwrap();
+        table.add_row(vec!["2".to_string(), String::new()]).unwrap();
+        assert_eq!(
+            table.to_sql_inserts("app.users").unwrap(),
+            "INSERT INTO \"app\".\"users\" (\"User \"\"id\"\"\", \"name\") VALUES ('1', 'O''Brien');\n\
+             INSERT INTO \"app\".\"users\" (\"User \"\"id\"\"\", \"name\") VALUES ('2', '');\n"
+        );
+        assert!(table.to_sql_inserts("").is_err());
+        assert!(table.to_sql_inserts("app.").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt::Write;
+
+use crate::{escape_xml, Table};
+
+/// Appearance settings for Table::to_svg. Colors are any SVG color value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Font size in pixels
+    pub font_size: f64,
+    /// Font family; should be monospace so columns line up
+    pub font_family: String,
+    /// Width of one character as a fraction of the font size
+    pub char_width: f64,
+    /// Line height as a multiple of the font size
+    pub line_height: f64,
+    /// Space around the table in pixels
+    pub padding: f64,
+    pub background: String,
+    pub text_color: String,
+    /// Background behind the header row
+    pub header_background: Option<String>,
+    /// Background for every other data row
+    pub stripe_background: Option<String>,
+    /// Color of the rule under the header
+    pub rule_color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            font_size: 14.0,
+            font_family: "monospace".to_string(),
+            char_width: 0.6,
+            line_height: 1.4,
+            padding: 8.0,
+            background: "#ffffff".to_string(),
+            text_color: "#000000".to_string(),
+            header_background: Some("#f0f0f0".to_string()),
+            stripe_background: Some("#f7f7f7".to_string()),
+            rule_color: "#888888".to_string(),
+        }
+    }
+}
+
+impl Table {
+    /// Renders the table as a standalone SVG image of monospace text, laid out with
+    /// the same widths, justification, and truncation as the text renderer.
+    /// A "background" entry in row or cell metadata (cell wins) fills that row or
+    /// cell, which allows heatmaps; otherwise rows alternate with the stripe color.
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let layout = self.layout();
+        let char_w = options.font_size * options.char_width;
+        let line_h = options.font_size * options.line_height;
+        let pad = options.padding;
+
+        // Left edge of each column in pixels (columns are separated by one space)
+        let mut xs = Vec::with_capacity(layout.widths.len());
+        let mut chars = 0;
+        for &width in &layout.widths {
+            xs.push(pad + chars as f64 * char_w);
+            chars += width + 1;
+        }
+        let table_w = layout.content_len() as f64 * char_w;
+
+        let mut body = String::new();
+        let mut y = pad;
+
+        // Header
+        let header_rows = self.header_rows(&layout);
+        if let Some(bg) = &options.header_background {
+            let _ = writeln!(body, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                pad, y, table_w, line_h * header_rows.len() as f64, escape_xml(bg));
+        }
+        for header in &header_rows {
+            for (text, &x) in header.iter().zip(&xs) {
+                write_text(&mut body, x, y + line_h * 0.75, text, true);
+            }
+            y += line_h;
+        }
+        let _ = writeln!(body, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"/>"#,
+            pad, y, pad + table_w, y, escape_xml(&options.rule_color));
+
+        // Rows
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            let cells: Vec<Vec<String>> = row.cells
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| self.cell_lines(&layout, idx, value))
+                .collect();
+            let lines = cells.iter().map(Vec::len).max().unwrap_or(1);
+            let row_h = lines as f64 * line_h;
+
+            let row_bg = row.metadata.get("background")
+                .or(if row_idx % 2 == 1 { options.stripe_background.as_ref() } else { None });
+            if let Some(bg) = row_bg {
+                let _ = writeln!(body, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    pad, y, table_w, row_h, escape_xml(bg));
+            }
+            for (col_idx, ((cell, &x), &width)) in cells.iter().zip(&xs).zip(&layout.widths).enumerate() {
+                if let Some(bg) = row.cell_metadata.get(&col_idx).and_then(|m| m.get("background")) {
+                    let _ = writeln!(body, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                        x, y, width as f64 * char_w, row_h, escape_xml(bg));
+                }
+                for (line_idx, line) in cell.iter().enumerate() {
+                    write_text(&mut body, x, y + line_idx as f64 * line_h + line_h * 0.75, line, false);
+                }
+            }
+            y += row_h;
+        }
+
+        let width = table_w + pad * 2.0;
+        let height = y + pad;
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#, "\n",
+                r#"<rect width="100%" height="100%" fill="{bg}"/>"#, "\n",
+                r#"<g font-family="{font}" font-size="{size}" fill="{fg}" xml:space="preserve">"#, "\n",
+                "{body}</g>\n</svg>\n"
+            ),
+            w = width,
+            h = height,
+            bg = escape_xml(&options.background),
+            font = escape_xml(&options.font_family),
+            size = options.font_size,
+            fg = escape_xml(&options.text_color),
+            body = body,
+        )
+    }
+}
+
+/// Writes one line of cell text at the given baseline position.
+/// Leading padding is kept (xml:space="preserve") so right justification holds.
+fn write_text(out: &mut String, x: f64, y: f64, text: &str, bold: bool) {
+    let text = text.trim_end();
+    if text.is_empty() {
+        return;
+    }
+    let weight = if bold { r#" font-weight="bold""# } else { "" };
+    let _ = writeln!(out, r#"<text x="{}" y="{}"{}>{}</text>"#, x, y, weight, escape_xml(text));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_to_svg() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Load", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        table.add_row(vec!["a<b>".to_string(), "12".to_string()]).unwrap();
+        table.add_row(vec!["two\nlines".to_string(), "7".to_string()]).unwrap();
+        table.set_cell_metadata(0, 1, "background", "#ff0000").unwrap();
+
+        let svg = table.to_svg(&SvgOptions::default());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">a&lt;b&gt;</text>"));
+        assert!(svg.contains("fill=\"#ff0000\""));
+        assert!(svg.contains(">Load</text>"));
+        assert!(svg.contains(">lines</text>"));
+        // Stripe on the second row only
+        assert_eq!(svg.matches("fill=\"#f7f7f7\"").count(), 1);
+    }
+}
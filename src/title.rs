@@ -0,0 +1,92 @@
+use crate::{push_justified, text_width, truncate_line, Justification, Layout, Table};
+
+/// How the title is drawn above the table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TitleStyle {
+    /// Placement of the title in the table width (Decimal places it like Right)
+    pub justification: Justification,
+    /// Draw a line of this character across the table under the title
+    pub underline: Option<char>,
+}
+
+impl Default for TitleStyle {
+    fn default() -> Self {
+        TitleStyle { justification: Justification::Center, underline: None }
+    }
+}
+
+/// A caption drawn above the header
+#[derive(Debug, Clone)]
+pub(crate) struct Title {
+    text: String,
+    style: TitleStyle,
+}
+
+impl Table {
+    /// Sets a title drawn centered above the table by the text renderer. Lines
+    /// of multiline text are drawn one beneath the other, and text wider than
+    /// the table is cut to its width.
+    pub fn set_title(&mut self, text: &str) {
+        self.set_title_with(text, TitleStyle::default());
+    }
+
+    /// Sets a title drawn with the given style (see set_title)
+    pub fn set_title_with(&mut self, text: &str, style: TitleStyle) {
+        self.title = Some(Title { text: text.to_string(), style });
+    }
+
+    /// Returns the title text, if any
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(|title| title.text.as_str())
+    }
+
+    /// Removes the title
+    pub fn clear_title(&mut self) {
+        self.title = None;
+    }
+
+    pub(crate) fn write_title(&self, f: &mut std::fmt::Formatter, layout: &Layout) -> std::fmt::Result {
+        let Some(title) = &self.title else {
+            return Ok(());
+        };
+        let width = layout.line_len();
+        let mut line = String::with_capacity(width);
+        for text in title.text.split('\n') {
+            let text = truncate_line(text, width);
+            line.clear();
+            push_justified(&mut line, &text, width - text_width(&text), title.style.justification, 0);
+            self.write_line(f, &line)?;
+        }
+        if let Some(underline) = title.style.underline {
+            self.write_line(f, &String::from_iter(std::iter::repeat_n(underline, width)))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of lines the title adds to a render
+    pub(crate) fn title_lines(&self) -> usize {
+        self.title.as_ref().map_or(0, |title| title.text.split('\n').count() + usize::from(title.style.underline.is_some()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BorderStyle;
+
+    #[test]
+    fn test_title() {
+        let mut table = Table::with_headers(["Host", "Status"]).unwrap();
+        table.add_row(vec!["web-1".to_string(), "up".to_string()]).unwrap();
+        table.set_title("Fleet");
+        assert_eq!(table.to_string(), "   Fleet    \nHost  Status\n===== ======\nweb-1 up    \n");
+        assert_eq!(table.title(), Some("Fleet"));
+
+        table.set_border(BorderStyle::Ascii);
+        let style = TitleStyle { justification: Justification::Left, underline: Some('~') };
+        table.set_title_with("Fleet status report", style);
+        assert!(table.to_string().starts_with("Fleet status re...\n~~~~~~~~~~~~~~~~~~\n+-------+--------+\n"));
+        assert_eq!(table.header_line_count(), 5);
+        assert_eq!(table.estimated_render_len(), table.to_string().len());
+    }
+}
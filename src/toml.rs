@@ -0,0 +1,586 @@
+use crate::{Column, Justification, Table};
+
+/// A parsed TOML value. Numbers and dates keep their source text (minus digit
+/// separators) so cell values match what was written.
+#[derive(Debug, Clone, PartialEq)]
+enum TomlValue {
+    String(String),
+    Bool(bool),
+    /// Integers, floats, and dates/times
+    Scalar(String),
+    Array(Vec<TomlValue>),
+    Table(Vec<(String, TomlValue)>),
+}
+
+impl TomlValue {
+    /// Converts the value to cell text: strings are used as-is and arrays and
+    /// inline tables are written back as inline TOML
+    fn to_cell(&self) -> String {
+        match self {
+            TomlValue::String(s) => s.clone(),
+            _ => {
+                let mut out = String::new();
+                self.write_inline(&mut out);
+                out
+            }
+        }
+    }
+
+    fn write_inline(&self, out: &mut String) {
+        match self {
+            TomlValue::String(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\t' => out.push_str("\\t"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            TomlValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            TomlValue::Scalar(s) => out.push_str(s),
+            TomlValue::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write_inline(out);
+                }
+                out.push(']');
+            }
+            TomlValue::Table(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    out.push_str(if i > 0 { ", " } else { " " });
+                    out.push_str(key);
+                    out.push_str(" = ");
+                    value.write_inline(out);
+                }
+                out.push_str(if fields.is_empty() { "}" } else { " }" });
+            }
+        }
+    }
+}
+
+/// One statement of a TOML document
+#[derive(Debug, PartialEq)]
+enum Item {
+    /// [a.b]
+    Table(Vec<String>),
+    /// [[a.b]]
+    ArrayTable(Vec<String>),
+    /// a.b = value
+    KeyValue(Vec<String>, TomlValue),
+}
+
+/// Deepest nesting of arrays and inline tables the parser accepts
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Number of arrays and inline tables enclosing the current position
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.text[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.text[..self.pos].matches('\n').count() + 1
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        format!("line {}: {}", self.line(), message)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(format_args!("expected '{}' but found '{}'", expected, c))),
+            None => Err(self.error(format_args!("expected '{}' but reached end of input", expected))),
+        }
+    }
+
+    /// Skips spaces and tabs
+    fn skip_blank(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace, newlines, and comments
+    fn skip_all(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\n' | '\r') => self.pos += 1,
+                Some('#') => self.skip_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.find('\n').unwrap_or(rest.len());
+    }
+
+    /// Requires the rest of the line to be blank or a comment
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_blank();
+        if self.peek() == Some('#') {
+            self.skip_comment();
+        }
+        self.eat("\r");
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format_args!("unexpected '{}' after value", c))),
+        }
+    }
+
+    fn items(&mut self) -> Result<Vec<Item>, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_all();
+            if self.peek().is_none() {
+                return Ok(items);
+            }
+            let item = if self.eat("[[") {
+                let path = self.key_path()?;
+                if !self.eat("]]") {
+                    return Err(self.error("expected ']]' to close the table header"));
+                }
+                Item::ArrayTable(path)
+            } else if self.eat("[") {
+                let path = self.key_path()?;
+                self.expect(']')?;
+                Item::Table(path)
+            } else {
+                let path = self.key_path()?;
+                self.expect('=')?;
+                self.skip_blank();
+                Item::KeyValue(path, self.value()?)
+            };
+            items.push(item);
+            self.end_of_line()?;
+        }
+    }
+
+    /// Parses a dotted key such as `a."b.c".d`
+    fn key_path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_blank();
+            let key = match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    self.basic_string()?
+                }
+                Some('\'') => {
+                    self.pos += 1;
+                    self.literal_string()?
+                }
+                _ => {
+                    let rest = &self.text[self.pos..];
+                    let len = rest
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                        .unwrap_or(rest.len());
+                    if len == 0 {
+                        return Err(self.error("expected a key"));
+                    }
+                    self.pos += len;
+                    rest[..len].to_string()
+                }
+            };
+            path.push(key);
+            self.skip_blank();
+            if !self.eat(".") {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<TomlValue, String> {
+        if self.eat("\"\"\"") {
+            return self.multiline_string(true).map(TomlValue::String);
+        }
+        if self.eat("'''") {
+            return self.multiline_string(false).map(TomlValue::String);
+        }
+        match self.peek() {
+            None => Err(self.error("expected a value")),
+            Some('"') => {
+                self.pos += 1;
+                self.basic_string().map(TomlValue::String)
+            }
+            Some('\'') => {
+                self.pos += 1;
+                self.literal_string().map(TomlValue::String)
+            }
+            Some('[' | '{') if self.depth == MAX_DEPTH => {
+                Err(self.error(format_args!("values nested more than {} deep", MAX_DEPTH)))
+            }
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::inline_table),
+            Some(_) => {
+                let rest = &self.text[self.pos..];
+                let mut len = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}' | '#'))
+                    .unwrap_or(rest.len());
+                // A date may be followed by a space and a time: 1979-05-27 07:32:00Z
+                let is_date = rest.len() >= 10 && rest.as_bytes()[4] == b'-' && rest.as_bytes()[7] == b'-';
+                if is_date && len == 10 && rest.as_bytes().get(10) == Some(&b' ') && rest[11..].starts_with(|c: char| c.is_ascii_digit()) {
+                    len = 11 + rest[11..]
+                        .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}' | '#'))
+                        .unwrap_or(rest.len() - 11);
+                }
+                let token = &rest[..len];
+                self.pos += len;
+                match token {
+                    "true" => Ok(TomlValue::Bool(true)),
+                    "false" => Ok(TomlValue::Bool(false)),
+                    "" => Err(self.error("expected a value")),
+                    t if t.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-'))
+                        || matches!(t, "inf" | "nan") =>
+                    {
+                        Ok(TomlValue::Scalar(if is_date { t.to_string() } else { t.replace('_', "") }))
+                    }
+                    t => Err(self.error(format_args!("invalid value '{}'", t))),
+                }
+            }
+        }
+    }
+
+    /// Parses an array or inline table one level deeper
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<TomlValue, String>) -> Result<TomlValue, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn array(&mut self) -> Result<TomlValue, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_all();
+            if self.eat("]") {
+                return Ok(TomlValue::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_all();
+            match self.bump() {
+                Some(',') => continue,
+                Some(']') => return Ok(TomlValue::Array(items)),
+                Some(c) => return Err(self.error(format_args!("expected ',' or ']' but found '{}'", c))),
+                None => return Err(self.error("unterminated array")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<TomlValue, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_blank();
+        if self.eat("}") {
+            return Ok(TomlValue::Table(fields));
+        }
+        loop {
+            let key = self.key_path()?.join(".");
+            self.expect('=')?;
+            self.skip_blank();
+            fields.push((key, self.value()?));
+            self.skip_blank();
+            match self.bump() {
+                Some(',') => continue,
+                Some('}') => return Ok(TomlValue::Table(fields)),
+                Some(c) => return Err(self.error(format_args!("expected ',' or '}}' but found '{}'", c))),
+                None => return Err(self.error("unterminated inline table")),
+            }
+        }
+    }
+
+    /// Parses a "..." string after its opening quote
+    fn basic_string(&mut self) -> Result<String, String> {
+        let mut result = String::new();
+        loop {
+            match self.peek() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(result);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    result.push(self.escape()?);
+                }
+                Some(c) => {
+                    self.pos += c.len_utf8();
+                    result.push(c);
+                }
+            }
+        }
+    }
+
+    /// Parses a '...' string after its opening quote
+    fn literal_string(&mut self) -> Result<String, String> {
+        let rest = &self.text[self.pos..];
+        match rest.find(['\'', '\n']) {
+            Some(end) if rest[end..].starts_with('\'') => {
+                self.pos += end + 1;
+                Ok(rest[..end].to_string())
+            }
+            _ => Err(self.error("unterminated string")),
+        }
+    }
+
+    /// Parses a """...""" or '''...''' string after its opening quotes.
+    /// A newline right after the opening quotes is dropped, as is a backslash at the
+    /// end of a line in a basic string along with the whitespace that follows it.
+    fn multiline_string(&mut self, basic: bool) -> Result<String, String> {
+        let delimiter = if basic { "\"\"\"" } else { "'''" };
+        if !self.eat("\r\n") {
+            self.eat("\n");
+        }
+        let mut result = String::new();
+        loop {
+            if self.eat(delimiter) {
+                // Up to two quotes may directly precede the closing delimiter
+                let quote = delimiter.chars().next().unwrap_or('"');
+                for _ in 0..2 {
+                    if self.peek() == Some(quote) {
+                        self.pos += 1;
+                        result.push(quote);
+                    }
+                }
+                return Ok(result);
+            }
+            match self.bump() {
+                None => return Err(self.error("unterminated multiline string")),
+                Some('\\') if basic => {
+                    let rest = &self.text[self.pos..];
+                    let blank = rest.find(|c: char| !matches!(c, ' ' | '\t' | '\r')).unwrap_or(rest.len());
+                    if rest[blank..].starts_with('\n') {
+                        self.pos += blank;
+                        self.skip_all_whitespace();
+                    } else {
+                        result.push(self.escape()?);
+                    }
+                }
+                Some(c) => result.push(c),
+            }
+        }
+    }
+
+    fn skip_all_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Parses the character of an escape sequence after its backslash
+    fn escape(&mut self) -> Result<char, String> {
+        let digits = match self.bump() {
+            Some('"') => return Ok('"'),
+            Some('\\') => return Ok('\\'),
+            Some('b') => return Ok('\u{8}'),
+            Some('f') => return Ok('\u{c}'),
+            Some('n') => return Ok('\n'),
+            Some('r') => return Ok('\r'),
+            Some('t') => return Ok('\t'),
+            Some('e') => return Ok('\u{1b}'),
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("invalid escape sequence")),
+        };
+        let hex = self.text.get(self.pos..self.pos + digits).unwrap_or("");
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == digits)
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += digits;
+        Ok(c)
+    }
+}
+
+/// Parses a TOML document into its key/value pairs in document order, with keys
+/// under `[table]` headers dotted ("table.key") and values as cell text. Used
+/// for flat settings files such as render profiles.
+#[cfg(feature = "profile")]
+pub(crate) fn key_values(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut prefix: Vec<String> = Vec::new();
+    let mut pairs = Vec::new();
+    for item in (Parser { text, pos: 0, depth: 0 }).items()? {
+        match item {
+            Item::Table(path) => prefix = path,
+            Item::ArrayTable(path) => return Err(format!("unexpected array of tables [[{}]]", path.join("."))),
+            Item::KeyValue(path, value) => {
+                pairs.push((prefix.iter().chain(&path).cloned().collect::<Vec<_>>().join("."), value.to_cell()));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+impl Table {
+    /// Loads a TOML array of tables (`[[name]]` entries) into a table, one row per
+    /// entry. The name may be dotted (e.g. "servers.hosts") to reach a nested array.
+    /// Columns are the union of keys in first-seen order (left-justified, not
+    /// truncated) and missing keys become empty cells. Keys of sub-tables and dotted
+    /// keys within an entry are flattened into names like "owner.name". Strings are
+    /// used as-is, numbers, booleans, and dates keep their TOML text, and arrays and
+    /// inline tables are written as inline TOML.
+    ///
+    /// # Errors
+    /// - If the document is not valid TOML (within the supported syntax)
+    /// - If there are no `[[name]]` entries
+    /// - If the rows exceed the table limits
+    pub fn from_toml(text: &str, name: &str) -> Result<Table, String> {
+        let target: Vec<String> = name.split('.').map(|s| s.trim().to_string()).collect();
+        let items = Parser { text, pos: 0, depth: 0 }
+            .items()
+            .map_err(|e| format!("Table::from_toml: {}", e))?;
+
+        let mut keys: Vec<String> = Vec::new();
+        let mut records: Vec<Vec<(usize, String)>> = Vec::new();
+        // Key prefix within the current entry, or None outside the target array
+        let mut prefix: Option<Vec<String>> = None;
+
+        for item in items {
+            match item {
+                Item::ArrayTable(path) if path == target => {
+                    records.push(Vec::new());
+                    prefix = Some(Vec::new());
+                }
+                Item::Table(path) | Item::ArrayTable(path)
+                    if !records.is_empty() && path.len() > target.len() && path.starts_with(&target) =>
+                {
+                    prefix = Some(path[target.len()..].to_vec());
+                }
+                Item::Table(_) | Item::ArrayTable(_) => prefix = None,
+                Item::KeyValue(path, value) => {
+                    let (Some(prefix), Some(record)) = (&prefix, records.last_mut()) else { continue };
+                    let key = prefix.iter().chain(&path).cloned().collect::<Vec<_>>().join(".");
+                    let pos = match keys.iter().position(|k| *k == key) {
+                        Some(pos) => pos,
+                        None => {
+                            keys.push(key);
+                            keys.len() - 1
+                        }
+                    };
+                    record.push((pos, value.to_cell()));
+                }
+            }
+        }
+
+        if records.is_empty() {
+            return Err(format!("Table::from_toml: no [[{}]] entries found", name));
+        }
+        if keys.is_empty() {
+            return Err(format!("Table::from_toml: [[{}]] entries have no keys", name));
+        }
+
+        let columns = keys
+            .into_iter()
+            .map(|key| Column::new(key, 0, Justification::Left))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut table = Table::new(columns)?;
+        let column_count = table.columns.len();
+        for record in records {
+            let mut row = vec![String::new(); column_count];
+            for (pos, value) in record {
+                row[pos] = value;
+            }
+            table.add_row(row)?;
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let text = r#"
+# Service inventory
+title = "ignored"
+
+[[items]]
+name = "api"   # trailing comment
+port = 8_080
+tags = ["web", 'public']
+limits = { cpu = 2, mem = "1G" }
+
+[items.owner]
+team = "platform"
+
+[[items]]
+name = """
+multi \
+  line"""
+enabled = false
+started = 1979-05-27 07:32:00Z
+'quoted.key' = 'C:\path'
+
+[other]
+name = "not an item"
+"#;
+        let table = Table::from_toml(text, "items").unwrap();
+        let names: Vec<&str> = table.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["name", "port", "tags", "limits", "owner.team", "enabled", "started", "quoted.key"]);
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.row(0).unwrap(), [
+            "api", "8080", r#"["web", "public"]"#, r#"{ cpu = 2, mem = "1G" }"#, "platform", "", "", "",
+        ]);
+        assert_eq!(table.row(1).unwrap(), [
+            "multi line", "", "", "", "", "false", "1979-05-27 07:32:00Z", r"C:\path",
+        ]);
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        assert!(Table::from_toml("[[things]]\na = 1\n", "items").unwrap_err().contains("no [[items]]"));
+        let err = Table::from_toml("[[items]]\na = \"open\n", "items").unwrap_err();
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(Table::from_toml("[[items]]\na = 1 2\n", "items").is_err());
+
+        let deep = format!("[[items]]\na = {}", "[".repeat(100_000));
+        assert!(Table::from_toml(&deep, "items").unwrap_err().contains("nested more than 128 deep"));
+        let deep = format!("[[items]]\na = {}", "{ b = ".repeat(100_000));
+        assert!(Table::from_toml(&deep, "items").unwrap_err().contains("nested more than 128 deep"));
+        let ok = format!("[[items]]\na = {}1{}\n", "[".repeat(128), "]".repeat(128));
+        assert!(Table::from_toml(&ok, "items").is_ok());
+
+        let nested = Table::from_toml("[[servers.hosts]]\nip = \"10.0.0.1\"\n", "servers.hosts").unwrap();
+        assert_eq!(nested.row(0).unwrap(), ["10.0.0.1"]);
+    }
+}
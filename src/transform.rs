@@ -0,0 +1,362 @@
+use crate::regex::Regex;
+use crate::{Column, Table, MAX_CELL_LINES};
+
+/// Checks a new cell value against the per-cell line limit
+fn check_lines(method: &str, value: &str) -> Result<(), String> {
+    let line_count = value.split('\n').count();
+    if line_count > MAX_CELL_LINES {
+        return Err(format!(
+            "Table::{}: cell contains {} lines, exceeding maximum allowed ({})",
+            method, line_count, MAX_CELL_LINES
+        ));
+    }
+    Ok(())
+}
+
+impl Table {
+    /// Replaces every value of the named column with the result of f, e.g.
+    /// `table.map_column("Path", |v| shorten(v))`. The column width is recomputed
+    /// and rows whose value changed are marked as changed.
+    ///
+    /// # Errors
+    /// - If there is no column with the name
+    /// - If a new value contains more than MAX_CELL_LINES (5000) lines; the table
+    ///   is left unchanged
+    pub fn map_column(&mut self, column: &str, mut f: impl FnMut(&str) -> String) -> Result<(), String> {
+        let idx = self.column_index(column)
+            .ok_or_else(|| format!("Table::map_column: no column named '{}'", column))?;
+        let values = self.rows
+            .iter()
+            .map(|row| {
+                let value = f(&row.cells[idx]);
+                check_lines("map_column", &value)?;
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let generation = self.next_generation();
+        for (row, value) in self.rows.iter_mut().zip(values) {
+            if row.cells[idx] != value {
+                row.cells[idx] = value;
+                row.changed = generation;
+            }
+        }
+        self.rescan_width(idx);
+        Ok(())
+    }
+
+    /// Calls f with the cells of every row to change them in place, for transforms
+    /// that need several columns at once. Column widths are recomputed and rows
+    /// whose cells changed are marked as changed.
+    ///
+    /// # Errors
+    /// - If a new value contains more than MAX_CELL_LINES (5000) lines; the table
+    ///   is left unchanged
+    pub fn map_rows(&mut self, mut f: impl FnMut(&mut [String])) -> Result<(), String> {
+        let rows = self.rows
+            .iter()
+            .map(|row| {
+                let mut cells = row.cells.clone();
+                f(&mut cells);
+                cells.iter().try_for_each(|value| check_lines("map_rows", value))?;
+                Ok(cells)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let generation = self.next_generation();
+        for (row, cells) in self.rows.iter_mut().zip(rows) {
+            if row.cells != cells {
+                row.cells = cells;
+                row.changed = generation;
+            }
+        }
+        for idx in 0..self.columns.len() {
+            self.rescan_width(idx);
+        }
+        Ok(())
+    }
+
+    /// Replaces a column with new columns named by names, splitting each value on
+    /// the delimiter, e.g. `table.split_column("Endpoint", '/', &["Service", "Route"])`.
+    /// The last new column keeps the rest of the value (including any further
+    /// delimiters) and values with fewer parts leave the remaining columns empty.
+    /// New columns take the original's justification; metadata of its cells is dropped.
+    ///
+    /// # Errors
+    /// - If there is no column with the name
+    /// - If names is empty or any name is empty
+    pub fn split_column(&mut self, column: &str, delimiter: char, names: &[&str]) -> Result<(), String> {
+        let (idx, columns) = self.split_target("split_column", column, names)?;
+        let values = self.rows
+            .iter()
+            .map(|row| {
+                let mut parts: Vec<String> = row.cells[idx].splitn(names.len(), delimiter).map(str::to_string).collect();
+                parts.resize(names.len(), String::new());
+                parts
+            })
+            .collect();
+        self.splice_columns(&[idx], idx, columns, values, Vec::new());
+        Ok(())
+    }
+
+    /// Replaces a column with new columns holding the capture groups of a pattern,
+    /// one per name, e.g. `split_column_regex("Endpoint", r"^/(\w+)/(.*)$", &["Service", "Route"])`.
+    /// Groups that don't participate in the match are empty. Values that don't
+    /// match are kept whole in the first new column.
+    ///
+    /// # Errors
+    /// - If there is no column with the name
+    /// - If names is empty or any name is empty
+    /// - If the pattern is invalid or doesn't have one capturing group per name
+    pub fn split_column_regex(&mut self, column: &str, pattern: &str, names: &[&str]) -> Result<(), String> {
+        let re = Regex::new(pattern)
+            .map_err(|e| format!("Table::split_column_regex: invalid pattern '{}': {}", pattern, e))?;
+        if re.group_count() != names.len() {
+            return Err(format!(
+                "Table::split_column_regex: pattern has {} capturing groups for {} column names",
+                re.group_count(),
+                names.len()
+            ));
+        }
+        let (idx, columns) = self.split_target("split_column_regex", column, names)?;
+        let values = self.rows
+            .iter()
+            .map(|row| {
+                let value = &row.cells[idx];
+                match re.captures_at(value, 0) {
+                    Some(groups) => groups[1..]
+                        .iter()
+                        .map(|group| group.map_or_else(String::new, |(start, end)| value[start..end].to_string()))
+                        .collect(),
+                    None => {
+                        let mut parts = vec![String::new(); names.len()];
+                        parts[0] = value.clone();
+                        parts
+                    }
+                }
+            })
+            .collect();
+        self.splice_columns(&[idx], idx, columns, values, Vec::new());
+        Ok(())
+    }
+
+    /// Replaces several columns with one whose values join theirs with a separator,
+    /// e.g. `table.merge_columns(&["First", "Last"], "Name", " ")`. Values are joined
+    /// in the order the columns are listed. See merge_columns_with.
+    ///
+    /// # Errors
+    /// - As for merge_columns_with
+    pub fn merge_columns(&mut self, columns: &[&str], name: &str, separator: &str) -> Result<(), String> {
+        self.merge_columns_with(columns, name, |values| values.join(separator))
+    }
+
+    /// Replaces several columns with one whose values are computed by f from
+    /// theirs (in the order the columns are listed). The new column takes the
+    /// place of the leftmost source and the justification of the first listed;
+    /// metadata of the source cells is dropped.
+    ///
+    /// # Errors
+    /// - If columns is empty, names a column twice, or names a missing column
+    /// - If name is empty
+    /// - If a new value contains more than MAX_CELL_LINES (5000) lines; the table
+    ///   is left unchanged
+    pub fn merge_columns_with(
+        &mut self,
+        columns: &[&str],
+        name: &str,
+        mut f: impl FnMut(&[&str]) -> String,
+    ) -> Result<(), String> {
+        let indices = columns
+            .iter()
+            .map(|column| {
+                self.column_index(column)
+                    .ok_or_else(|| format!("Table::merge_columns: no column named '{}'", column))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut remove = indices.clone();
+        remove.sort_unstable();
+        remove.dedup();
+        if remove.is_empty() {
+            return Err("Table::merge_columns: no columns given".to_string());
+        }
+        if remove.len() != indices.len() {
+            return Err("Table::merge_columns: a column is listed more than once".to_string());
+        }
+        let column = Column::new(name, 0, self.columns[indices[0]].justification)
+            .map_err(|e| format!("Table::merge_columns: {}", e))?;
+
+        let values = self.rows
+            .iter()
+            .map(|row| {
+                let parts: Vec<&str> = indices.iter().map(|&idx| row.cells[idx].as_str()).collect();
+                let value = f(&parts);
+                check_lines("merge_columns", &value)?;
+                Ok(vec![value])
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let footer = self.footer.as_ref().map(|footer| {
+            let parts: Vec<&str> = indices.iter().map(|&idx| footer[idx].as_str()).collect();
+            f(&parts)
+        });
+        self.splice_columns(&remove, remove[0], vec![column], values, footer.into_iter().collect());
+        Ok(())
+    }
+
+    /// Looks up the column to split and creates the columns replacing it
+    fn split_target(&self, method: &str, column: &str, names: &[&str]) -> Result<(usize, Vec<Column>), String> {
+        let idx = self.column_index(column)
+            .ok_or_else(|| format!("Table::{}: no column named '{}'", method, column))?;
+        if names.is_empty() {
+            return Err(format!("Table::{}: no column names given", method));
+        }
+        let justification = self.columns[idx].justification;
+        let columns = names
+            .iter()
+            .map(|name| Column::new(*name, 0, justification))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Table::{}: {}", method, e))?;
+        Ok((idx, columns))
+    }
+
+    /// Removes the columns at the (ascending) indices in remove and inserts columns
+    /// at position at of the remaining ones, with each row's values for them taken
+    /// from values and the footer's from footer (empty where it runs out).
+    /// Metadata of kept cells moves with its column; every row is marked as changed.
+    fn splice_columns(&mut self, remove: &[usize], at: usize, columns: Vec<Column>, values: Vec<Vec<String>>, footer: Vec<String>) {
+        // Old index of each column in the new layout, or None for inserted ones
+        let kept: Vec<usize> = (0..self.columns.len()).filter(|idx| !remove.contains(idx)).collect();
+        let layout: Vec<Option<usize>> = kept[..at]
+            .iter()
+            .map(|&idx| Some(idx))
+            .chain(std::iter::repeat_n(None, columns.len()))
+            .chain(kept[at..].iter().map(|&idx| Some(idx)))
+            .collect();
+
+        let mut old_columns: Vec<Option<Column>> = std::mem::take(&mut self.columns).into_iter().map(Some).collect();
+        let mut new_columns = columns.into_iter();
+        self.columns = layout
+            .iter()
+            .map(|old| match old {
+                Some(idx) => old_columns[*idx].take().expect("each column is kept once"),
+                None => new_columns.next().expect("one new column per inserted slot"),
+            })
+            .collect();
+
+        if let Some(old_footer) = self.footer.take() {
+            let mut footer = footer.into_iter();
+            self.footer = Some(
+                layout
+                    .iter()
+                    .map(|old| match old {
+                        Some(idx) => old_footer[*idx].clone(),
+                        None => footer.next().unwrap_or_default(),
+                    })
+                    .collect(),
+            );
+        }
+
+        let generation = self.next_generation();
+        for (row, values) in self.rows.iter_mut().zip(values) {
+            let mut old_cells: Vec<Option<String>> = std::mem::take(&mut row.cells).into_iter().map(Some).collect();
+            let mut values = values.into_iter();
+            row.cells = layout
+                .iter()
+                .map(|old| match old {
+                    Some(idx) => old_cells[*idx].take().unwrap_or_default(),
+                    None => values.next().unwrap_or_default(),
+                })
+                .collect();
+            row.cell_metadata = layout
+                .iter()
+                .enumerate()
+                .filter_map(|(new, old)| row.cell_metadata.remove(&(*old)?).map(|m| (new, m)))
+                .collect();
+            row.changed = generation;
+        }
+        for (idx, old) in layout.iter().enumerate() {
+            if old.is_none() {
+                self.rescan_width(idx);
+            }
+        }
+    }
+
+    /// Recomputes a column's longest value from the rows
+    fn rescan_width(&mut self, idx: usize) {
+        let col = &mut self.columns[idx];
+        col.max_length = self.rows.iter().map(|row| col.value_width(&row.cells[idx])).max().unwrap_or(0);
+        col.multiline = self.rows.iter().any(|row| row.cells[idx].contains('\n'));
+        col.width_stale = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_column_and_rows() {
+        let mut table = Table::with_headers(["Path", "Size"]).unwrap();
+        table.add_row(vec!["/home/user/projects/ctable/src/lib.rs".to_string(), "2048".to_string()]).unwrap();
+        table.add_row(vec!["README.md".to_string(), "512".to_string()]).unwrap();
+        let start = table.generation();
+
+        table.map_column("Path", |v| v.rsplit('/').next().unwrap_or(v).to_string()).unwrap();
+        assert_eq!(table.to_string(), "Path      Size\n========= ====\nlib.rs    2048\nREADME.md 512 \n");
+        assert_eq!(table.changed_rows_since(start), [0]);
+        assert!(table.map_column("Missing", str::to_string).is_err());
+
+        table.map_rows(|cells| cells[1] = format!("{} B", cells[1])).unwrap();
+        assert_eq!(table.row(1), Some(&["README.md".to_string(), "512 B".to_string()][..]));
+        assert!(table.to_string().starts_with("Path      Size  \n"));
+
+        let many_lines = "\n".repeat(MAX_CELL_LINES);
+        assert!(table.map_column("Size", |_| many_lines.clone()).is_err());
+        assert_eq!(table.row(0).unwrap()[1], "2048 B");
+    }
+
+    #[test]
+    fn test_merge_columns() {
+        let mut table = Table::with_headers(["Id", "First", "Age", "Last"]).unwrap();
+        table.add_row(vec!["1".to_string(), "Ada".to_string(), "36".to_string(), "Lovelace".to_string()]).unwrap();
+        table.add_row(vec!["2".to_string(), "Alan".to_string(), "41".to_string(), "Turing".to_string()]).unwrap();
+
+        table.merge_columns(&["Last", "First"], "Name", ", ").unwrap();
+        assert_eq!(
+            table.to_string(),
+            "Id Name          Age\n== ============= ===\n1  Lovelace, Ada 36 \n2  Turing, Alan  41 \n"
+        );
+
+        table.set_footer(vec!["2".to_string(), String::new(), "77".to_string()]).unwrap();
+        table.merge_columns_with(&["Id", "Age"], "Key", |parts| format!("{}-{}", parts[0], parts[1])).unwrap();
+        assert_eq!(table.row(1), Some(&["2-41".to_string(), "Turing, Alan".to_string()][..]));
+        assert_eq!(table.footer(), Some(&["2-77".to_string(), String::new()][..]));
+        assert!(table.merge_columns(&["Key", "Key"], "X", "").is_err());
+        assert!(table.merge_columns(&["Missing"], "X", "").is_err());
+    }
+
+    #[test]
+    fn test_split_column() {
+        let mut table = Table::with_headers(["Time", "Endpoint", "Status"]).unwrap();
+        table.add_row(vec!["10:00".to_string(), "users/list/all".to_string(), "200".to_string()]).unwrap();
+        table.add_row(vec!["10:01".to_string(), "health".to_string(), "500".to_string()]).unwrap();
+        table.set_cell_metadata(0, 2, "class", "ok").unwrap();
+        table.set_footer(vec!["2 calls".to_string(), String::new(), "1 error".to_string()]).unwrap();
+
+        table.split_column("Endpoint", '/', &["Service", "Route"]).unwrap();
+        assert_eq!(
+            table.to_string(),
+            "Time    Service Route    Status \n======= ======= ======== =======\n\
+             10:00   users   list/all 200    \n10:01   health           500    \n\
+             ------- ------- -------- -------\n2 calls                  1 error\n"
+        );
+        assert_eq!(table.cell_metadata(0, 3).unwrap()["class"], "ok");
+        assert!(table.split_column("Endpoint", '/', &["A"]).is_err());
+
+        table.split_column_regex("Time", r"^(\d+):(\d+)$", &["Hour", "Minute"]).unwrap();
+        assert_eq!(table.row(1).unwrap()[..2], ["10".to_string(), "01".to_string()]);
+        assert_eq!(
+            table.split_column_regex("Route", "(a)", &["A", "B"]).unwrap_err(),
+            "Table::split_column_regex: pattern has 1 capturing groups for 2 column names"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::{Column, Table};
+
+/// A tuple usable as a statically typed row. Implemented for tuples of 1 to 12
+/// Display values; the matching column array has exactly one Column per element.
+pub trait RowTuple {
+    /// An array holding one Column per tuple element
+    type Columns;
+
+    /// Converts the tuple into cell strings
+    fn into_cells(self) -> Vec<String>;
+
+    /// Converts the column array into a Vec
+    fn column_vec(columns: Self::Columns) -> Vec<Column>;
+}
+
+macro_rules! impl_row_tuple {
+    ($len:expr; $($name:ident),+) => {
+        impl<$($name: Display),+> RowTuple for ($($name,)+) {
+            type Columns = [Column; $len];
+
+            #[allow(non_snake_case)]
+            fn into_cells(self) -> Vec<String> {
+                let ($($name,)+) = self;
+                vec![$($name.to_string()),+]
+            }
+
+            fn column_vec(columns: Self::Columns) -> Vec<Column> {
+                columns.into()
+            }
+        }
+    };
+}
+
+impl_row_tuple!(1; A);
+impl_row_tuple!(2; A, B);
+impl_row_tuple!(3; A, B, C);
+impl_row_tuple!(4; A, B, C, D);
+impl_row_tuple!(5; A, B, C, D, E);
+impl_row_tuple!(6; A, B, C, D, E, F);
+impl_row_tuple!(7; A, B, C, D, E, F, G);
+impl_row_tuple!(8; A, B, C, D, E, F, G, H);
+impl_row_tuple!(9; A, B, C, D, E, F, G, H, I);
+impl_row_tuple!(10; A, B, C, D, E, F, G, H, I, J);
+impl_row_tuple!(11; A, B, C, D, E, F, G, H, I, J, K);
+impl_row_tuple!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// A table whose row shape is fixed at compile time, e.g.
+/// `TypedTable<(String, u32, String)>`. The constructor takes exactly one Column per
+/// tuple element and push_row takes a tuple of the row type, so a row with the wrong
+/// number of values is a compile error rather than a runtime one.
+#[derive(Debug)]
+pub struct TypedTable<R: RowTuple> {
+    table: Table,
+    row: PhantomData<fn(R)>,
+}
+
+impl<R: RowTuple> TypedTable<R> {
+    /// Creates a new typed table with one column per row element
+    pub fn new(columns: R::Columns) -> Self {
+        TypedTable {
+            table: Table::with_columns(R::column_vec(columns)),
+            row: PhantomData,
+        }
+    }
+
+    /// Adds a row, converting each value with Display
+    ///
+    /// # Errors
+    /// - If the table would exceed MAX_TABLE_ROWS or a cell exceeds MAX_CELL_LINES
+    pub fn push_row(&mut self, row: R) -> Result<(), String> {
+        self.table.add_row(row.into_cells())
+    }
+
+    /// Returns the underlying table
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Returns the underlying table for configuration (render options, metadata)
+    pub fn table_mut(&mut self) -> &mut Table {
+        &mut self.table
+    }
+
+    /// Consumes the typed table, returning the underlying table
+    pub fn into_table(self) -> Table {
+        self.table
+    }
+}
+
+impl<R: RowTuple> Display for TypedTable<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.table.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Justification;
+
+    #[test]
+    fn test_typed_table() {
+        let mut table: TypedTable<(&str, u32, f64)> = TypedTable::new([
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Age", 0, Justification::Right).unwrap(),
+            Column::new("Score", 0, Justification::Right).unwrap(),
+        ]);
+        table.push_row(("John Doe", 30, 91.5)).unwrap();
+        table.push_row(("Jane Smith", 25, 88.0)).unwrap();
+
+        assert_eq!(table.table().row_count(), 2);
+        assert_eq!(table.table().row(0).unwrap(), ["John Doe", "30", "91.5"]);
+        assert_eq!(table.to_string(), table.into_table().to_string());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Column, Table};
+
+/// Column order of a table produced by Table::union_with
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ColumnOrder {
+    /// The first table's columns, then columns only the second has, in their order
+    #[default]
+    FirstSeen,
+    /// Columns sorted by name
+    Alphabetical,
+    /// The named columns first, in the given order, then the rest in first-seen
+    /// order. Names neither table has are ignored.
+    Given(Vec<String>),
+}
+
+/// Options for Table::union_with
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnionOptions {
+    /// Value for cells of columns a row's source table doesn't have
+    pub placeholder: String,
+    pub order: ColumnOrder,
+}
+
+impl Table {
+    /// Appends the rows of another table, matching columns by name, with the
+    /// default UnionOptions (empty placeholder, first-seen column order)
+    ///
+    /// # Errors
+    /// - If the combined rows exceed the table limits
+    pub fn union(&self, other: &Table) -> Result<Table, String> {
+        self.union_with(other, &UnionOptions::default())
+    }
+
+    /// Combines the rows of two tables whose columns may differ. Columns are
+    /// matched by name and keep the settings of the first table that has them;
+    /// cells of columns a row's table doesn't have are filled with the placeholder.
+    /// This table's rows come first, and row and cell metadata are carried over.
+    /// The render options, header formatter, and sections are taken from this table.
+    ///
+    /// # Errors
+    /// - If the combined rows exceed the table limits
+    pub fn union_with(&self, other: &Table, options: &UnionOptions) -> Result<Table, String> {
+        let mut columns: Vec<&Column> = self.columns.iter().collect();
+        columns.extend(other.columns.iter().filter(|col| self.column_index(&col.name).is_none()));
+        match &options.order {
+            ColumnOrder::FirstSeen => {}
+            ColumnOrder::Alphabetical => columns.sort_by(|a, b| a.name.cmp(&b.name)),
+            ColumnOrder::Given(names) => {
+                let rank = |col: &Column| names.iter().position(|name| *name == col.name).unwrap_or(names.len());
+                columns.sort_by_key(|col| rank(col));
+            }
+        }
+
+        let mut table = Table::with_columns(columns.into_iter().cloned().collect());
+        table.options = self.options.clone();
+        table.header_formatter = self.header_formatter.clone();
+        table.redactions = self.redactions.clone();
+        table.sections = self.sections.clone();
+        for source in [self, other] {
+            // Position of each result column in the source table
+            let sources: Vec<Option<usize>> = table.columns.iter().map(|col| source.column_index(&col.name)).collect();
+            for row in &source.rows {
+                let cells = sources
+                    .iter()
+                    .map(|idx| idx.map_or_else(|| options.placeholder.clone(), |idx| row.cells[idx].clone()))
+                    .collect();
+                table.add_row(cells).map_err(|e| format!("Table::union: {}", e))?;
+                let added = table.rows.last_mut().expect("row was just added");
+                added.metadata = row.metadata.clone();
+                added.cell_metadata = sources
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(new, old)| row.cell_metadata.get(&(*old)?).map(|m| (new, m.clone())))
+                    .collect();
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Justification;
+
+    #[test]
+    fn test_union() {
+        let mut hosts = Table::new(vec![
+            Column::new("Host", 0, Justification::Left).unwrap(),
+            Column::new("Cpu", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        hosts.add_row(vec!["web-1".to_string(), "12".to_string()]).unwrap();
+        hosts.set_cell_metadata(0, 1, "class", "ok").unwrap();
+        let mut disks = Table::with_headers(["Disk", "Host"]).unwrap();
+        disks.add_row(vec!["sda".to_string(), "db-1".to_string()]).unwrap();
+
+        let merged = hosts.union(&disks).unwrap();
+        assert_eq!(merged.to_string(), "Host  Cpu Disk\n===== === ====\nweb-1  12     \ndb-1      sda \n");
+        assert_eq!(merged.cell_metadata(0, 1).unwrap()["class"], "ok");
+        assert_eq!(merged.columns()[1].justification(), Justification::Right);
+
+        let options = UnionOptions { placeholder: "-".to_string(), order: ColumnOrder::Alphabetical };
+        let merged = hosts.union_with(&disks, &options).unwrap();
+        assert_eq!(merged.row(0), Some(&["12".to_string(), "-".to_string(), "web-1".to_string()][..]));
+        assert!(merged.cell_metadata(0, 0).is_some());
+
+        let options = UnionOptions { order: ColumnOrder::Given(vec!["Disk".to_string()]), ..Default::default() };
+        let merged = hosts.union_with(&disks, &options).unwrap();
+        let names: Vec<&str> = merged.columns().iter().map(Column::name).collect();
+        assert_eq!(names, ["Disk", "Host", "Cpu"]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use crate::{cell_width, is_number, AlignMode, Justification, Table, MAX_CELL_LINES, MAX_TABLE_ROWS};
+
+/// A likely data problem found by Table::validate. Cell problems are reported
+/// once per column, with the number of affected rows and the first of them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lint {
+    /// Cells contain control characters other than newlines (tabs, carriage
+    /// returns, escapes), which throw off alignment
+    ControlCharacters { column: usize, rows: usize, first_row: usize },
+    /// Cells contain ANSI escape sequences, which other output formats (HTML,
+    /// JSON, images) carry through as text
+    AnsiCodes { column: usize, rows: usize, first_row: usize },
+    /// A few values are far longer than is typical for the column, widening it
+    /// for every row (see Column::set_truncate_percentile)
+    MixedLengths { column: usize, median: usize, longest: usize },
+    /// Every value is a number but the column is left-justified (and not AlignMode::Auto)
+    NumericLeftJustified { column: usize },
+    /// A cell has nearly MAX_CELL_LINES (5000) lines
+    NearLineLimit { column: usize, row: usize, lines: usize },
+    /// The table has nearly MAX_TABLE_ROWS (5,000,000) rows
+    NearRowLimit { rows: usize },
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lint::ControlCharacters { column, rows, first_row } => write!(
+                f,
+                "column {}: {} cell(s) contain control characters (first in row {})",
+                column, rows, first_row
+            ),
+            Lint::AnsiCodes { column, rows, first_row } => write!(
+                f,
+                "column {}: {} cell(s) contain ANSI escape codes (first in row {})",
+                column, rows, first_row
+            ),
+            Lint::MixedLengths { column, median, longest } => write!(
+                f,
+                "column {}: longest value is {} characters but the median is {}",
+                column, longest, median
+            ),
+            Lint::NumericLeftJustified { column } => {
+                write!(f, "column {}: values are all numbers but the column is left-justified", column)
+            }
+            Lint::NearLineLimit { column, row, lines } => write!(
+                f,
+                "row {}, column {}: cell has {} lines (limit {})",
+                row, column, lines, MAX_CELL_LINES
+            ),
+            Lint::NearRowLimit { rows } => write!(f, "table has {} rows (limit {})", rows, MAX_TABLE_ROWS),
+        }
+    }
+}
+
+/// Longest values at least this long and this many times the median are reported
+const MIXED_LENGTH_MIN: usize = 40;
+const MIXED_LENGTH_RATIO: usize = 8;
+
+/// Fraction (in tenths) of a limit at which it is reported as near
+const NEAR_LIMIT_TENTHS: usize = 9;
+
+impl Table {
+    /// Checks the table for data problems that make for poor output: control
+    /// characters or ANSI codes in cells, columns whose longest values dwarf the
+    /// rest, numeric columns left-justified, and cells or tables near the size
+    /// limits. Returns the problems found, each printable as a message.
+    pub fn validate(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        let near_row_limit = MAX_TABLE_ROWS / 10 * NEAR_LIMIT_TENTHS;
+        if self.rows.len() >= near_row_limit {
+            lints.push(Lint::NearRowLimit { rows: self.rows.len() });
+        }
+        for (idx, col) in self.columns.iter().enumerate() {
+            let values = || self.rows.iter().map(move |row| row.cells[idx].as_str());
+
+            let mut control = (0, 0);
+            let mut ansi = (0, 0);
+            for (row, value) in values().enumerate() {
+                if value.contains("\x1b[") {
+                    ansi = (ansi.0 + 1, if ansi.0 == 0 { row } else { ansi.1 });
+                } else if value.chars().any(|c| c.is_control() && c != '\n') {
+                    control = (control.0 + 1, if control.0 == 0 { row } else { control.1 });
+                }
+                let lines = value.split('\n').count();
+                if lines >= MAX_CELL_LINES / 10 * NEAR_LIMIT_TENTHS {
+                    lints.push(Lint::NearLineLimit { column: idx, row, lines });
+                }
+            }
+            if control.0 > 0 {
+                lints.push(Lint::ControlCharacters { column: idx, rows: control.0, first_row: control.1 });
+            }
+            if ansi.0 > 0 {
+                lints.push(Lint::AnsiCodes { column: idx, rows: ansi.0, first_row: ansi.1 });
+            }
+
+            let mut lengths: Vec<usize> = values().map(cell_width).collect();
+            if !lengths.is_empty() {
+                let mid = lengths.len() / 2;
+                let median = *lengths.select_nth_unstable(mid).1;
+                let longest = lengths.iter().copied().max().unwrap_or(0);
+                if longest >= MIXED_LENGTH_MIN && longest > median.max(1) * MIXED_LENGTH_RATIO {
+                    lints.push(Lint::MixedLengths { column: idx, median, longest });
+                }
+            }
+
+            let mut filled = values().filter(|v| !v.trim().is_empty()).peekable();
+            let auto = col.align_mode == AlignMode::Auto;
+            if col.justification == Justification::Left && !auto && filled.peek().is_some() && filled.all(is_number) {
+                lints.push(Lint::NumericLeftJustified { column: idx });
+            }
+        }
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+
+    #[test]
+    fn test_validate() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Count", 0, Justification::Left).unwrap(),
+            Column::new("Size", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        for (name, count) in [("a\tb", "1"), ("\x1b[31mred\x1b[0m", "2,5"), ("c", ""), ("d\r", "NaN")] {
+            table.add_row(vec![name.to_string(), count.to_string(), "10".to_string()]).unwrap();
+        }
+        table.add_row(vec!["e".to_string(), "4".to_string(), "x".repeat(50)]).unwrap();
+
+        let lints = table.validate();
+        assert_eq!(
+            lints,
+            [
+                Lint::ControlCharacters { column: 0, rows: 2, first_row: 0 },
+                Lint::AnsiCodes { column: 0, rows: 1, first_row: 1 },
+                Lint::MixedLengths { column: 2, median: 2, longest: 50 },
+            ]
+        );
+        assert_eq!(lints[0].to_string(), "column 0: 2 cell(s) contain control characters (first in row 0)");
+
+        table.retain_rows(|row| row[1] == "1" || row[1] == "4");
+        assert_eq!(table.validate()[1], Lint::NumericLeftJustified { column: 1 });
+    }
+}
@@ -0,0 +1,405 @@
+use std::io::{self, Read, Write};
+
+use crate::pager::terminal_size;
+use crate::{take_width, text_width, Table};
+
+/// Columns moved per horizontal scroll step
+const SCROLL_STEP: usize = 8;
+
+const HELP: &str = "arrows/hjkl scroll, space/b page, 1-9 sort, 0 unsort, / search, n next, q quit";
+
+/// Keys the viewer acts on, decoded from terminal input
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Backspace,
+    Escape,
+    Char(char),
+}
+
+/// Decodes one read from the terminal. Escape sequences for special keys arrive
+/// in a single read; a lone escape byte is the Escape key.
+fn parse_key(bytes: &[u8]) -> Option<Key> {
+    let key = match bytes {
+        [] => return None,
+        [0x1b] => Key::Escape,
+        [0x1b, b'[' | b'O', rest @ ..] => match rest {
+            [b'A', ..] => Key::Up,
+            [b'B', ..] => Key::Down,
+            [b'C', ..] => Key::Right,
+            [b'D', ..] => Key::Left,
+            [b'H', ..] | [b'1', b'~', ..] => Key::Home,
+            [b'F', ..] | [b'4', b'~', ..] => Key::End,
+            [b'5', b'~', ..] => Key::PageUp,
+            [b'6', b'~', ..] => Key::PageDown,
+            _ => return None,
+        },
+        [b'\r' | b'\n', ..] => Key::Enter,
+        [0x7f | 0x08, ..] => Key::Backspace,
+        _ => Key::Char(std::str::from_utf8(bytes).ok()?.chars().next()?),
+    };
+    Some(key)
+}
+
+/// Returns the part of a line that is visible when scrolled `left` columns to the
+/// right in a window `width` columns wide
+fn visible(line: &str, left: usize, width: usize) -> &str {
+    take_width(&line[take_width(line, left).len()..], width)
+}
+
+/// Where the viewer reads keys from and draws to
+trait Screen {
+    fn read_key(&mut self) -> io::Result<Option<Key>>;
+    /// Returns the (columns, rows) to draw in
+    fn size(&self) -> (usize, usize);
+    fn output(&mut self) -> &mut dyn Write;
+}
+
+/// Puts the terminal in raw mode on an alternate screen, restoring it when dropped.
+/// The crate has no dependencies, so rather than a terminal library such as
+/// crossterm this drives the Unix `stty` command on /dev/tty.
+struct RawTerminal {
+    tty: std::fs::File,
+    saved: String,
+    restored: bool,
+}
+
+impl RawTerminal {
+    #[cfg(unix)]
+    fn open() -> io::Result<Self> {
+        use std::process::{Command, Stdio};
+
+        let tty = std::fs::OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        let stty = |args: &[&str]| -> io::Result<String> {
+            let output = Command::new("stty").args(args).stdin(tty.try_clone()?).stderr(Stdio::null()).output()?;
+            if !output.status.success() {
+                return Err(io::Error::other("stty failed"));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        let mut terminal = RawTerminal { tty, saved, restored: false };
+        // Alternate screen, hidden cursor
+        terminal.tty.write_all(b"\x1b[?1049h\x1b[?25l")?;
+        Ok(terminal)
+    }
+
+    #[cfg(not(unix))]
+    fn open() -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Table::view_interactive: no terminal support on this platform"))
+    }
+
+    /// Leaves the alternate screen and puts back the terminal settings saved on open
+    fn restore(&mut self) -> io::Result<()> {
+        self.restored = true;
+        self.tty.write_all(b"\x1b[?25h\x1b[?1049l")?;
+        #[cfg(unix)]
+        {
+            let status = std::process::Command::new("stty").arg(&self.saved).stdin(self.tty.try_clone()?).status()?;
+            if !status.success() {
+                return Err(io::Error::other("stty could not restore the terminal settings"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Screen for RawTerminal {
+    fn read_key(&mut self) -> io::Result<Option<Key>> {
+        let mut buf = [0u8; 16];
+        let len = self.tty.read(&mut buf)?;
+        Ok(parse_key(&buf[..len]))
+    }
+
+    fn size(&self) -> (usize, usize) {
+        terminal_size().unwrap_or((80, 24))
+    }
+
+    fn output(&mut self) -> &mut dyn Write {
+        &mut self.tty
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        // Reached without restore() only when unwinding; say so rather than
+        // silently leave the terminal in raw mode
+        if !self.restored {
+            if let Err(e) = self.restore() {
+                eprintln!("Table::view_interactive: {}; run `stty sane` to reset the terminal", e);
+            }
+        }
+    }
+}
+
+/// Viewer state: the (possibly sorted) table and the scroll position within it
+struct Viewer<'a> {
+    original: &'a Table,
+    table: Table,
+    lines: Vec<String>,
+    header_lines: usize,
+    top: usize,
+    left: usize,
+    sort: Option<(usize, bool)>,
+    search: Option<String>,
+    status: String,
+}
+
+impl<'a> Viewer<'a> {
+    fn new(table: &'a Table) -> Self {
+        let mut viewer = Viewer {
+            original: table,
+            table: table.clone(),
+            lines: Vec::new(),
+            header_lines: table.header_line_count(),
+            top: 0,
+            left: 0,
+            sort: None,
+            search: None,
+            status: HELP.to_string(),
+        };
+        viewer.rerender();
+        viewer
+    }
+
+    fn rerender(&mut self) {
+        self.lines = self.table.to_string().lines().map(str::to_string).collect();
+    }
+
+    fn body_len(&self) -> usize {
+        self.lines.len().saturating_sub(self.header_lines)
+    }
+
+    /// Sorts by the given column, toggling between ascending and descending when
+    /// the table is already sorted by it
+    fn sort_by(&mut self, column: usize) {
+        if column >= self.table.columns().len() {
+            self.status = format!("no column {}", column + 1);
+            return;
+        }
+        let descending = self.sort == Some((column, false));
+        self.table = self.original.clone();
+        let _ = self.table.sort_by_column(column, descending);
+        self.sort = Some((column, descending));
+        self.top = 0;
+        self.status = format!(
+            "sorted by {} ({})",
+            self.table.columns()[column].name(),
+            if descending { "descending" } else { "ascending" }
+        );
+        self.rerender();
+    }
+
+    fn unsort(&mut self) {
+        self.table = self.original.clone();
+        self.sort = None;
+        self.status = "original order".to_string();
+        self.rerender();
+    }
+
+    /// Scrolls to the next body line after the top one containing the search text
+    /// (ignoring case), wrapping around to the start
+    fn find_next(&mut self) {
+        let Some(query) = self.search.as_ref().map(|s| s.to_lowercase()) else {
+            return;
+        };
+        let body = &self.lines[self.header_lines.min(self.lines.len())..];
+        let len = body.len();
+        let found = (1..=len)
+            .map(|step| (self.top + step) % len)
+            .find(|&idx| body[idx].to_lowercase().contains(&query));
+        self.status = match found {
+            Some(idx) => {
+                self.top = idx;
+                format!("/{}", query)
+            }
+            None => format!("/{}: not found", query),
+        };
+    }
+
+    fn draw(&self, out: &mut dyn Write, (columns, rows): (usize, usize)) -> io::Result<()> {
+        let page = rows.saturating_sub(self.header_lines + 1);
+        let mut screen = String::from("\x1b[H\x1b[2J");
+        let header = self.lines.iter().take(self.header_lines);
+        let body = self.lines.iter().skip(self.header_lines + self.top).take(page);
+        for line in header.chain(body) {
+            screen.push_str(visible(line, self.left, columns));
+            screen.push_str("\r\n");
+        }
+        // Status line in reverse video on the last row
+        let last = (self.top + page).min(self.body_len());
+        let position = format!("{}-{} of {} lines  ", (self.top + 1).min(last), last, self.body_len());
+        let status = format!("{}{}", position, self.status);
+        screen.push_str(&format!("\x1b[{};1H\x1b[7m{}\x1b[0m", rows, take_width(&status, columns)));
+        out.write_all(screen.as_bytes())?;
+        out.flush()
+    }
+
+    /// Reads a search query on the status line. Returns None if cancelled with Escape.
+    fn prompt(&mut self, terminal: &mut impl Screen, size: (usize, usize)) -> io::Result<Option<String>> {
+        let mut query = String::new();
+        loop {
+            self.status = format!("/{}", query);
+            self.draw(terminal.output(), size)?;
+            match terminal.read_key()? {
+                Some(Key::Enter) => return Ok(Some(query)),
+                Some(Key::Escape) => return Ok(None),
+                Some(Key::Backspace) => {
+                    query.pop();
+                }
+                Some(Key::Char(c)) if !c.is_control() => query.push(c),
+                _ => {}
+            }
+        }
+    }
+
+    fn run(&mut self, terminal: &mut impl Screen) -> io::Result<()> {
+        loop {
+            let size = terminal.size();
+            let page = size.1.saturating_sub(self.header_lines + 1).max(1);
+            let max_top = self.body_len().saturating_sub(page);
+            let max_left = self.lines.iter().map(|line| text_width(line)).max().unwrap_or(0).saturating_sub(size.0);
+            self.top = self.top.min(max_top);
+            self.left = self.left.min(max_left);
+            self.draw(terminal.output(), size)?;
+
+            match terminal.read_key()? {
+                Some(Key::Char('q') | Key::Escape) => return Ok(()),
+                Some(Key::Up | Key::Char('k')) => self.top = self.top.saturating_sub(1),
+                Some(Key::Down | Key::Char('j')) => self.top += 1,
+                Some(Key::PageUp | Key::Char('b')) => self.top = self.top.saturating_sub(page),
+                Some(Key::PageDown | Key::Char(' ')) => self.top += page,
+                Some(Key::Home | Key::Char('g')) => self.top = 0,
+                Some(Key::End | Key::Char('G')) => self.top = max_top,
+                Some(Key::Left | Key::Char('h')) => self.left = self.left.saturating_sub(SCROLL_STEP),
+                Some(Key::Right | Key::Char('l')) => self.left += SCROLL_STEP,
+                Some(Key::Char('0')) => self.unsort(),
+                Some(Key::Char(c @ '1'..='9')) => self.sort_by(c as usize - '1' as usize),
+                Some(Key::Char('/')) => {
+                    if let Some(query) = self.prompt(terminal, size)? {
+                        self.search = Some(query).filter(|q| !q.is_empty());
+                        self.find_next();
+                    } else {
+                        self.status = HELP.to_string();
+                    }
+                }
+                Some(Key::Char('n')) => self.find_next(),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Table {
+    /// Shows the table in a full-screen terminal viewer. The header stays in place
+    /// while rows scroll with the arrow keys (or hjkl), space and b page down and up,
+    /// digit keys 1-9 sort by that column (pressing again reverses it, 0 restores the
+    /// original order), / searches, n finds the next match, and q or Escape quits.
+    /// Returns an error if there is no terminal to draw on, or if the terminal's
+    /// settings could not be restored afterwards.
+    ///
+    /// The viewer is Unix-only: to keep the crate free of dependencies it switches
+    /// the terminal to raw mode with the `stty` command instead of a library such
+    /// as crossterm, and returns an Unsupported error on other platforms.
+    pub fn view_interactive(&self) -> io::Result<()> {
+        let mut terminal = RawTerminal::open()?;
+        let result = Viewer::new(self).run(&mut terminal);
+        let restored = terminal.restore();
+        result.and(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Justification};
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key(b"\x1b[A"), Some(Key::Up));
+        assert_eq!(parse_key(b"\x1b[6~"), Some(Key::PageDown));
+        assert_eq!(parse_key(b"\x1b"), Some(Key::Escape));
+        assert_eq!(parse_key(b"\r"), Some(Key::Enter));
+        assert_eq!(parse_key("é".as_bytes()), Some(Key::Char('é')));
+        assert_eq!(parse_key(b"\x1b[Z"), None);
+        assert_eq!(visible("abcdefgh", 2, 3), "cde");
+        assert_eq!(visible("abc", 5, 3), "");
+    }
+
+    #[test]
+    fn test_viewer_sort_and_search() {
+        let mut table = Table::new(vec![
+            Column::new("Name", 0, Justification::Left).unwrap(),
+            Column::new("Size", 0, Justification::Right).unwrap(),
+        ]).unwrap();
+        for (name, size) in [("b", "2"), ("a", "10"), ("c", "1")] {
+            table.add_row(vec![name.to_string(), size.to_string()]).unwrap();
+        }
+        let mut viewer = Viewer::new(&table);
+        viewer.sort_by(1);
+        assert_eq!(viewer.lines[2], "c       1");
+        viewer.sort_by(1);
+        assert_eq!(viewer.lines[2], "a      10");
+        viewer.unsort();
+        assert_eq!(viewer.lines[2], "b       2");
+
+        viewer.search = Some("C".to_string());
+        viewer.find_next();
+        assert_eq!(viewer.top, 2);
+
+        let mut screen = Vec::new();
+        viewer.top = 0;
+        viewer.draw(&mut screen, (6, 5)).unwrap();
+        let screen = String::from_utf8(screen).unwrap();
+        assert!(screen.starts_with("\x1b[H\x1b[2JName S\r\n==== =\r\nb     \r\na     \r\n\x1b[5;1H"));
+    }
+
+    /// Plays keys to the viewer, then quits
+    struct Scripted {
+        keys: std::collections::VecDeque<Key>,
+        screen: Vec<u8>,
+    }
+
+    impl Screen for Scripted {
+        fn read_key(&mut self) -> io::Result<Option<Key>> {
+            Ok(Some(self.keys.pop_front().unwrap_or(Key::Char('q'))))
+        }
+
+        fn size(&self) -> (usize, usize) {
+            (20, 4)
+        }
+
+        fn output(&mut self) -> &mut dyn Write {
+            &mut self.screen
+        }
+    }
+
+    #[test]
+    fn test_viewer_run() {
+        let mut table = Table::with_headers(["Name"]).unwrap();
+        for name in ["delta", "alpha", "charlie", "bravo"] {
+            table.add_row(vec![name.to_string()]).unwrap();
+        }
+        let keys = [Key::Char('1'), Key::Down, Key::Char('/'), Key::Char('d'), Key::Enter, Key::Char('q'), Key::Down];
+        let mut terminal = Scripted { keys: keys.into(), screen: Vec::new() };
+        let mut viewer = Viewer::new(&table);
+        viewer.run(&mut terminal).unwrap();
+
+        // Sorted, scrolled down one row, then moved to the match; the key after q is never read
+        assert_eq!(viewer.top, 3);
+        assert_eq!(terminal.keys, [Key::Down]);
+        let screen = String::from_utf8(terminal.screen).unwrap();
+        let last_frame = screen.rsplit("\x1b[H\x1b[2J").next().unwrap();
+        assert!(last_frame.starts_with("Name   \r\n=======\r\ndelta  \r\n"), "{:?}", last_frame);
+        assert!(last_frame.contains("4-4 of 4 lines  /d"));
+    }
+}
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+
+use crate::text_width;
+
+/// Ranges of characters that take no columns: combining marks, zero-width
+/// spaces and joiners, and variation selectors
+const ZERO_WIDTH: [(u32, u32); 9] = [
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+];
+
+/// Ranges of East Asian wide and fullwidth characters and emoji, which take two
+/// columns in a terminal
+const WIDE: [(u32, u32); 18] = [
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE30, 0xFE4F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F900, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], c: u32) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Returns the length of the ANSI escape sequence at the start of s, or 0 if it
+/// doesn't start with one: CSI sequences such as colors ("\x1b[31m"), and OSC
+/// sequences such as hyperlinks, ended by BEL or ESC \. An unterminated
+/// sequence runs to the end of s.
+pub(crate) fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&0x1b) {
+        return 0;
+    }
+    match bytes.get(1) {
+        Some(b'[') => bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)).map_or(s.len(), |pos| pos + 3),
+        Some(b']') => {
+            let body = &bytes[2..];
+            let bel = body.iter().position(|&b| b == 0x07).map(|pos| pos + 3);
+            let st = body.windows(2).position(|pair| pair == b"\x1b\\").map(|pos| pos + 4);
+            match (bel, st) {
+                (Some(bel), Some(st)) => bel.min(st),
+                (end, None) | (None, end) => end.unwrap_or(s.len()),
+            }
+        }
+        Some(b) if b.is_ascii() => 2,
+        _ => 1,
+    }
+}
+
+/// Iterates over the visible characters of a line and their byte positions,
+/// skipping ANSI escape sequences
+pub(crate) fn visible_chars(s: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || loop {
+        let rest = &s[pos..];
+        let skip = escape_len(rest);
+        if skip > 0 {
+            pos += skip;
+            continue;
+        }
+        let c = rest.chars().next()?;
+        let at = pos;
+        pos += c.len_utf8();
+        return Some((at, c));
+    })
+}
+
+/// Returns the ANSI escape sequences in s, in order, so the color state at the
+/// end of s can be restored after cutting it out
+pub(crate) fn escapes(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('\x1b') {
+        let len = escape_len(&rest[start..]);
+        out.push_str(&rest[start..start + len]);
+        rest = &rest[start + len..];
+    }
+    out
+}
+
+/// Returns s without its ANSI escape sequences
+pub(crate) fn strip_escapes(s: &str) -> Cow<'_, str> {
+    if !s.contains('\x1b') {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('\x1b') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + escape_len(&rest[start..])..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Replaces the tabs in a value with spaces up to the next tab stop, every
+/// tab_width columns from the start of each line
+pub(crate) fn expand_tabs(value: &str, tab_width: usize) -> String {
+    let mut out = String::with_capacity(value.len() + tab_width);
+    for (idx, line) in value.split('\n').enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        let mut pieces = line.split('\t');
+        let mut column = 0;
+        if let Some(first) = pieces.next() {
+            out.push_str(first);
+            column = text_width(first);
+        }
+        for piece in pieces {
+            let spaces = tab_width - column % tab_width;
+            out.extend(std::iter::repeat_n(' ', spaces));
+            out.push_str(piece);
+            column += spaces + text_width(piece);
+        }
+    }
+    out
+}
+
+/// Returns the number of terminal columns a character takes: 0 for combining
+/// marks, 2 for wide characters, and 1 for the rest
+pub(crate) fn char_width(c: char) -> usize {
+    let c = c as u32;
+    if c < 0x300 {
+        1
+    } else if in_ranges(&ZERO_WIDTH, c) {
+        0
+    } else if in_ranges(&WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('中'), 2);
+        assert_eq!(char_width('ア'), 2);
+        assert_eq!(char_width('Ａ'), 2);
+        assert_eq!(char_width('😀'), 2);
+        assert_eq!(char_width('→'), 1);
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs("a\tbc\td", 4), "a   bc  d");
+        assert_eq!(expand_tabs("\tx\n東\ty", 4), "    x\n東  y");
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(escape_len("\x1b[1;31mred"), 7);
+        assert_eq!(escape_len("\x1b]8;;http://x\x07link"), 14);
+        assert_eq!(escape_len("red"), 0);
+        let line = "a\x1b[31mb\x1b[0mc";
+        let visible: String = visible_chars(line).map(|(_, c)| c).collect();
+        assert_eq!(visible, "abc");
+        assert_eq!(escapes(line), "\x1b[31m\x1b[0m");
+        assert_eq!(strip_escapes("\x1b]8;;http://x\x1b\\link\x1b]8;;\x1b\\ \x1b[1mbold"), "link bold");
+    }
+}
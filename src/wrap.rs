@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+
+use crate::{take_width, text_width, Column};
+
+/// What happens to lines too wide for their column
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Overflow {
+    /// Cut the line to the width, marking the cut with the column's ellipsis
+    #[default]
+    Truncate,
+    /// Break the line at spaces into as many lines as it takes. Words wider than
+    /// the column are truncated.
+    Wrap,
+    /// Break the line every width characters, wherever that falls, so long
+    /// tokens such as URLs and hashes are kept whole
+    CharWrap,
+}
+
+impl Column {
+    /// Sets what happens to lines too wide for the column, which only happens when
+    /// its width is capped (a truncation width, percentile, or fixed width).
+    /// Wrapped cells make their rows taller in the text renderer.
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Returns what happens to lines too wide for the column
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Returns true if the line is too wide for the width and would be broken
+    /// into several lines rather than truncated
+    pub(crate) fn wraps(&self, line: &str, width: usize) -> bool {
+        self.overflow != Overflow::Truncate && text_width(line) > width
+    }
+
+    /// Breaks a line into lines that fit the width, as the overflow mode says
+    pub(crate) fn wrap<'a>(&self, line: &'a str, width: usize) -> Vec<Cow<'a, str>> {
+        if !self.wraps(line, width) {
+            return vec![Cow::Borrowed(line)];
+        }
+        let lines = match self.overflow {
+            Overflow::CharWrap => wrap_chars(line, width),
+            _ => wrap_words(line, width),
+        };
+        lines.into_iter().map(Cow::Borrowed).collect()
+    }
+}
+
+/// Breaks a line at spaces into lines of at most width, except for words that
+/// are wider on their own. The spaces at each break are dropped.
+fn wrap_words(line: &str, width: usize) -> Vec<&str> {
+    let mut lines = Vec::new();
+    // Byte range of the line being built
+    let mut start = None;
+    let mut end = 0;
+    let mut used = 0;
+    for (pos, word) in line.split(' ').scan(0, |pos, word| {
+        let at = *pos;
+        *pos += word.len() + 1;
+        Some((at, word))
+    }) {
+        if word.is_empty() {
+            continue;
+        }
+        let word_width = text_width(word);
+        match start {
+            Some(first) if used + 1 + word_width > width => {
+                lines.push(&line[first..end]);
+                start = Some(pos);
+                used = word_width;
+            }
+            Some(_) => used += 1 + word_width,
+            None => {
+                start = Some(pos);
+                used = word_width;
+            }
+        }
+        end = pos + word.len();
+    }
+    match start {
+        Some(first) => lines.push(&line[first..end]),
+        None => lines.push(""),
+    }
+    lines
+}
+
+/// Breaks a line into pieces of width, except the last (and ones that end before
+/// a wide character that would straddle the edge)
+fn wrap_chars(mut line: &str, width: usize) -> Vec<&str> {
+    let mut lines = Vec::new();
+    while text_width(line) > width {
+        // A wide character in a one-column width gets a line to itself
+        let piece = match take_width(line, width) {
+            "" => &line[..line.chars().next().map_or(0, char::len_utf8)],
+            piece => piece,
+        };
+        lines.push(piece);
+        line = &line[piece.len()..];
+    }
+    lines.push(line);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Justification, Table};
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap_words("the quick  brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap_words("a verylongword b", 5), ["a", "verylongword", "b"]);
+
+        let mut table = Table::new(vec![
+            Column::new("Id", 0, Justification::Left).unwrap(),
+            Column::new("Note", 12, Justification::Left).unwrap(),
+        ]).unwrap();
+        table.columns[1].set_overflow(Overflow::Wrap);
+        table.add_row(vec!["1".to_string(), "disk usage above the limit".to_string()]).unwrap();
+        table.add_row(vec!["2".to_string(), "ok".to_string()]).unwrap();
+        let expected = "Id Note        \n== ============\n1  disk usage  \n   above the   \n   limit       \n2  ok          \n";
+        assert_eq!(table.to_string(), expected);
+        assert_eq!(table.estimated_render_len(), expected.len());
+
+        table.columns[1].set_overflow(Overflow::CharWrap);
+        table.add_row(vec!["3".to_string(), "https://example.com/a/b".to_string()]).unwrap();
+        assert!(table.to_string().ends_with("3  https://exam\n   ple.com/a/b \n"));
+        assert_eq!(wrap_chars("abcdefg", 3), ["abc", "def", "g"]);
+    }
+}